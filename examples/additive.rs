@@ -0,0 +1,65 @@
+//! Draws overlapping text with additive blending.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::render::BlendMode;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 additive blending example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+    font_texture.set_blend_mode(BlendMode::Add);
+
+    let font = include_bytes!("roboto/Roboto-Bold.ttf") as &[u8];
+    let roboto_bold = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_bold];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        x: 20.0,
+        y: 20.0,
+        ..LayoutSettings::default()
+    });
+    let color = Color::RGB(0x80, 0x30, 0x10);
+    layout.append(fonts, &TextStyle::with_user_data("Glow", 128.0, 0, color));
+
+    canvas.set_draw_color(Color::RGB(0x10, 0x10, 0x20));
+    canvas.clear();
+    // The overlapping copies add up into a bright orange where they
+    // overlap the most.
+    for offset in 0..6 {
+        let offset = (offset * 3, offset * 2);
+        font_texture.draw_text_at(&mut canvas, fonts, layout.glyphs(), offset)?;
+    }
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+//! Compares drawing a paragraph one glyph at a time and batched into a single draw call.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use std::time::Instant;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 batched example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        x: 10.0,
+        y: 10.0,
+        max_width: Some(780.0),
+        ..LayoutSettings::default()
+    });
+    let paragraph = "The quick brown fox jumps over the lazy dog. ".repeat(12);
+    let color = Color::RGB(0xFF, 0xFF, 0xFF);
+    layout.append(
+        fonts,
+        &TextStyle::with_user_data(&paragraph, 20.0, 0, color),
+    );
+
+    // Draws the paragraph a hundred times with separate copies for
+    // each glyph, and then with all the glyphs batched into one draw
+    // call, and prints how long each took.
+    const FRAMES: u32 = 100;
+    for batched in [false, true] {
+        font_texture.set_batched(batched);
+        let start = Instant::now();
+        let mut draw_calls = 0;
+        for _ in 0..FRAMES {
+            canvas.set_draw_color(Color::RGB(0x22, 0x22, 0x22));
+            canvas.clear();
+            let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs())?;
+            draw_calls = report.draw_calls;
+            canvas.present();
+        }
+        println!(
+            "batched: {}, glyphs: {}, draw calls per frame: {}, time per frame: {:?}",
+            batched,
+            layout.glyphs().len(),
+            draw_calls,
+            start.elapsed() / FRAMES,
+        );
+    }
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
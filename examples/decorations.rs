@@ -0,0 +1,91 @@
+//! Draws underlined and struck-through text. The underlined link
+//! mixes two fonts, and each part's underline is placed by the
+//! underline metrics of its font.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::{Decoration, DecorationStyle, FontTexture, UnderlineMetrics};
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 decorations example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    font_texture.set_underline_metrics(&roboto_regular, UnderlineMetrics::from_font_data(font, 0));
+    let font = include_bytes!("playfair-display/PlayfairDisplay-Regular.ttf") as &[u8];
+    let playfair_display = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    font_texture
+        .set_underline_metrics(&playfair_display, UnderlineMetrics::from_font_data(font, 0));
+    let fonts = &[roboto_regular, playfair_display];
+    let text_color = Color::RGB(0xFF, 0xFF, 0xFF);
+    let link_color = Color::RGB(0x66, 0xAA, 0xFF);
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        x: 20.0,
+        y: 20.0,
+        max_width: Some(760.0),
+        ..LayoutSettings::default()
+    });
+    let mut append = |text: &str, font_index: usize, color: Color| {
+        let start = layout.glyphs().len();
+        layout.append(
+            fonts,
+            &TextStyle::with_user_data(text, 32.0, font_index, color),
+        );
+        start..layout.glyphs().len()
+    };
+    append("Read the ", 0, text_color);
+    let link_start = append("documentation ", 0, link_color).start;
+    let link_end = append("of fontdue-sdl2", 1, link_color).end;
+    append(" for ", 0, text_color);
+    let struck = append("some", 0, text_color);
+    append(" all the details.", 0, text_color);
+    let decorations = [
+        Decoration {
+            glyphs: link_start..link_end,
+            style: DecorationStyle::Underline,
+            color: link_color,
+        },
+        Decoration {
+            glyphs: struck,
+            style: DecorationStyle::Strikethrough,
+            color: text_color,
+        },
+    ];
+
+    canvas.clear();
+    font_texture.draw_text_decorated(&mut canvas, fonts, layout.glyphs(), &decorations)?;
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! Draws a line of text truncated with an ellipsis to fit boxes of different widths.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 ellipsis example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+
+    canvas.set_draw_color(Color::RGB(0x31, 0x87, 0xC0));
+    canvas.clear();
+    // The same line truncated to narrower and narrower boxes.
+    let text = "Some long text that doesn't fit in its box";
+    for (i, width) in [600, 300, 150, 60].iter().enumerate() {
+        let y = 20 + i as i32 * 50;
+        layout.reset(&LayoutSettings {
+            x: 20.0,
+            y: y as f32,
+            ..LayoutSettings::default()
+        });
+        layout.append(
+            fonts,
+            &TextStyle::with_user_data(text, 24.0, 0, Color::WHITE),
+        );
+        canvas.set_draw_color(Color::RGB(0x24, 0x64, 0x8E));
+        canvas.fill_rect(Rect::new(20, y, *width, 30))?;
+        font_texture.draw_text_ellipsized(&mut canvas, fonts, layout.glyphs(), *width as f32)?;
+    }
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Draws upscaled text with nearest neighbor and linear filtering.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::{FontTexture, ScaleMode};
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 filtering example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let color = Color::RGB(0xFF, 0xFF, 0xFF);
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+
+    // Small text scaled up 6x: blocky with nearest neighbor filtering
+    // on top, and smooth with linear filtering below it.
+    canvas.set_scale(6.0, 6.0)?;
+    canvas.set_draw_color(Color::RGB(0x22, 0x22, 0x22));
+    canvas.clear();
+    let scale_modes = [ScaleMode::Nearest, ScaleMode::Linear];
+    let mut font_textures = Vec::new();
+    for scale_mode in &scale_modes {
+        let mut font_texture = FontTexture::new(&texture_creator)?;
+        font_texture.set_scale_mode(*scale_mode)?;
+        font_textures.push(font_texture);
+    }
+    for (i, (scale_mode, font_texture)) in scale_modes.iter().zip(&mut font_textures).enumerate() {
+        layout.reset(&LayoutSettings {
+            x: 4.0,
+            y: 4.0 + i as f32 * 40.0,
+            ..LayoutSettings::default()
+        });
+        let text = format!("{:?}", scale_mode);
+        layout.append(fonts, &TextStyle::with_user_data(&text, 24.0, 0, color));
+        font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+    }
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
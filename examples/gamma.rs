@@ -0,0 +1,81 @@
+//! Draws small text with different gamma values and coverage curves
+//! side by side.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 gamma example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let color = Color::RGB(0, 0, 0);
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    let text = "Small text, with gamma ";
+
+    canvas.set_draw_color(Color::RGB(0xFF, 0xFF, 0xFF));
+    canvas.clear();
+    // The same text with increasing gamma, from top to bottom. The
+    // glyphs for each gamma get their own cache entries.
+    for (i, gamma) in [1.0, 1.8, 2.2].iter().enumerate() {
+        layout.reset(&LayoutSettings {
+            x: 20.0,
+            y: 20.0 + i as f32 * 30.0,
+            ..LayoutSettings::default()
+        });
+        let text = format!("{}{:.1}", text, gamma);
+        layout.append(fonts, &TextStyle::with_user_data(&text, 13.0, 0, color));
+        font_texture.set_gamma(*gamma);
+        font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+    }
+
+    // With a coverage curve boosting the faint edges of thin strokes
+    // on top of the gamma.
+    layout.reset(&LayoutSettings {
+        x: 20.0,
+        y: 110.0,
+        ..LayoutSettings::default()
+    });
+    let text = "Small text, with gamma 2.2 and a darkening coverage curve";
+    layout.append(fonts, &TextStyle::with_user_data(text, 13.0, 0, color));
+    font_texture.set_coverage_curve(|coverage| {
+        let coverage = coverage as f32 / 255.0;
+        (coverage.powf(0.7) * 255.0).round() as u8
+    });
+    font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Draws text with a horizontal color gradient.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 gradient example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Bold.ttf") as &[u8];
+    let roboto_bold = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_bold];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        x: 20.0,
+        y: 20.0,
+        ..LayoutSettings::default()
+    });
+    layout.append(
+        fonts,
+        &TextStyle::with_user_data("RAINBOW", 128.0, 0, Color::WHITE),
+    );
+
+    canvas.set_draw_color(Color::RGB(0x22, 0x22, 0x22));
+    canvas.clear();
+    let start_color = Color::RGB(0xFF, 0x44, 0x22);
+    let end_color = Color::RGB(0x44, 0x66, 0xFF);
+    font_texture.draw_text_gradient(&mut canvas, fonts, layout.glyphs(), start_color, end_color)?;
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+//! Draws text on a HiDPI-aware window, with the glyphs rasterized at
+//! the full resolution of the display.
+
+use fontdue::layout::{CoordinateSystem, Layout, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 hidpi example", 800, 600)
+        .position_centered()
+        .allow_highdpi()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    // Draw in logical units, regardless of the display's DPI.
+    let (output_width, _) = canvas.output_size()?;
+    let (window_width, _) = canvas.window().size();
+    let scale = output_width as f32 / window_width as f32;
+    canvas.set_scale(scale, scale)?;
+
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+    font_texture.set_dpi_scale(scale);
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    let text = format!("Hello, World! The DPI scale is {}.", scale);
+    layout.append(
+        fonts,
+        &TextStyle::with_user_data(&text, 24.0, 0, Color::WHITE),
+    );
+
+    canvas.set_draw_color(Color::RGB(0x31, 0x87, 0xC0));
+    canvas.clear();
+    font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
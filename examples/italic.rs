@@ -0,0 +1,65 @@
+//! Draws faux-italic text by shearing upright glyphs.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 italic example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let color = Color::RGB(0xFF, 0xFF, 0xFF);
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+
+    canvas.set_draw_color(Color::RGB(0x22, 0x22, 0x22));
+    canvas.clear();
+    for (i, slant) in [0.0, 0.2, 0.4].iter().enumerate() {
+        let y = 20.0 + i as f32 * 80.0;
+        layout.reset(&LayoutSettings {
+            x: 20.0,
+            y,
+            ..LayoutSettings::default()
+        });
+        let text = format!("Slanted by {}", slant);
+        layout.append(fonts, &TextStyle::with_user_data(&text, 48.0, 0, color));
+        // Shear the glyphs to the right above the baseline, and to
+        // the left below it, so the baseline stays in place.
+        let baseline = y + layout.lines().unwrap()[0].max_ascent;
+        let italic = [1.0, 0.0, -slant, 1.0, slant * baseline, 0.0];
+        font_texture.draw_text_matrix(&mut canvas, fonts, layout.glyphs(), italic)?;
+    }
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
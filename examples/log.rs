@@ -0,0 +1,90 @@
+//! Draws a log which grows by a line every 100 milliseconds. Only
+//! the new lines are drawn into the log's texture, which scrolls
+//! along with the end of the log, so drawing a frame doesn't get any
+//! slower as the log grows.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::{AppendedText, FontTexture};
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::Point;
+use sdl2::render::BlendMode;
+use std::time::{Duration, Instant};
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 log example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window
+        .into_canvas()
+        .present_vsync()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+    let mut log_texture = texture_creator
+        .create_texture_target(PixelFormatEnum::RGBA32, 760, 560)
+        .map_err(|e| e.to_string())?;
+    log_texture.set_blend_mode(BlendMode::Blend);
+    let mut appended = AppendedText::new();
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        max_width: Some(760.0),
+        ..LayoutSettings::default()
+    });
+    let start = Instant::now();
+    let mut lines = 0;
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+
+        while start.elapsed() > Duration::from_millis(100) * lines {
+            let line = format!("[{:>8.1}] Log line number {}\n", lines as f32 / 10.0, lines);
+            let color = if lines % 10 == 0 {
+                Color::RGB(0xFF, 0xCC, 0x66)
+            } else {
+                Color::WHITE
+            };
+            layout.append(fonts, &TextStyle::with_user_data(&line, 20.0, 0, color));
+            lines += 1;
+        }
+        font_texture.draw_text_appended(
+            &mut canvas,
+            &mut log_texture,
+            &mut appended,
+            fonts,
+            layout.glyphs(),
+        )?;
+
+        canvas.set_draw_color(Color::RGB(0x20, 0x24, 0x30));
+        canvas.clear();
+        appended.copy(&mut canvas, &log_texture, Point::new(20, 20))?;
+        canvas.present();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Draws translucent text with overlapping glyphs, blended and merged.
+
+use fontdue::layout::{CoordinateSystem, GlyphPosition, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 merged example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let color = Color::RGBA(0x22, 0x22, 0x88, 0x99);
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+
+    canvas.set_draw_color(Color::RGB(0xFF, 0xFF, 0xFF));
+    canvas.clear();
+    // Translucent text with the glyphs squeezed together so that
+    // they overlap: drawn separately on top, where the overlaps are
+    // darker, and merged below it.
+    for i in 0..2 {
+        layout.reset(&LayoutSettings {
+            x: 20.0,
+            y: 20.0 + i as f32 * 120.0,
+            ..LayoutSettings::default()
+        });
+        layout.append(
+            fonts,
+            &TextStyle::with_user_data("Overlapping", 96.0, 0, color),
+        );
+        let glyphs = layout
+            .glyphs()
+            .iter()
+            .enumerate()
+            .map(|(i, glyph)| GlyphPosition {
+                x: glyph.x - i as f32 * 12.0,
+                ..*glyph
+            })
+            .collect::<Vec<_>>();
+        if i == 0 {
+            font_texture.draw_text(&mut canvas, fonts, &glyphs)?;
+        } else {
+            font_texture.draw_text_merged(&mut canvas, &texture_creator, fonts, &glyphs)?;
+        }
+    }
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
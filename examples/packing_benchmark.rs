@@ -0,0 +1,142 @@
+//! Measures how long packing glyph-sized rects into a texture atlas
+//! takes, with and without discarding the slivers of empty space
+//! thinner than the glyphs (see `RectPacker::set_min_split_size`).
+//! The amount of empty rects tracked, and the fraction of the atlas
+//! filled before the first rect doesn't fit, are printed too, and
+//! compared to the first-fit packer the glyph cache used before.
+
+use fontdue_sdl2::RectPacker;
+use sdl2::rect::Rect;
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 20;
+const SIZE: u32 = 1024;
+
+pub fn main() {
+    // Glyph-sized rects in a random, but repeatable, order.
+    let mut seed = 7u32;
+    let mut random = |range: u32| {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) % range
+    };
+    let sizes = (0..10_000)
+        .map(|_| (4 + random(28), 8 + random(28)))
+        .collect::<Vec<_>>();
+
+    for &min_split_size in &[0, 4] {
+        let mut total = Duration::ZERO;
+        let mut packed = 0;
+        let mut packed_area = 0;
+        let mut empty_rects = 0;
+        for _ in 0..ROUNDS {
+            let mut packer = RectPacker::new(SIZE, SIZE);
+            packer.set_min_split_size(min_split_size);
+            let start = Instant::now();
+            let rects = sizes
+                .iter()
+                .map_while(|&(width, height)| packer.allocate(width, height))
+                .collect::<Vec<_>>();
+            total += start.elapsed();
+            packed = rects.len();
+            packed_area = rects.iter().map(|rect| rect.width() * rect.height()).sum();
+            empty_rects = packer.empty_rects().len();
+        }
+        println!(
+            "Min split size {}: packed {} rects in {:.2} ms on average, {:.1}% of the atlas, {} empty rects left",
+            min_split_size,
+            packed,
+            total.as_secs_f64() * 1000.0 / ROUNDS as f64,
+            packed_area as f64 / (SIZE * SIZE) as f64 * 100.0,
+            empty_rects,
+        );
+    }
+
+    let mut packer = RectPacker::new(SIZE, SIZE);
+    let mut first_fit = FirstFitPacker::new(SIZE, SIZE);
+    let packed_area = |allocate: &mut dyn FnMut(u32, u32) -> Option<Rect>| {
+        sizes
+            .iter()
+            .map_while(|&(width, height)| allocate(width, height).map(|_| width * height))
+            .sum::<u32>() as f64
+            / (SIZE * SIZE) as f64
+    };
+    let packed = packed_area(&mut |width, height| packer.allocate(width, height));
+    let first_fit_packed = packed_area(&mut |width, height| first_fit.allocate(width, height));
+    println!(
+        "First fit: {:.1}% of the atlas, RectPacker: {:.1}%",
+        first_fit_packed * 100.0,
+        packed * 100.0,
+    );
+    assert!(packed > first_fit_packed);
+}
+
+/// The first-fit packer the glyph cache used before `RectPacker`, for
+/// comparison: rects are placed in the first empty rect they fit
+/// into, with the empty rects sorted smallest first.
+struct FirstFitPacker {
+    empty_rects: Vec<Rect>,
+}
+
+impl FirstFitPacker {
+    fn new(width: u32, height: u32) -> FirstFitPacker {
+        FirstFitPacker {
+            empty_rects: vec![Rect::new(0, 0, width, height)],
+        }
+    }
+
+    fn allocate(&mut self, width: u32, height: u32) -> Option<Rect> {
+        let mut new_rect = *self
+            .empty_rects
+            .iter()
+            .find(|rect| rect.width() >= width && rect.height() >= height)?;
+        new_rect.resize(width, height);
+        self.empty_rects
+            .retain(|rect| !new_rect.contains_rect(*rect));
+        let mut i = 0;
+        while i < self.empty_rects.len() {
+            if !self.empty_rects[i].has_intersection(new_rect) {
+                i += 1;
+                continue;
+            }
+            let old = self.empty_rects.remove(i);
+            if old.left() < new_rect.left() {
+                let width = (new_rect.left() - old.left()) as u32;
+                self.empty_rects
+                    .push(Rect::new(old.x(), old.y(), width, old.height()));
+            }
+            if old.right() > new_rect.right() {
+                let width = (old.right() - new_rect.right()) as u32;
+                self.empty_rects
+                    .push(Rect::new(new_rect.right(), old.y(), width, old.height()));
+            }
+            if old.top() < new_rect.top() {
+                let height = (new_rect.top() - old.top()) as u32;
+                self.empty_rects
+                    .push(Rect::new(old.x(), old.y(), old.width(), height));
+            }
+            if old.bottom() > new_rect.bottom() {
+                let height = (old.bottom() - new_rect.bottom()) as u32;
+                self.empty_rects
+                    .push(Rect::new(old.x(), new_rect.bottom(), old.width(), height));
+            }
+        }
+        self.empty_rects
+            .sort_by_key(|rect| rect.width() * rect.height());
+        // Drop the rects within bigger ones.
+        let mut i = 1;
+        while i < self.empty_rects.len() {
+            let rect = self.empty_rects[i];
+            let mut j = 0;
+            while j < i {
+                if rect.contains_rect(self.empty_rects[j]) {
+                    self.empty_rects.remove(j);
+                    i -= 1;
+                } else {
+                    j += 1;
+                }
+            }
+            i += 1;
+        }
+        Some(new_rect)
+    }
+}
@@ -0,0 +1,66 @@
+//! Measures how long drawing 200 glyphs that aren't cached yet takes,
+//! on a software canvas. Run with and without `--features rayon` to
+//! compare rasterizing the glyphs on one thread and on many. The
+//! checksum of the drawn pixels is printed too, which should be the
+//! same for both.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::surface::Surface;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 20;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let surface = Surface::new(1024, 512, PixelFormatEnum::RGBA32)?;
+    let mut canvas = surface.into_canvas()?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+
+    // 50 different characters in 4 sizes, so every glyph is new.
+    let text = "ABCDEFGHIJKLMNOPQRSTUVWXYabcdefghijklmnopqrstuvwxy";
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        max_width: Some(1024.0),
+        ..LayoutSettings::default()
+    });
+    for &px in &[18.0, 24.0, 32.0, 40.0] {
+        layout.append(fonts, &TextStyle::with_user_data(text, px, 0, Color::WHITE));
+        layout.append(fonts, &TextStyle::with_user_data("\n", px, 0, Color::WHITE));
+    }
+
+    let mut total = Duration::ZERO;
+    for _ in 0..ROUNDS {
+        font_texture.evict_font(&fonts[0]);
+        canvas.set_draw_color(Color::BLACK);
+        canvas.clear();
+        let start = Instant::now();
+        font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+        total += start.elapsed();
+    }
+
+    let mut hasher = DefaultHasher::new();
+    canvas
+        .read_pixels(None, PixelFormatEnum::RGBA32)?
+        .hash(&mut hasher);
+    println!(
+        "Drew {} new glyphs in {:.2} ms on average, rayon {}, pixel checksum {:016x}",
+        layout.glyphs().len(),
+        total.as_secs_f64() * 1000.0 / ROUNDS as f64,
+        if cfg!(feature = "rayon") {
+            "enabled"
+        } else {
+            "disabled"
+        },
+        hasher.finish(),
+    );
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! Draws small text without anti-aliasing, scaled up for a retro look.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 retro example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+    font_texture.set_monochrome(true);
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        x: 8.0,
+        y: 8.0,
+        ..LayoutSettings::default()
+    });
+    let color = Color::RGB(0x33, 0xFF, 0x66);
+    let text = "READY.\nLOAD \"*\",8,1\n\nSEARCHING FOR *\nLOADING";
+    layout.append(fonts, &TextStyle::with_user_data(text, 12.0, 0, color));
+
+    // Small text scaled up 4x, so the hard edges of each pixel are
+    // easy to see.
+    canvas.set_scale(4.0, 4.0)?;
+    canvas.set_draw_color(Color::RGB(0x10, 0x10, 0x30));
+    canvas.clear();
+    font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
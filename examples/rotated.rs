@@ -0,0 +1,66 @@
+//! Draws the same text both normally and rotated 90 degrees, like a
+//! vertical side label.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::rect::Point;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 rotated text example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let color = Color::RGB(0xFF, 0xFF, 0);
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        x: 40.0,
+        y: 560.0,
+        ..LayoutSettings::default()
+    });
+    layout.append(
+        fonts,
+        &TextStyle::with_user_data("Side label", 32.0, 0, color),
+    );
+
+    canvas.clear();
+    font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+    // Rotate counter-clockwise around the start of the text, so that
+    // it reads from bottom to top along the left edge.
+    let pivot = Point::new(40, 560);
+    font_texture.draw_text_transformed(&mut canvas, fonts, layout.glyphs(), pivot, -90.0)?;
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
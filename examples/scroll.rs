@@ -0,0 +1,77 @@
+//! Draws a paragraph of text scrolling up and off the top of the
+//! window, wrapping back to the bottom. The glyphs that are partially
+//! scrolled off are cut off at the edge, and the ones that are
+//! completely off the window aren't drawn (or cached) at all.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use std::time::Instant;
+
+static TEXT: &str = "Typesetting is the composition of text by means of arranging physical type or its digital equivalents. Stored letters and other symbols (called sorts in mechanical systems and glyphs in digital systems) are retrieved and ordered according to a language's orthography for visual display.";
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 scroll example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window
+        .into_canvas()
+        .present_vsync()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    let start = Instant::now();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+
+        // Scroll up by 40 pixels per second, from the bottom of the
+        // window until the text is well past the top.
+        let t = start.elapsed().as_secs_f32();
+        let y = 600.0 - (t * 40.0) % 1000.0;
+        layout.reset(&LayoutSettings {
+            x: 20.0,
+            y,
+            max_width: Some(760.0),
+            ..LayoutSettings::default()
+        });
+        layout.append(
+            fonts,
+            &TextStyle::with_user_data(TEXT, 32.0, 0, Color::WHITE),
+        );
+
+        canvas.set_draw_color(Color::RGB(0x31, 0x87, 0xC0));
+        canvas.clear();
+        font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+        canvas.present();
+    }
+
+    Ok(())
+}
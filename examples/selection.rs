@@ -0,0 +1,82 @@
+//! Draws text with a selection highlight behind part of it.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 selection example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        x: 20.0,
+        y: 20.0,
+        ..LayoutSettings::default()
+    });
+    let text = "Hello World,\nthis text is partially selected.";
+    layout.append(
+        fonts,
+        &TextStyle::with_user_data(text, 32.0, 0, Color::WHITE),
+    );
+    // The selection starts at "World" and continues onto the second
+    // line, so it's highlighted with one rectangle per line.
+    let selection_start = text.find("World").unwrap();
+    let selection_end = text.find("partially").unwrap();
+    let selected_glyphs = layout
+        .glyphs()
+        .iter()
+        .position(|glyph| glyph.byte_offset == selection_start)
+        .unwrap()
+        ..layout
+            .glyphs()
+            .iter()
+            .position(|glyph| glyph.byte_offset == selection_end)
+            .unwrap();
+    let highlight_color = Color::RGB(0x26, 0x4F, 0x78);
+
+    canvas.set_draw_color(Color::RGB(0x1E, 0x1E, 0x1E));
+    canvas.clear();
+    font_texture.draw_text_with_highlight(
+        &mut canvas,
+        fonts,
+        layout.glyphs(),
+        &[selected_glyphs],
+        highlight_color,
+    )?;
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
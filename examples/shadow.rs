@@ -0,0 +1,68 @@
+//! Draws text with a drop shadow.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 drop shadow example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Bold.ttf") as &[u8];
+    let roboto_bold = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_bold];
+    let color = Color::RGB(0xFF, 0xFF, 0xFF);
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        x: 20.0,
+        y: 20.0,
+        ..LayoutSettings::default()
+    });
+    layout.append(
+        fonts,
+        &TextStyle::with_user_data("Drop shadow", 64.0, 0, color),
+    );
+
+    canvas.set_draw_color(Color::RGB(0x44, 0x88, 0xCC));
+    canvas.clear();
+    let shadow_color = Color::RGBA(0, 0, 0, 0xAA);
+    font_texture.draw_text_with_shadow(
+        &mut canvas,
+        fonts,
+        layout.glyphs(),
+        (4, 4),
+        shadow_color,
+    )?;
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Draws a string with [`SimpleText`], which takes care of the
+//! [`fontdue::layout::Layout`] and the [`FontTexture`] behind the
+//! scenes.
+//!
+//! [`FontTexture`]: fontdue_sdl2::FontTexture
+
+use fontdue::Font;
+use fontdue_sdl2::SimpleText;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 simple example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let mut text = SimpleText::new(&texture_creator, roboto_regular)?;
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+
+        canvas.set_draw_color(Color::BLACK);
+        canvas.clear();
+        text.draw(&mut canvas, "Hello world!", 20.0, 20.0, 32.0, Color::WHITE)?;
+        canvas.present();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Draws small text with grayscale and subpixel anti-aliasing.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::{ColorMode, FontTexture};
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 subpixel example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+
+    canvas.set_draw_color(Color::RGB(0xFF, 0xFF, 0xFF));
+    canvas.clear();
+    // Grayscale text on top, subpixel text below it. Zoom in on a
+    // screenshot to see the color fringes on the vertical edges of
+    // the subpixel glyphs.
+    let modes = [
+        (ColorMode::Modulated, "Grayscale"),
+        (ColorMode::Subpixel, "Subpixel"),
+    ];
+    for (i, (color_mode, name)) in modes.iter().enumerate() {
+        layout.reset(&LayoutSettings {
+            x: 20.0,
+            y: 20.0 + i as f32 * 60.0,
+            ..LayoutSettings::default()
+        });
+        let text = format!("{}: The quick brown fox jumps over the lazy dog.\n", name);
+        let black = Color::RGB(0, 0, 0);
+        let blue = Color::RGB(0x20, 0x40, 0xA0);
+        layout.append(fonts, &TextStyle::with_user_data(&text, 12.0, 0, black));
+        layout.append(fonts, &TextStyle::with_user_data(&text, 16.0, 0, blue));
+        font_texture.set_color_mode(*color_mode);
+        font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+    }
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! Draws a large heading with different supersampling factors.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 supersample example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+
+    canvas.set_draw_color(Color::RGB(0xFF, 0xFF, 0xFF));
+    canvas.clear();
+    // The same heading without and with supersampling, from top to
+    // bottom. The glyphs for each factor get their own cache entries.
+    for (i, factor) in [1, 2, 4].iter().enumerate() {
+        layout.reset(&LayoutSettings {
+            x: 20.0,
+            y: 20.0 + i as f32 * 90.0,
+            ..LayoutSettings::default()
+        });
+        let text = format!("Heading {}x", factor);
+        let color = Color::RGB(0, 0, 0);
+        layout.append(fonts, &TextStyle::with_user_data(&text, 64.0, 0, color));
+        font_texture.set_supersampling(*factor);
+        font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+    }
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -82,10 +82,11 @@ pub fn main() -> Result<(), String> {
 
         font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
         if show_glyph_cache {
-            let glyph_cache_rect = Rect::new(0, 0, 1024, 1024);
+            let (cache_width, cache_height) = font_texture.texture_dimensions();
+            let glyph_cache_rect = Rect::new(0, 0, cache_width, cache_height);
             canvas.set_draw_color(Color::RGB(0xEE, 0xEE, 0xEE));
             let _ = canvas.fill_rect(glyph_cache_rect);
-            let _ = canvas.copy(&font_texture.texture, None, glyph_cache_rect);
+            let _ = font_texture.draw_debug_atlas(&mut canvas, glyph_cache_rect);
         }
 
         canvas.present();
@@ -0,0 +1,96 @@
+//! Compares glyph cache textures created with streaming and target access.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTextureBuilder;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use sdl2::render::TextureAccess;
+use std::time::Instant;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 texture access example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    layout.reset(&LayoutSettings {
+        x: 10.0,
+        y: 10.0,
+        max_width: Some(780.0),
+        ..LayoutSettings::default()
+    });
+    let paragraph = "The quick brown fox jumps over the lazy dog. ".repeat(12);
+    let color = Color::RGB(0xFF, 0xFF, 0xFF);
+    layout.append(
+        fonts,
+        &TextStyle::with_user_data(&paragraph, 20.0, 0, color),
+    );
+
+    // Draws the paragraph with a glyph cache texture of each access,
+    // first into an empty glyph cache a few times, which uploads every
+    // glyph, and then a hundred times from the warm glyph cache, and
+    // prints how long each took, and whether the drawn text is the
+    // same as with the streaming texture.
+    const COLD_FRAMES: u32 = 10;
+    const WARM_FRAMES: u32 = 100;
+    let mut streaming_pixels = None;
+    for access in [TextureAccess::Streaming, TextureAccess::Target] {
+        let builder = FontTextureBuilder::new().texture_access(access);
+        let start = Instant::now();
+        for _ in 0..COLD_FRAMES {
+            let mut font_texture = builder.build(&texture_creator)?;
+            canvas.set_draw_color(Color::RGB(0x22, 0x22, 0x22));
+            canvas.clear();
+            font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+        }
+        let cold_time = start.elapsed() / COLD_FRAMES;
+
+        let mut font_texture = builder.build(&texture_creator)?;
+        font_texture.cache_glyphs(fonts, layout.glyphs())?;
+        let start = Instant::now();
+        for _ in 0..WARM_FRAMES {
+            canvas.set_draw_color(Color::RGB(0x22, 0x22, 0x22));
+            canvas.clear();
+            font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+        }
+        let warm_time = start.elapsed() / WARM_FRAMES;
+
+        let pixels = canvas.read_pixels(None, canvas.default_pixel_format())?;
+        let same = streaming_pixels.get_or_insert_with(|| pixels.clone()) == &pixels;
+        println!(
+            "{:?}: cold draw {:?}, warm draw {:?}, same output as streaming: {}",
+            access, cold_time, warm_time, same,
+        );
+        canvas.present();
+    }
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
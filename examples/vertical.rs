@@ -0,0 +1,78 @@
+//! Draws vertical text, with some of the glyphs rotated.
+
+use fontdue::layout::{CoordinateSystem, HorizontalAlign, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 vertical text example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+
+    // fontdue only lays out horizontal text, so vertical text is laid
+    // out one glyph at a time, centered on a column. Most glyphs stay
+    // upright, but brackets and dashes are rotated to run along the
+    // column, like in vertical CJK text.
+    let size = 28.0;
+    let column_x = 400.0;
+    let mut glyphs = Vec::new();
+    let mut angles = Vec::new();
+    for (i, c) in "(VERTICAL—TEXT)".chars().enumerate() {
+        layout.reset(&LayoutSettings {
+            x: column_x - size / 2.0,
+            y: 40.0 + i as f32 * size,
+            max_width: Some(size),
+            horizontal_align: HorizontalAlign::Center,
+            ..LayoutSettings::default()
+        });
+        layout.append(
+            fonts,
+            &TextStyle::with_user_data(&c.to_string(), size, 0, Color::WHITE),
+        );
+        let rotated = matches!(c, '(' | ')' | '—');
+        for glyph in layout.glyphs() {
+            glyphs.push(*glyph);
+            angles.push(if rotated { 90.0 } else { 0.0 });
+        }
+    }
+
+    canvas.set_draw_color(Color::RGB(0x31, 0x87, 0xC0));
+    canvas.clear();
+    font_texture.draw_text_with_glyph_angles(&mut canvas, fonts, &glyphs, &angles)?;
+    canvas.present();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! Draws text that zooms in and out smoothly, with each glyph
+//! rasterized only once.
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font;
+use fontdue_sdl2::FontTexture;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::Color;
+use std::time::Instant;
+
+pub fn main() -> Result<(), String> {
+    env_logger::init();
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+
+    let window = video_subsystem
+        .window("fontdue-sdl2 zoom example", 800, 600)
+        .position_centered()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut canvas = window
+        .into_canvas()
+        .present_vsync()
+        .build()
+        .map_err(|e| e.to_string())?;
+    let texture_creator = canvas.texture_creator();
+    let mut font_texture = FontTexture::new(&texture_creator)?;
+    // Rasterize the glyphs at the biggest size they're drawn at, so
+    // they only get scaled down.
+    font_texture.set_fixed_raster_size(Some(48.0))?;
+
+    let font = include_bytes!("roboto/Roboto-Regular.ttf") as &[u8];
+    let roboto_regular = Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+    let fonts = &[roboto_regular];
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    let start = Instant::now();
+
+    let mut event_pump = sdl_context.event_pump()?;
+
+    'running: loop {
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. }
+                | Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } => break 'running,
+                _ => {}
+            }
+        }
+
+        // Zoom between 12px and 48px.
+        let t = start.elapsed().as_secs_f32();
+        let size = 30.0 + 18.0 * t.sin();
+        layout.reset(&LayoutSettings {
+            x: 20.0,
+            y: 20.0,
+            ..LayoutSettings::default()
+        });
+        let text = "Zooming text";
+        layout.append(
+            fonts,
+            &TextStyle::with_user_data(text, size, 0, Color::WHITE),
+        );
+
+        canvas.set_draw_color(Color::RGB(0x31, 0x87, 0xC0));
+        canvas.clear();
+        font_texture.draw_text(&mut canvas, fonts, layout.glyphs())?;
+        canvas.present();
+    }
+
+    Ok(())
+}
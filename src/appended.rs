@@ -0,0 +1,107 @@
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, RenderTarget, Texture};
+
+use crate::FontTextureError;
+
+/// Text drawn into a target texture bit by bit, as it's appended to,
+/// by [`FontTexture::draw_text_appended`](crate::FontTexture::draw_text_appended),
+/// e.g. for a console or a log view. Only the glyphs appended since
+/// the last draw are drawn, and once the text is taller than the
+/// texture, the texture scrolls along with the end of the text.
+///
+/// The texture is used as a ring buffer: instead of moving the
+/// already drawn text up, the rows scrolled out of view are reused
+/// for the new text. So the texture should be drawn with
+/// [`AppendedText::copy`], which puts the rows back in order.
+///
+/// Each `AppendedText` tracks the text drawn into one texture, and
+/// should only be used with that texture and that text.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AppendedText {
+    /// The amount of glyphs of the text already drawn.
+    pub(crate) drawn_glyphs: usize,
+    /// The y coordinate of the text (in the coordinates of its
+    /// layout) at the top edge of the visible area.
+    pub(crate) scroll: i32,
+}
+
+impl AppendedText {
+    /// Creates the state for a texture nothing has been drawn into.
+    pub fn new() -> AppendedText {
+        AppendedText::default()
+    }
+
+    /// Returns the y coordinate of the text, in the coordinates of
+    /// its layout, which is at the top of the texture when it's drawn
+    /// with [`AppendedText::copy`]. This is 0 until the text grows
+    /// taller than the texture.
+    pub fn scroll(&self) -> i32 {
+        self.scroll
+    }
+
+    /// Returns the amount of glyphs of the text which have been drawn
+    /// into the texture.
+    pub fn drawn_glyphs(&self) -> usize {
+        self.drawn_glyphs
+    }
+
+    /// Copies the texture onto the canvas with its top left corner at
+    /// `position`, with the rows of the text in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::Copy`] if copying the texture
+    /// onto the canvas fails.
+    pub fn copy<RT: RenderTarget>(
+        &self,
+        canvas: &mut Canvas<RT>,
+        texture: &Texture,
+        position: Point,
+    ) -> Result<(), FontTextureError> {
+        let query = texture.query();
+        let (width, height) = (query.width, query.height as i32);
+        let top = self.scroll.rem_euclid(height);
+        let (x, y) = (position.x(), position.y());
+        canvas
+            .copy(
+                texture,
+                Rect::new(0, top, width, (height - top) as u32),
+                Rect::new(x, y, width, (height - top) as u32),
+            )
+            .map_err(FontTextureError::Copy)?;
+        if top > 0 {
+            canvas
+                .copy(
+                    texture,
+                    Rect::new(0, 0, width, top as u32),
+                    Rect::new(x, y + height - top, width, top as u32),
+                )
+                .map_err(FontTextureError::Copy)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the rects of the texture which the text rows from `top` to
+/// `bottom` are drawn into, for a ring buffer texture of the size.
+/// The rows wrap around from the bottom of the texture to the top, so
+/// there can be two rects.
+pub(crate) fn ring_rects(top: i32, bottom: i32, width: u32, height: u32) -> Vec<Rect> {
+    let height = height as i32;
+    if bottom - top >= height {
+        return vec![Rect::new(0, 0, width, height as u32)];
+    }
+    if bottom <= top {
+        return Vec::new();
+    }
+    let start = top.rem_euclid(height);
+    let end = start + bottom - top;
+    if end <= height {
+        vec![Rect::new(0, start, width, (end - start) as u32)]
+    } else {
+        vec![
+            Rect::new(0, start, width, (height - start) as u32),
+            Rect::new(0, 0, width, (end - height) as u32),
+        ]
+    }
+}
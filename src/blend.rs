@@ -0,0 +1,160 @@
+//! Blend modes which can't be represented by [BlendMode], since
+//! they're composed with `SDL_ComposeCustomBlendMode`.
+//!
+//! [BlendMode]: sdl2::render::BlendMode
+
+use sdl2::render::Texture;
+use std::os::raw::c_int;
+
+// These are declared here instead of using the ones in sdl2::sys,
+// since those use an enum for the blend mode, and custom blend modes
+// aren't valid values of that enum.
+extern "C" {
+    fn SDL_ComposeCustomBlendMode(
+        src_color_factor: u32,
+        dst_color_factor: u32,
+        color_operation: u32,
+        src_alpha_factor: u32,
+        dst_alpha_factor: u32,
+        alpha_operation: u32,
+    ) -> u32;
+    fn SDL_SetTextureBlendMode(texture: *mut sdl2::sys::SDL_Texture, blend_mode: u32) -> c_int;
+    fn SDL_GetTextureBlendMode(texture: *mut sdl2::sys::SDL_Texture, blend_mode: *mut u32)
+        -> c_int;
+}
+
+const SDL_BLENDOPERATION_ADD: u32 = 1;
+const SDL_BLENDOPERATION_MAXIMUM: u32 = 5;
+const SDL_BLENDFACTOR_ZERO: u32 = 1;
+const SDL_BLENDFACTOR_ONE: u32 = 2;
+const SDL_BLENDFACTOR_ONE_MINUS_SRC_COLOR: u32 = 4;
+const SDL_BLENDFACTOR_ONE_MINUS_SRC_ALPHA: u32 = 6;
+
+/// Returns the blend mode of the texture, which, unlike
+/// [Texture::blend_mode], works for custom blend modes too.
+pub(crate) fn raw_blend_mode(texture: &Texture) -> Result<u32, String> {
+    let mut blend_mode = 0;
+    // Safety: the texture pointer is valid for the lifetime of the
+    // Texture.
+    let result = unsafe { SDL_GetTextureBlendMode(texture.raw(), &mut blend_mode) };
+    if result != 0 {
+        Err(sdl2::get_error())
+    } else {
+        Ok(blend_mode)
+    }
+}
+
+/// Sets a blend mode returned by [raw_blend_mode] back to the
+/// texture.
+pub(crate) fn set_raw_blend_mode(texture: &mut Texture, blend_mode: u32) -> Result<(), String> {
+    // Safety: the texture pointer is valid for the lifetime of the
+    // Texture.
+    unsafe { set_texture_ptr_blend_mode(texture.raw(), blend_mode) }
+}
+
+/// Like [set_raw_blend_mode], for a texture that isn't wrapped in a
+/// [Texture].
+///
+/// # Safety
+///
+/// The texture pointer must point to a valid texture, and the blend
+/// mode must be one SDL has returned before.
+pub(crate) unsafe fn set_texture_ptr_blend_mode(
+    texture: *mut sdl2::sys::SDL_Texture,
+    blend_mode: u32,
+) -> Result<(), String> {
+    let result = SDL_SetTextureBlendMode(texture, blend_mode);
+    if result != 0 {
+        Err(sdl2::get_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Composes a custom blend mode and sets it to the texture.
+fn set_custom_blend_mode(
+    texture: &mut Texture,
+    (src_color_factor, dst_color_factor): (u32, u32),
+    (src_alpha_factor, dst_alpha_factor): (u32, u32),
+) -> Result<(), String> {
+    // Safety: composing a blend mode has no preconditions.
+    let blend_mode = unsafe {
+        SDL_ComposeCustomBlendMode(
+            src_color_factor,
+            dst_color_factor,
+            SDL_BLENDOPERATION_ADD,
+            src_alpha_factor,
+            dst_alpha_factor,
+            SDL_BLENDOPERATION_ADD,
+        )
+    };
+    set_raw_blend_mode(texture, blend_mode)
+}
+
+/// Sets the blend mode of the texture to the first pass of subpixel
+/// blending, which darkens the destination by the coverage of each
+/// color channel separately:
+///
+/// dstRGB = dstRGB * (1-srcRGB)
+///
+/// dstA = dstA
+pub(crate) fn set_subpixel_mask_blend_mode(texture: &mut Texture) -> Result<(), String> {
+    set_custom_blend_mode(
+        texture,
+        (SDL_BLENDFACTOR_ZERO, SDL_BLENDFACTOR_ONE_MINUS_SRC_COLOR),
+        (SDL_BLENDFACTOR_ZERO, SDL_BLENDFACTOR_ONE),
+    )
+}
+
+/// Sets the blend mode of the texture to the second pass of subpixel
+/// blending, which adds the (color modulated) coverage to the
+/// destination:
+///
+/// dstRGB = srcRGB + dstRGB
+///
+/// dstA = dstA
+pub(crate) fn set_subpixel_color_blend_mode(texture: &mut Texture) -> Result<(), String> {
+    set_custom_blend_mode(
+        texture,
+        (SDL_BLENDFACTOR_ONE, SDL_BLENDFACTOR_ONE),
+        (SDL_BLENDFACTOR_ZERO, SDL_BLENDFACTOR_ONE),
+    )
+}
+
+/// Sets the blend mode of the texture to keeping the maximum of the
+/// source and the destination, for merging overlapping glyphs:
+///
+/// dstRGB = max(srcRGB, dstRGB)
+///
+/// dstA = max(srcA, dstA)
+///
+/// Only some renderers support this, so this fails on the others.
+pub(crate) fn set_maximum_blend_mode(texture: &mut Texture) -> Result<(), String> {
+    // Safety: SDL_ComposeCustomBlendMode has no safety concerns, it
+    // just returns an integer.
+    let blend_mode = unsafe {
+        SDL_ComposeCustomBlendMode(
+            SDL_BLENDFACTOR_ONE,
+            SDL_BLENDFACTOR_ONE,
+            SDL_BLENDOPERATION_MAXIMUM,
+            SDL_BLENDFACTOR_ONE,
+            SDL_BLENDFACTOR_ONE,
+            SDL_BLENDOPERATION_MAXIMUM,
+        )
+    };
+    set_raw_blend_mode(texture, blend_mode)
+}
+
+/// Sets the blend mode of the texture to alpha blending for textures
+/// with premultiplied alpha:
+///
+/// dstRGB = srcRGB + (dstRGB * (1-srcA))
+///
+/// dstA = srcA + (dstA * (1-srcA))
+pub(crate) fn set_premultiplied_blend_mode(texture: &mut Texture) -> Result<(), String> {
+    set_custom_blend_mode(
+        texture,
+        (SDL_BLENDFACTOR_ONE, SDL_BLENDFACTOR_ONE_MINUS_SRC_ALPHA),
+        (SDL_BLENDFACTOR_ONE, SDL_BLENDFACTOR_ONE_MINUS_SRC_ALPHA),
+    )
+}
@@ -0,0 +1,204 @@
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, TextureAccess};
+
+use crate::{ColorMode, MissingGlyphStyle, ScaleMode};
+
+/// A builder for configuring a [`FontTexture`](crate::FontTexture)
+/// before creating it, for when the defaults of
+/// [`FontTexture::new`](crate::FontTexture::new) aren't enough.
+///
+/// ```no_run
+/// # use fontdue_sdl2::{ColorMode, FontTextureBuilder};
+/// # let sdl_context = sdl2::init().unwrap();
+/// # let video_subsystem = sdl_context.video().unwrap();
+/// # let window = video_subsystem.window("", 800, 600).build().unwrap();
+/// # let canvas = window.into_canvas().build().unwrap();
+/// # let texture_creator = canvas.texture_creator();
+/// let font_texture = FontTextureBuilder::new()
+///     .size(512, 512)
+///     .color_mode(ColorMode::Modulated)
+///     .gamma(2.2)
+///     .build(&texture_creator)
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct FontTextureBuilder {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) native_format: bool,
+    pub(crate) padding: u32,
+    pub(crate) color_mode: ColorMode,
+    pub(crate) gamma: f32,
+    pub(crate) premultiplied_alpha: bool,
+    pub(crate) blend_mode: Option<BlendMode>,
+    pub(crate) scale_mode: Option<ScaleMode>,
+    pub(crate) missing_glyph_style: MissingGlyphStyle,
+    pub(crate) texture_access: TextureAccess,
+    pub(crate) reserved_areas: Vec<Rect>,
+}
+
+impl Default for FontTextureBuilder {
+    fn default() -> FontTextureBuilder {
+        FontTextureBuilder {
+            width: crate::DEFAULT_TEXTURE_SIZE,
+            height: crate::DEFAULT_TEXTURE_SIZE,
+            native_format: false,
+            padding: crate::DEFAULT_GLYPH_PADDING,
+            color_mode: ColorMode::default(),
+            gamma: 1.0,
+            premultiplied_alpha: false,
+            blend_mode: None,
+            scale_mode: None,
+            missing_glyph_style: MissingGlyphStyle::default(),
+            texture_access: TextureAccess::Streaming,
+            reserved_areas: Vec::new(),
+        }
+    }
+}
+
+impl FontTextureBuilder {
+    /// Creates a builder with the same configuration as
+    /// [`FontTexture::new`](crate::FontTexture::new).
+    pub fn new() -> FontTextureBuilder {
+        FontTextureBuilder::default()
+    }
+
+    /// Sets the size of the glyph cache texture, see
+    /// [`FontTexture::with_size`](crate::FontTexture::with_size). The
+    /// default is 1024x1024.
+    pub fn size(mut self, width: u32, height: u32) -> FontTextureBuilder {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets whether the glyph cache texture is created in the
+    /// renderer's preferred channel order, see
+    /// [`FontTexture::with_native_format`](crate::FontTexture::with_native_format).
+    /// The default is false, which creates an RGBA texture.
+    pub fn native_format(mut self, native_format: bool) -> FontTextureBuilder {
+        self.native_format = native_format;
+        self
+    }
+
+    /// Sets the amount of transparent pixels reserved around each
+    /// glyph in the glyph cache. The default is 1, which keeps
+    /// neighboring glyphs from bleeding in when glyphs are drawn with
+    /// linear filtering at fractional positions or scaled down by up
+    /// to half. Glyphs scaled down further may need more padding,
+    /// while 0 packs the most glyphs into the cache, but should only
+    /// be used with nearest neighbor filtering and unscaled text.
+    pub fn padding(mut self, padding: u32) -> FontTextureBuilder {
+        self.padding = padding;
+        self
+    }
+
+    /// See [`FontTexture::set_color_mode`](crate::FontTexture::set_color_mode).
+    pub fn color_mode(mut self, color_mode: ColorMode) -> FontTextureBuilder {
+        self.color_mode = color_mode;
+        self
+    }
+
+    /// See [`FontTexture::set_gamma`](crate::FontTexture::set_gamma).
+    pub fn gamma(mut self, gamma: f32) -> FontTextureBuilder {
+        self.gamma = gamma;
+        self
+    }
+
+    /// See [`FontTexture::set_premultiplied_alpha`](crate::FontTexture::set_premultiplied_alpha).
+    pub fn premultiplied_alpha(mut self, premultiplied_alpha: bool) -> FontTextureBuilder {
+        self.premultiplied_alpha = premultiplied_alpha;
+        self
+    }
+
+    /// See [`FontTexture::set_blend_mode`](crate::FontTexture::set_blend_mode).
+    pub fn blend_mode(mut self, blend_mode: BlendMode) -> FontTextureBuilder {
+        self.blend_mode = Some(blend_mode);
+        self
+    }
+
+    /// See [`FontTexture::set_scale_mode`](crate::FontTexture::set_scale_mode).
+    pub fn scale_mode(mut self, scale_mode: ScaleMode) -> FontTextureBuilder {
+        self.scale_mode = Some(scale_mode);
+        self
+    }
+
+    /// See [`FontTexture::set_missing_glyph_style`](crate::FontTexture::set_missing_glyph_style).
+    pub fn missing_glyph_style(mut self, style: MissingGlyphStyle) -> FontTextureBuilder {
+        self.missing_glyph_style = style;
+        self
+    }
+
+    /// Sets the access of the glyph cache texture, which decides how
+    /// new glyphs are uploaded to it. The default is
+    /// [`TextureAccess::Streaming`], which is meant for textures that
+    /// are updated often, like the glyph cache while new text is
+    /// drawn.
+    ///
+    /// With [`TextureAccess::Target`], glyphs cached while drawing are
+    /// instead rendered into the texture from a temporary texture,
+    /// which some drivers handle better, and target textures can be
+    /// faster to draw from once the glyph cache is warm, e.g. after
+    /// [`FontTexture::cache_glyphs`](crate::FontTexture::cache_glyphs).
+    /// The canvas drawn onto must be the one whose texture creator
+    /// created the glyph cache texture, and it must support rendering
+    /// to textures. Glyphs cached outside of draws, e.g. by
+    /// [`FontTexture::cache_glyphs`](crate::FontTexture::cache_glyphs),
+    /// are uploaded like with the other accesses.
+    ///
+    /// Note that some renderers, e.g. Direct3D, lose the contents of
+    /// target textures when the device is reset, which SDL reports
+    /// with an `SDL_RENDER_TARGETS_RESET` event. The glyph cache
+    /// doesn't notice this, so when the event arrives, evict the
+    /// glyphs with
+    /// [`FontTexture::evict_font`](crate::FontTexture::evict_font),
+    /// and the next draws rasterize and render them into the texture
+    /// again.
+    ///
+    /// The drawn text is the same with every access, see the
+    /// `texture_access` example for a comparison of their speed.
+    pub fn texture_access(mut self, texture_access: TextureAccess) -> FontTextureBuilder {
+        self.texture_access = texture_access;
+        self
+    }
+
+    /// Sets areas of the glyph cache texture which glyphs are never
+    /// placed in, e.g. to keep a corner of it for your own sprites,
+    /// so they can be drawn from the same texture as the text. The
+    /// default is no reserved areas.
+    ///
+    /// The contents of the reserved areas should be written with
+    /// [`FontTexture::update_reserved_area`](crate::FontTexture::update_reserved_area),
+    /// since the glyph cache overwrites the texture from its own copy
+    /// of the pixels when uploading glyphs.
+    ///
+    /// ```
+    /// # use fontdue::Font;
+    /// # use fontdue_sdl2::FontTextureBuilder;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let sprites = Rect::new(0, 0, 64, 64);
+    /// let mut font_texture = FontTextureBuilder::new()
+    ///     .size(128, 128)
+    ///     .reserved_areas(&[sprites])
+    ///     .build(&texture_creator)
+    ///     .unwrap();
+    /// font_texture.update_reserved_area(sprites, &[0xFF; 64 * 64 * 4]).unwrap();
+    ///
+    /// for c in "ABCDEFGHIJKLMNOPQRSTUVWXYZ".chars() {
+    ///     if let Some(rect) = font_texture.glyph_rect(fonts, 0, c, 16.0, Color::WHITE).unwrap() {
+    ///         assert!(!rect.has_intersection(sprites));
+    ///     }
+    /// }
+    /// ```
+    pub fn reserved_areas(mut self, reserved_areas: &[Rect]) -> FontTextureBuilder {
+        self.reserved_areas = reserved_areas.to_vec();
+        self
+    }
+}
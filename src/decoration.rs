@@ -0,0 +1,325 @@
+use fontdue::layout::GlyphPosition;
+use fontdue::Font;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// The kind of line drawn by a [`Decoration`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecorationStyle {
+    /// A line under the text, a bit below the baseline.
+    Underline,
+    /// A line through the middle of the lowercase letters.
+    Strikethrough,
+}
+
+/// A line drawn along a run of glyphs, by
+/// [`FontTexture::draw_text_decorated`](crate::FontTexture::draw_text_decorated).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoration {
+    /// The indices of the decorated glyphs, in the glyph slice given
+    /// to the draw function.
+    pub glyphs: Range<usize>,
+    /// The kind of line to draw.
+    pub style: DecorationStyle,
+    /// The color of the line.
+    pub color: Color,
+}
+
+/// The position and thickness of a font's underline, as chosen by the
+/// font's designer, in ems, i.e. multiples of the font size. fontdue
+/// doesn't read these from fonts, so they're read from the font file
+/// with [`UnderlineMetrics::from_font_data`], and set for the font
+/// with
+/// [`FontTexture::set_underline_metrics`](crate::FontTexture::set_underline_metrics).
+///
+/// ```
+/// # use fontdue_sdl2::UnderlineMetrics;
+/// let roboto = include_bytes!("../examples/roboto/Roboto-Bold.ttf") as &[u8];
+/// let playfair = include_bytes!("../examples/playfair-display/PlayfairDisplay-Regular.ttf") as &[u8];
+/// let roboto = UnderlineMetrics::from_font_data(roboto, 0).unwrap();
+/// let playfair = UnderlineMetrics::from_font_data(playfair, 0).unwrap();
+/// assert!(roboto.position < 0.0 && playfair.position < 0.0);
+/// assert!(roboto.thickness > playfair.thickness);
+/// assert_eq!(UnderlineMetrics::from_font_data(b"not a font", 0), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnderlineMetrics {
+    /// The distance from the baseline to the top of the underline,
+    /// negative below the baseline.
+    pub position: f32,
+    /// The thickness of the underline.
+    pub thickness: f32,
+}
+
+impl UnderlineMetrics {
+    /// Reads the underline metrics from the `post` table of a
+    /// TrueType or OpenType font file, i.e. the data given to
+    /// [`Font::from_bytes`]. The `collection_index` picks the font
+    /// from a font collection, like
+    /// [`FontSettings::collection_index`](fontdue::FontSettings::collection_index).
+    /// Returns None if the data isn't a font file, or the font has no
+    /// underline metrics.
+    pub fn from_font_data(data: &[u8], collection_index: u32) -> Option<UnderlineMetrics> {
+        let font_offset = if data.get(0..4)? == b"ttcf" {
+            read_u32(data, 12 + 4 * collection_index as usize)? as usize
+        } else {
+            0
+        };
+        let table_count = read_u16(data, font_offset + 4)? as usize;
+        let table_offset = |tag: &[u8]| {
+            let record = (0..table_count)
+                .map(|i| font_offset + 12 + 16 * i)
+                .find(|&record| data.get(record..record + 4) == Some(tag))?;
+            Some(read_u32(data, record + 8)? as usize)
+        };
+        let units_per_em = read_u16(data, table_offset(b"head")? + 18)?;
+        let post = table_offset(b"post")?;
+        // Both are FWORDs, i.e. signed font units.
+        let position = read_u16(data, post + 8)? as i16;
+        let thickness = read_u16(data, post + 10)? as i16;
+        if units_per_em == 0 || thickness <= 0 {
+            return None;
+        }
+        Some(UnderlineMetrics {
+            position: position as f32 / units_per_em as f32,
+            thickness: thickness as f32 / units_per_em as f32,
+        })
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// A glyph's position on its line, recovered from the glyph position
+/// and the font's metrics. Assumes the layout was done with
+/// [`CoordinateSystem::PositiveYDown`](fontdue::layout::CoordinateSystem::PositiveYDown).
+pub(crate) struct GlyphLinePosition {
+    pub baseline: f32,
+    pub left: f32,
+    pub right: f32,
+    pub ascent: f32,
+    pub descent: f32,
+    /// The [`Font::file_hash`] of the glyph's font.
+    pub font_hash: usize,
+    pub px: f32,
+}
+
+/// Returns the glyph's position on its line. Unlike
+/// [`glyph_canvas_rect`](crate::geometry::glyph_canvas_rect), this
+/// works for glyphs which aren't drawn, e.g. spaces and combining
+/// marks, so that they're included in the decorations and highlights
+/// spanning them.
+pub(crate) fn glyph_line_position<U: Copy>(
+    fonts: &[Font],
+    glyph: &GlyphPosition<U>,
+) -> GlyphLinePosition {
+    let font = &fonts[glyph.font_index];
+    let px = glyph.key.px;
+    let metrics = font.metrics_indexed(glyph.key.glyph_index, px);
+    let line_metrics = font.horizontal_line_metrics(px);
+    // These are the inverse of the calculations in fontdue's layout.
+    let baseline = glyph.y - (-metrics.bounds.height - metrics.bounds.ymin).floor();
+    let left = glyph.x - metrics.bounds.xmin;
+    GlyphLinePosition {
+        baseline,
+        left,
+        right: left + metrics.advance_width.ceil(),
+        ascent: line_metrics.map(|m| m.ascent).unwrap_or(px),
+        descent: line_metrics.map(|m| m.descent).unwrap_or(0.0),
+        font_hash: font.file_hash(),
+        px,
+    }
+}
+
+/// Returns the rectangles that make up the line of the decoration. A
+/// decoration spanning multiple lines of text, or multiple fonts or
+/// sizes, is split into one rectangle per line and font, so each
+/// font's line sits where that font's metrics say it should.
+/// `underline_metrics` are the underline metrics of the fonts by
+/// their [`Font::file_hash`].
+pub(crate) fn decoration_rects<U: Copy>(
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    decoration: &Decoration,
+    underline_metrics: &HashMap<usize, UnderlineMetrics>,
+) -> Vec<Rect> {
+    line_segments(fonts, glyphs, decoration.glyphs.clone(), true)
+        .iter()
+        .map(|segment| {
+            let metrics = underline_metrics.get(&segment.font_hash);
+            segment_rect(segment, decoration.style, metrics)
+        })
+        .collect()
+}
+
+/// Returns the rectangles covering the glyphs in the range, from the
+/// ascent to the descent of their line. A range spanning multiple
+/// lines of text is split into one rectangle per line.
+pub(crate) fn highlight_rects<U: Copy>(
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    range: Range<usize>,
+) -> Vec<Rect> {
+    line_segments(fonts, glyphs, range, false)
+        .iter()
+        .map(|segment| {
+            let top = (segment.baseline - segment.ascent).round();
+            let bottom = (segment.baseline - segment.descent).round();
+            Rect::new(
+                segment.left.round() as i32,
+                top as i32,
+                (segment.right - segment.left).round().max(1.0) as u32,
+                (bottom - top).max(1.0) as u32,
+            )
+        })
+        .collect()
+}
+
+/// Merges the line positions of the glyphs in the range into one
+/// segment per line of text, or with `split_fonts`, per consecutive
+/// glyphs of the same font and size on a line. Each segment spans the
+/// whole visual extent of its glyphs, so the glyphs can be in any
+/// order along the line, e.g. right-to-left.
+fn line_segments<U: Copy>(
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    range: Range<usize>,
+    split_fonts: bool,
+) -> Vec<GlyphLinePosition> {
+    let end = range.end.min(glyphs.len());
+    let start = range.start.min(end);
+
+    let mut segments = Vec::new();
+    let mut segment: Option<GlyphLinePosition> = None;
+    for glyph in &glyphs[start..end] {
+        let position = glyph_line_position(fonts, glyph);
+        let continues = |segment: &GlyphLinePosition| {
+            (segment.baseline - position.baseline).abs() < 0.5
+                && (!split_fonts
+                    || (segment.font_hash == position.font_hash && segment.px == position.px))
+        };
+        match &mut segment {
+            Some(segment) if continues(segment) => {
+                segment.left = segment.left.min(position.left);
+                segment.right = segment.right.max(position.right);
+            }
+            _ => {
+                if let Some(segment) = segment.take() {
+                    segments.push(segment);
+                }
+                segment = Some(position);
+            }
+        }
+    }
+    segments.extend(segment);
+    segments
+}
+
+/// Returns the thickness of lines drawn along a line of text, for
+/// decorations and carets.
+fn line_thickness(position: &GlyphLinePosition) -> f32 {
+    // Fonts usually have underlines about 1/14th of the line's height
+    // thick.
+    ((position.ascent - position.descent) / 16.0)
+        .round()
+        .max(1.0)
+}
+
+fn segment_rect(
+    segment: &GlyphLinePosition,
+    style: DecorationStyle,
+    underline_metrics: Option<&UnderlineMetrics>,
+) -> Rect {
+    let (top, thickness) = match (style, underline_metrics) {
+        (DecorationStyle::Underline, Some(metrics)) => (
+            segment.baseline - metrics.position * segment.px,
+            (metrics.thickness * segment.px).round().max(1.0),
+        ),
+        _ => {
+            // Without the font's own metrics, underlines are a bit
+            // under the baseline, and strikethroughs around half of
+            // the x-height.
+            let thickness = line_thickness(segment);
+            let center = match style {
+                DecorationStyle::Underline => segment.baseline - segment.descent * 0.4,
+                DecorationStyle::Strikethrough => segment.baseline - segment.ascent * 0.3,
+            };
+            (center - thickness / 2.0, thickness)
+        }
+    };
+    Rect::new(
+        segment.left.round() as i32,
+        top.round() as i32,
+        (segment.right - segment.left).round().max(1.0) as u32,
+        thickness as u32,
+    )
+}
+
+/// Returns the rectangle of a text cursor in front of the glyph at
+/// `index`, from the ascent to the descent of the glyph's line. An
+/// index past the last glyph places the cursor after the last glyph,
+/// i.e. at the end of the text. Returns None if there are no glyphs.
+///
+/// The cursor is at the boundary between the glyph and the one before
+/// it on the line, with the same positions
+/// [`FontTexture::draw_text`](crate::FontTexture::draw_text) draws
+/// them at. It's as thick as the lines of
+/// [`FontTexture::draw_text_decorated`](crate::FontTexture::draw_text_decorated),
+/// so it can be filled with e.g. [Canvas::fill_rect]:
+///
+/// ```
+/// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+/// # use fontdue_sdl2::{caret_rect, layout_draw_rects};
+/// # use sdl2::pixels::Color;
+/// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+/// # let roboto_regular = Font::from_bytes(font, Default::default()).unwrap();
+/// # let fonts = &[roboto_regular];
+/// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+/// layout.append(fonts, &TextStyle::with_user_data("HI", 32.0, 0, Color::WHITE));
+/// let caret = caret_rect(fonts, layout.glyphs(), 1).unwrap();
+/// let glyph_rects = layout_draw_rects(layout.glyphs());
+/// // Between the H and the I.
+/// assert!(glyph_rects[0].1.right() <= caret.left());
+/// assert!(caret.right() <= glyph_rects[1].1.left());
+/// // After the I.
+/// let end = caret_rect(fonts, layout.glyphs(), 2).unwrap();
+/// assert!(glyph_rects[1].1.right() <= end.left());
+/// ```
+///
+/// [Canvas::fill_rect]: sdl2::render::Canvas::fill_rect
+pub fn caret_rect<U: Copy>(
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    index: usize,
+) -> Option<Rect> {
+    let (position, x) = match glyphs.get(index) {
+        Some(glyph) => {
+            let position = glyph_line_position(fonts, glyph);
+            let x = position.left;
+            (position, x)
+        }
+        None => {
+            let position = glyph_line_position(fonts, glyphs.last()?);
+            let x = position.right;
+            (position, x)
+        }
+    };
+    let thickness = line_thickness(&position);
+    let top = (position.baseline - position.ascent).round();
+    let bottom = (position.baseline - position.descent).round();
+    Some(Rect::new(
+        x.round() as i32 - thickness as i32 / 2,
+        top as i32,
+        thickness as u32,
+        (bottom - top).max(1.0) as u32,
+    ))
+}
@@ -0,0 +1,117 @@
+use fontdue::layout::{CharacterData, GlyphPosition, GlyphRasterConfig};
+use fontdue::Font;
+
+use crate::decoration::glyph_line_position;
+
+/// Truncates a single line of glyphs to fit in `max_width` pixels,
+/// replacing the end of the line with an ellipsis ("…") if it
+/// doesn't fit. The returned glyphs can be drawn like any other
+/// glyphs, e.g. with
+/// [`FontTexture::draw_text`](crate::FontTexture::draw_text).
+///
+/// The glyphs should be left-to-right text: the width is measured
+/// from the left edge of the first glyph, and the glyphs are cut off
+/// from the end of the slice. The ellipsis uses the font, size, and
+/// user data of the last glyph before it, and is placed on the same
+/// baseline. Fonts without an ellipsis glyph get three periods
+/// instead. The glyphs should be laid out with
+/// [`CoordinateSystem::PositiveYDown`](fontdue::layout::CoordinateSystem::PositiveYDown).
+///
+/// ```
+/// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+/// # use fontdue_sdl2::{ellipsize, text_bounds};
+/// # use sdl2::pixels::Color;
+/// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+/// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+/// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+/// layout.append(fonts, &TextStyle::with_user_data("Some long text", 20.0, 0, Color::WHITE));
+/// let glyphs = ellipsize(fonts, layout.glyphs(), 130.0);
+/// let text = glyphs.iter().map(|glyph| glyph.parent).collect::<String>();
+/// assert_eq!(text, "Some long t…");
+/// assert!(text_bounds(&glyphs).unwrap().right() <= 130);
+///
+/// // The ellipsis sits on the same baseline as the rest of the text.
+/// let baseline = |glyph: &fontdue::layout::GlyphPosition<Color>| {
+///     let metrics = fonts[0].metrics_indexed(glyph.key.glyph_index, glyph.key.px);
+///     glyph.y + metrics.height as f32 + metrics.ymin as f32
+/// };
+/// assert_eq!(baseline(&glyphs[0]), baseline(glyphs.last().unwrap()));
+///
+/// // Text that fits is left as it is.
+/// assert_eq!(ellipsize(fonts, layout.glyphs(), 500.0).len(), layout.glyphs().len());
+/// ```
+pub fn ellipsize<U: Copy>(
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    max_width: f32,
+) -> Vec<GlyphPosition<U>> {
+    let first = match glyphs.first() {
+        Some(first) => first,
+        None => return Vec::new(),
+    };
+    let start = glyph_line_position(fonts, first).left;
+    let max_right = start + max_width;
+    let fits = |glyph: &GlyphPosition<U>| glyph_line_position(fonts, glyph).right <= max_right;
+    if glyphs.iter().all(fits) {
+        return glyphs.to_vec();
+    }
+
+    // Keep as many glyphs as fit with the ellipsis after them, but
+    // don't leave whitespace right before the ellipsis.
+    let mut kept = 0;
+    for (i, glyph) in glyphs.iter().enumerate() {
+        let (_, ellipsis_width) = ellipsis_glyphs(fonts, glyph);
+        if glyph_line_position(fonts, glyph).right + ellipsis_width > max_right {
+            break;
+        }
+        if !glyph.parent.is_whitespace() {
+            kept = i + 1;
+        }
+    }
+
+    let mut result = glyphs[..kept].to_vec();
+    let style = glyphs[kept.saturating_sub(1)];
+    let mut pen = match kept {
+        0 => start,
+        _ => glyph_line_position(fonts, &style).right,
+    };
+    let baseline = glyph_line_position(fonts, &style).baseline;
+    let font = &fonts[style.font_index];
+    let (ellipsis, _) = ellipsis_glyphs(fonts, &style);
+    for c in ellipsis.chars() {
+        let glyph_index = font.lookup_glyph_index(c);
+        let metrics = font.metrics_indexed(glyph_index, style.key.px);
+        // The same positioning as fontdue's layout does.
+        result.push(GlyphPosition {
+            key: GlyphRasterConfig {
+                glyph_index,
+                ..style.key
+            },
+            parent: c,
+            x: (pen + metrics.bounds.xmin).floor(),
+            y: baseline + (-metrics.bounds.height - metrics.bounds.ymin).floor(),
+            width: metrics.width,
+            height: metrics.height,
+            char_data: CharacterData::classify(c, glyph_index),
+            ..style
+        });
+        pen += metrics.advance_width.ceil();
+    }
+    result
+}
+
+/// Returns the ellipsis to use with the glyph's font, and its width
+/// at the glyph's size.
+fn ellipsis_glyphs<U: Copy>(fonts: &[Font], glyph: &GlyphPosition<U>) -> (&'static str, f32) {
+    let font = &fonts[glyph.font_index];
+    let ellipsis = if font.lookup_glyph_index('…') != 0 {
+        "…"
+    } else {
+        "..."
+    };
+    let width = ellipsis
+        .chars()
+        .map(|c| font.metrics(c, glyph.key.px).advance_width.ceil())
+        .sum();
+    (ellipsis, width)
+}
@@ -0,0 +1,67 @@
+use std::error::Error;
+use std::fmt;
+
+/// The errors returned by [`FontTexture`](crate::FontTexture).
+///
+/// All of the variants contain an informational string, usually from
+/// SDL. The [`Display`](fmt::Display) implementation includes it, and
+/// the error can be converted into a [`String`], so code written for
+/// the `Result<_, String>` returning functions of earlier versions
+/// keeps working.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FontTextureError {
+    /// The glyph cache texture could not be created.
+    TextureCreation(String),
+    /// Newly rasterized glyphs could not be uploaded to the glyph
+    /// cache texture.
+    TextureUpdate(String),
+    /// Glyphs could not be copied from the glyph cache texture to the
+    /// canvas.
+    Copy(String),
+    /// Something other than glyphs, e.g. decorations, could not be
+    /// drawn on the canvas.
+    Draw(String),
+    /// The glyph cache could not be saved or loaded, because of an
+    /// I/O error or an invalid file.
+    CacheFile(String),
+    /// The fonts passed to a draw aren't the ones bound to the
+    /// [`FontTexture`](crate::FontTexture) with
+    /// [`FontTexture::bind_fonts`](crate::FontTexture::bind_fonts).
+    FontMismatch(String),
+    /// The requested size of the glyph cache texture is larger than
+    /// the renderer's maximum texture size, see
+    /// [`FontTexture::max_texture_size`](crate::FontTexture::max_texture_size),
+    /// or has more pixels than a `u32` can count.
+    AtlasTooLarge(String),
+    /// The glyph cache can't be repacked with
+    /// [`FontTexture::defragment`](crate::FontTexture::defragment) or
+    /// [`FontTexture::resize`](crate::FontTexture::resize) while there
+    /// are draws waiting for
+    /// [`FontTexture::flush_uploads`](crate::FontTexture::flush_uploads),
+    /// since they would draw the glyphs from their old places.
+    PendingDraws(String),
+}
+
+impl fmt::Display for FontTextureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FontTextureError::*;
+        match self {
+            TextureCreation(s) => write!(f, "could not create the glyph cache texture: {}", s),
+            TextureUpdate(s) => write!(f, "could not update the glyph cache texture: {}", s),
+            Copy(s) => write!(f, "could not copy glyphs to the canvas: {}", s),
+            Draw(s) => write!(f, "could not draw on the canvas: {}", s),
+            CacheFile(s) => write!(f, "could not save or load the glyph cache: {}", s),
+            FontMismatch(s) => write!(f, "the fonts are not the bound fonts: {}", s),
+            AtlasTooLarge(s) => write!(f, "the glyph cache texture is too large: {}", s),
+            PendingDraws(s) => write!(f, "the glyph cache has pending draws: {}", s),
+        }
+    }
+}
+
+impl Error for FontTextureError {}
+
+impl From<FontTextureError> for String {
+    fn from(err: FontTextureError) -> String {
+        err.to_string()
+    }
+}
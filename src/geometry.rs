@@ -0,0 +1,234 @@
+use fontdue::layout::{GlyphPosition, GlyphRasterConfig};
+use fontdue::{Font, Metrics};
+use sdl2::rect::{FPoint, FRect, Rect};
+use std::collections::HashMap;
+
+/// Returns the rectangle on the canvas that the glyph is drawn into,
+/// or None for glyphs with no area (e.g. spaces, or zero-width
+/// marks), which aren't drawn. Such glyphs still have a position and
+/// an advance, which decorations and highlights use, see
+/// [`glyph_line_position`](crate::decoration::glyph_line_position).
+///
+/// Glyphs with a position that isn't finite, or a size that doesn't
+/// fit in a [Rect], e.g. from a malformed layout, aren't drawn
+/// either.
+pub(crate) fn glyph_canvas_rect<U: Copy>(glyph: &GlyphPosition<U>) -> Option<Rect> {
+    if glyph.width == 0 || glyph.height == 0 {
+        return None;
+    }
+    if !glyph.x.is_finite()
+        || !glyph.y.is_finite()
+        || glyph.width > i32::MAX as usize
+        || glyph.height > i32::MAX as usize
+    {
+        return None;
+    }
+    // Rounded down, so that any fractional part of the position is
+    // between 0 and 1 also for negative positions.
+    Some(Rect::new(
+        glyph.x.floor() as i32,
+        glyph.y.floor() as i32,
+        glyph.width as u32,
+        glyph.height as u32,
+    ))
+}
+
+/// The metrics of glyphs at sizes other than the ones they were laid
+/// out at, e.g. when drawn scaled, so that they aren't looked up from
+/// the font again for every glyph of every draw.
+#[derive(Default)]
+pub(crate) struct MetricsCache {
+    metrics: HashMap<GlyphRasterConfig, Metrics>,
+}
+
+impl MetricsCache {
+    /// The amount of metrics kept before the cache is emptied, so
+    /// that text zoomed through lots of sizes doesn't fill it up.
+    const CAPACITY: usize = 4096;
+
+    /// Returns the metrics of the glyph, rasterized with the config.
+    pub fn metrics(&mut self, font: &Font, config: GlyphRasterConfig) -> Metrics {
+        if let Some(metrics) = self.metrics.get(&config) {
+            return *metrics;
+        }
+        if self.metrics.len() >= MetricsCache::CAPACITY {
+            self.metrics.clear();
+        }
+        let metrics = font.metrics_indexed(config.glyph_index, config.px);
+        self.metrics.insert(config, metrics);
+        metrics
+    }
+}
+
+/// Returns the glyph as if it was laid out at `scale` times its
+/// size, with its position scaled from the origin.
+pub(crate) fn scale_glyph<U: Copy>(
+    metrics_cache: &mut MetricsCache,
+    fonts: &[Font],
+    glyph: &GlyphPosition<U>,
+    scale: f32,
+) -> GlyphPosition<U> {
+    let mut scaled = *glyph;
+    scaled.key.px *= scale;
+    let metrics = metrics_cache.metrics(&fonts[glyph.font_index], scaled.key);
+    scaled.x *= scale;
+    scaled.y *= scale;
+    scaled.width = metrics.width;
+    scaled.height = metrics.height;
+    scaled
+}
+
+/// How [`FontTexture::draw_text_fitted`](crate::FontTexture::draw_text_fitted)
+/// fits text into a rectangle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FitMode {
+    /// The text is scaled uniformly to be as big as possible while
+    /// still fitting entirely inside the rectangle.
+    Contain,
+    /// The text is scaled uniformly to cover the whole rectangle,
+    /// with the parts outside it clipped off.
+    Cover,
+    /// The text is scaled separately horizontally and vertically to
+    /// fill the rectangle exactly.
+    Stretch,
+}
+
+/// Returns the horizontal and vertical scale which fits text with
+/// the bounds (see [`text_bounds`]) into the target rectangle, with
+/// the fit mode.
+///
+/// ```
+/// # use fontdue_sdl2::{fit_scale, FitMode};
+/// # use sdl2::rect::Rect;
+/// let label = Rect::new(0, 0, 200, 20);
+/// let button = Rect::new(10, 10, 100, 40);
+/// assert_eq!(fit_scale(label, button, FitMode::Contain), (0.5, 0.5));
+/// assert_eq!(fit_scale(label, button, FitMode::Cover), (2.0, 2.0));
+/// assert_eq!(fit_scale(label, button, FitMode::Stretch), (0.5, 2.0));
+/// ```
+pub fn fit_scale(bounds: Rect, target: Rect, mode: FitMode) -> (f32, f32) {
+    let scale_x = target.width() as f32 / bounds.width() as f32;
+    let scale_y = target.height() as f32 / bounds.height() as f32;
+    match mode {
+        FitMode::Contain => (scale_x.min(scale_y), scale_x.min(scale_y)),
+        FitMode::Cover => (scale_x.max(scale_y), scale_x.max(scale_y)),
+        FitMode::Stretch => (scale_x, scale_y),
+    }
+}
+
+/// Returns the corners of the rectangle transformed by the affine
+/// transform `matrix`, clockwise from the top-left corner, i.e. where
+/// [`FontTexture::draw_text_matrix`](crate::FontTexture::draw_text_matrix)
+/// draws the corners of a glyph with the rectangle.
+///
+/// The matrix is `[a, b, c, d, e, f]`, which maps a point `(x, y)` to
+/// `(a * x + c * y + e, b * x + d * y + f)`, like the `matrix()`
+/// transform function in CSS. For example, shearing the top of a
+/// glyph to the right, as in faux-italic text:
+///
+/// ```
+/// # use fontdue_sdl2::transform_corners;
+/// # use sdl2::rect::{FPoint, FRect};
+/// // Each pixel up moves the glyph 0.25 pixels right, relative to
+/// // the baseline at y = 40.
+/// let slant = 0.25;
+/// let italic = [1.0, 0.0, -slant, 1.0, slant * 40.0, 0.0];
+/// let glyph = FRect::new(10.0, 20.0, 8.0, 20.0);
+/// let corners = transform_corners(glyph, italic);
+/// assert_eq!(corners[0], FPoint::new(15.0, 20.0)); // top-left
+/// assert_eq!(corners[1], FPoint::new(23.0, 20.0)); // top-right
+/// assert_eq!(corners[2], FPoint::new(18.0, 40.0)); // bottom-right
+/// assert_eq!(corners[3], FPoint::new(10.0, 40.0)); // bottom-left
+/// ```
+pub fn transform_corners(rect: FRect, matrix: [f32; 6]) -> [FPoint; 4] {
+    let [a, b, c, d, e, f] = matrix;
+    let transform = |x: f32, y: f32| FPoint::new(a * x + c * y + e, b * x + d * y + f);
+    [
+        transform(rect.left(), rect.top()),
+        transform(rect.right(), rect.top()),
+        transform(rect.right(), rect.bottom()),
+        transform(rect.left(), rect.bottom()),
+    ]
+}
+
+/// Returns the bounding box of the glyphs, i.e. the smallest
+/// rectangle containing every pixel that
+/// [`FontTexture::draw_text`](crate::FontTexture::draw_text) would
+/// draw for them. Returns None if no glyph would be drawn, e.g. if
+/// the slice is empty or only has spaces.
+///
+/// Useful for sizing e.g. a background panel before drawing the text
+/// on top of it.
+///
+/// ```
+/// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+/// # use fontdue_sdl2::{text_bounds, FontTexture};
+/// # use sdl2::pixels::{Color, PixelFormatEnum};
+/// # use sdl2::rect::Rect;
+/// # use sdl2::surface::Surface;
+/// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+/// # let mut canvas = surface.into_canvas().unwrap();
+/// # let texture_creator = canvas.texture_creator();
+/// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+/// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+/// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+/// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+/// layout.append(fonts, &TextStyle::with_user_data("Hello, World!", 32.0, 0, Color::WHITE));
+/// let bounds = text_bounds(layout.glyphs()).unwrap();
+///
+/// // The bounds span from the first glyph to the last one.
+/// let glyphs = layout.glyphs();
+/// assert_eq!(bounds.left(), glyphs[0].x as i32);
+/// let last = glyphs.last().unwrap();
+/// assert_eq!(bounds.right(), last.x as i32 + last.width as i32);
+///
+/// // And they're the smallest rectangle containing all drawn pixels.
+/// canvas.set_draw_color(Color::BLACK);
+/// canvas.clear();
+/// font_texture.draw_text(&mut canvas, fonts, glyphs).unwrap();
+/// let pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+/// let drawn = (0..256 * 64).filter(|i| pixels[i * 4] > 0).map(|i| (i % 256, i / 256));
+/// let (xs, ys): (Vec<_>, Vec<_>) = drawn.unzip();
+/// let (left, top) = (*xs.iter().min().unwrap(), *ys.iter().min().unwrap());
+/// let (right, bottom) = (*xs.iter().max().unwrap() + 1, *ys.iter().max().unwrap() + 1);
+/// let drawn_bounds = Rect::new(left as i32, top as i32, (right - left) as u32, (bottom - top) as u32);
+/// assert_eq!(bounds, drawn_bounds);
+/// ```
+pub fn text_bounds<U: Copy>(glyphs: &[GlyphPosition<U>]) -> Option<Rect> {
+    glyphs
+        .iter()
+        .filter_map(glyph_canvas_rect)
+        .reduce(|bounds, rect| bounds.union(rect))
+}
+
+/// Returns the rectangles on the canvas that
+/// [`FontTexture::draw_text`](crate::FontTexture::draw_text) would
+/// draw the glyphs into, along with the index of each glyph in the
+/// slice. Glyphs that aren't drawn (e.g. spaces) are left out.
+///
+/// Useful for hit-testing, e.g. finding the glyph under the mouse
+/// cursor:
+///
+/// ```
+/// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+/// # use sdl2::pixels::Color;
+/// # use sdl2::rect::Point;
+/// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+/// # let roboto_regular = Font::from_bytes(font, Default::default()).unwrap();
+/// # let fonts = &[roboto_regular];
+/// # let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+/// # layout.append(fonts, &TextStyle::with_user_data("Hello, World!", 32.0, 0, Color::WHITE));
+/// let mouse = Point::new(10, 20);
+/// let hovered_glyph = fontdue_sdl2::layout_draw_rects(layout.glyphs())
+///     .into_iter()
+///     .find(|(_, rect)| rect.contains_point(mouse))
+///     .map(|(index, _)| &layout.glyphs()[index]);
+/// # assert!(hovered_glyph.is_some());
+/// ```
+pub fn layout_draw_rects<U: Copy>(glyphs: &[GlyphPosition<U>]) -> Vec<(usize, Rect)> {
+    glyphs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, glyph)| Some((i, glyph_canvas_rect(glyph)?)))
+        .collect()
+}
@@ -0,0 +1,418 @@
+use crate::geometry::MetricsCache;
+use crate::quads::QuadBuffers;
+use crate::{
+    CacheStats, DrawBuffers, DrawReport, FontTextureError, FrameStats, PendingDraw, RectAllocator,
+    UnderlineMetrics, Warning,
+};
+use fontdue::layout::GlyphPosition;
+use fontdue::Metrics;
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::Rect;
+use sdl2::render::Texture;
+use sdl2::sys;
+use std::collections::HashMap;
+use std::mem::MaybeUninit;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+/// A bitmap drawn instead of a glyph from the font, see
+/// [`FontTexture::set_glyph_override`](crate::FontTexture::set_glyph_override).
+pub struct GlyphOverride {
+    pub rasterize: Box<dyn Fn() -> (Metrics, Vec<u8>)>,
+    /// The result of `rasterize`, once it's been called.
+    pub rasterized: Option<(Metrics, Vec<u8>)>,
+}
+
+/// The bookkeeping for the glyphs in a glyph cache texture.
+///
+/// Also holds a copy of the texture's pixels in memory, so that all
+/// the glyphs rasterized during a draw can be uploaded to the texture
+/// with a single update, instead of one update per glyph.
+pub struct GlyphCache {
+    pub rect_allocator: RectAllocator,
+    /// The amount of transparent pixels around each glyph.
+    pub padding: u32,
+    width: u32,
+    /// Whether the texture is BGRA32 instead of RGBA32, so the red and
+    /// blue channels need to be swapped when writing pixels.
+    bgra: bool,
+    pixels: Vec<u8>,
+    /// The area of `pixels` that has been written to since the last
+    /// upload.
+    dirty_rect: Option<Rect>,
+    pub draw_buffers: DrawBuffers,
+    /// The quads of batched draws, see
+    /// [`FontTexture::set_batched`](crate::FontTexture::set_batched).
+    /// Kept between draws like `draw_buffers`.
+    pub quad_buffers: QuadBuffers,
+    /// The glyphs passed to [`FontTexture::draw_text`](crate::FontTexture::draw_text),
+    /// collected from its iterator. Kept between draws like
+    /// `draw_buffers`.
+    pub glyph_buffer: Vec<GlyphPosition<Color>>,
+    /// The characters of the missing glyphs of the last draw, given
+    /// out in [`DrawReport::missing_glyphs`](crate::DrawReport::missing_glyphs),
+    /// and taken back from the reports that aren't returned to the
+    /// caller with [`GlyphCache::reuse_report`]. Kept between draws
+    /// like `draw_buffers`.
+    pub missing_chars: Vec<char>,
+    /// The indices and canvas rectangles of the glyphs drawn by the
+    /// last draw, for [`FontTexture::hit_test`](crate::FontTexture::hit_test).
+    pub hit_rects: Vec<(usize, Rect)>,
+    /// The draws waiting for [`FontTexture::flush_uploads`](crate::FontTexture::flush_uploads).
+    pub pending_draws: Vec<PendingDraw>,
+    /// See [`FontTexture::set_warning_handler`](crate::FontTexture::set_warning_handler).
+    pub warning_handler: Option<Box<dyn FnMut(Warning)>>,
+    /// The glyph overrides by character and the bits of their size.
+    pub glyph_overrides: HashMap<(char, u32), GlyphOverride>,
+    /// See [`FontTexture::last_frame_stats`](crate::FontTexture::last_frame_stats).
+    pub frame_stats: FrameStats,
+    /// Whether [`FontTexture::begin_frame`](crate::FontTexture::begin_frame)
+    /// has been called, so that draws add to `frame_stats` instead of
+    /// resetting it.
+    pub frame_started: bool,
+    /// See [`FontTexture::set_underline_metrics`](crate::FontTexture::set_underline_metrics),
+    /// by the [`Font::file_hash`](fontdue::Font::file_hash) of the font.
+    pub underline_metrics: HashMap<usize, UnderlineMetrics>,
+    /// The identities of the fonts bound with
+    /// [`FontTexture::bind_fonts`](crate::FontTexture::bind_fonts),
+    /// see [`font_identity`](crate::font_identity).
+    pub bound_fonts: Option<Vec<u64>>,
+    pub metrics_cache: MetricsCache,
+}
+
+impl GlyphCache {
+    pub fn new(width: u32, height: u32, format: PixelFormatEnum) -> GlyphCache {
+        GlyphCache {
+            rect_allocator: RectAllocator::new(width, height),
+            padding: crate::DEFAULT_GLYPH_PADDING,
+            width,
+            bgra: format == PixelFormatEnum::BGRA32,
+            pixels: vec![0; width as usize * height as usize * 4],
+            dirty_rect: None,
+            draw_buffers: DrawBuffers::default(),
+            quad_buffers: QuadBuffers::default(),
+            glyph_buffer: Vec::new(),
+            missing_chars: Vec::new(),
+            hit_rects: Vec::new(),
+            pending_draws: Vec::new(),
+            warning_handler: None,
+            glyph_overrides: HashMap::new(),
+            frame_stats: FrameStats::default(),
+            frame_started: false,
+            underline_metrics: HashMap::new(),
+            bound_fonts: None,
+            metrics_cache: MetricsCache::default(),
+        }
+    }
+
+    /// Takes back the memory of a report that isn't returned to the
+    /// caller, so that the next draw can reuse it.
+    pub fn reuse_report(&mut self, report: DrawReport) {
+        self.missing_chars = report.missing_glyphs;
+    }
+
+    /// Returns the metrics and coverage of the character's glyph
+    /// override at the size, if it has one. The override is
+    /// rasterized the first time it's needed.
+    pub fn glyph_override(&mut self, character: char, px: f32) -> Option<&(Metrics, Vec<u8>)> {
+        if self.glyph_overrides.is_empty() {
+            return None;
+        }
+        let GlyphOverride {
+            rasterize,
+            rasterized,
+        } = self.glyph_overrides.get_mut(&(character, px.to_bits()))?;
+        Some(rasterized.get_or_insert_with(rasterize))
+    }
+
+    /// Passes the warning to the warning handler, or logs it if
+    /// there isn't one.
+    pub fn warn(&mut self, warning: Warning) {
+        match &mut self.warning_handler {
+            Some(handler) => handler(warning),
+            None => log_error!("{}", warning),
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.rect_allocator.stats()
+    }
+
+    /// Writes RGBA pixels, tightly packed rows of `rect.width()`
+    /// pixels, into the in-memory copy of the texture, in the
+    /// texture's channel order. They will be
+    /// uploaded by the next [GlyphCache::upload].
+    ///
+    /// Only the rows that fit in the rect are written, so that a
+    /// buffer of the wrong size can't overwrite the neighboring
+    /// glyphs.
+    pub fn write_pixels(&mut self, rect: Rect, rgba_pixels: &[u8]) {
+        let row_length = rect.width() as usize * 4;
+        let pitch = self.width as usize * 4;
+        debug_assert_eq!(rgba_pixels.len(), row_length * rect.height() as usize);
+        let rows = rgba_pixels
+            .chunks_exact(row_length)
+            .take(rect.height() as usize);
+        for (y, row) in rows.enumerate() {
+            let start = (rect.y() as usize + y) * pitch + rect.x() as usize * 4;
+            let target = &mut self.pixels[start..start + row_length];
+            target.copy_from_slice(row);
+            if self.bgra {
+                for pixel in target.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+            }
+        }
+        self.dirty_rect = Some(match self.dirty_rect {
+            Some(dirty_rect) => dirty_rect.union(rect),
+            None => rect,
+        });
+    }
+
+    /// Returns the RGBA pixels in the rect, in tightly packed rows,
+    /// i.e. the inverse of [GlyphCache::write_pixels].
+    pub fn read_pixels(&self, rect: Rect) -> Vec<u8> {
+        let row_length = rect.width() as usize * 4;
+        let pitch = self.width as usize * 4;
+        let mut rgba_pixels = Vec::with_capacity(row_length * rect.height() as usize);
+        for y in rect.top()..rect.bottom() {
+            let start = y as usize * pitch + rect.x() as usize * 4;
+            rgba_pixels.extend_from_slice(&self.pixels[start..start + row_length]);
+        }
+        if self.bgra {
+            for pixel in rgba_pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        rgba_pixels
+    }
+
+    /// Writes RGBA pixels like [GlyphCache::write_pixels], into the
+    /// middle of `padded_rect`, leaving a border of
+    /// [GlyphCache::padding] transparent pixels around them.
+    pub fn write_padded_pixels(&mut self, padded_rect: Rect, rgba_pixels: &[u8]) {
+        let padding = self.padding;
+        let pitch = self.width as usize * 4;
+        for y in padded_rect.top()..padded_rect.bottom() {
+            let start = y as usize * pitch + padded_rect.x() as usize * 4;
+            self.pixels[start..start + padded_rect.width() as usize * 4].fill(0);
+        }
+        let rect = Rect::new(
+            padded_rect.x() + padding as i32,
+            padded_rect.y() + padding as i32,
+            padded_rect.width() - padding * 2,
+            padded_rect.height() - padding * 2,
+        );
+        self.write_pixels(rect, rgba_pixels);
+        self.dirty_rect = self
+            .dirty_rect
+            .map(|dirty_rect| dirty_rect.union(padded_rect));
+    }
+
+    /// Repacks the glyphs in the cache, see [RectAllocator::repack],
+    /// moving their pixels to their new places, and uploads the whole
+    /// texture.
+    pub fn defragment(&mut self, texture: &mut Texture) -> Result<(), FontTextureError> {
+        self.repack_with(texture, RectAllocator::repack)
+    }
+
+    /// Changes the size of the glyph cache to match `texture`, which
+    /// should be a new `width`x`height` texture, and uploads the
+    /// glyphs to it from memory, without rasterizing them again.
+    /// Glyphs that don't fit in the new size are evicted.
+    pub fn resize(
+        &mut self,
+        texture: &mut Texture,
+        width: u32,
+        height: u32,
+    ) -> Result<(), FontTextureError> {
+        self.repack_with(texture, |rect_allocator| {
+            rect_allocator.resize(width, height)
+        })
+    }
+
+    /// Repacks a copy of the rect allocator with `repack`, moves the
+    /// pixels of the glyphs to their new rects, and uploads the whole
+    /// texture. The glyph cache is only changed if the upload
+    /// succeeds, so a failed upload leaves it as it was. Fails if
+    /// there are pending draws, which refer to the old rects.
+    fn repack_with(
+        &mut self,
+        texture: &mut Texture,
+        repack: impl FnOnce(&mut RectAllocator) -> Vec<(Rect, Rect)>,
+    ) -> Result<(), FontTextureError> {
+        if !self.pending_draws.is_empty() {
+            return Err(FontTextureError::PendingDraws(format!(
+                "{} draws should be flushed first",
+                self.pending_draws.len()
+            )));
+        }
+        let mut rect_allocator = self.rect_allocator.clone();
+        let moves = repack(&mut rect_allocator);
+        let (width, height) = (
+            rect_allocator.packer.width(),
+            rect_allocator.packer.height(),
+        );
+        let pixels = self.moved_pixels(&rect_allocator, width, height, &moves);
+        let pitch = width as usize * 4;
+        texture
+            .update(Rect::new(0, 0, width, height), &pixels, pitch)
+            .map_err(|err| FontTextureError::TextureUpdate(err.to_string()))?;
+        self.frame_stats.uploaded_bytes += pixels.len();
+        self.rect_allocator = rect_allocator;
+        self.pixels = pixels;
+        self.width = width;
+        // The whole texture was just uploaded.
+        self.dirty_rect = None;
+        Ok(())
+    }
+
+    /// Returns the pixels of a `width`x`height` texture with the
+    /// glyphs moved from their old rects to their new ones, after
+    /// `rect_allocator` has repacked them. The pixels of the reserved
+    /// areas are kept.
+    fn moved_pixels(
+        &self,
+        rect_allocator: &RectAllocator,
+        width: u32,
+        height: u32,
+        moves: &[(Rect, Rect)],
+    ) -> Vec<u8> {
+        let old_pitch = self.width as usize * 4;
+        let new_pitch = width as usize * 4;
+        let mut pixels = vec![0; new_pitch * height as usize];
+        // The reserved areas stay where they are, as far as they fit.
+        let old_area = Rect::new(
+            0,
+            0,
+            self.width,
+            self.pixels.len() as u32 / old_pitch as u32,
+        );
+        let new_area = Rect::new(0, 0, width, height);
+        let reserved_moves = rect_allocator
+            .packer
+            .reserved()
+            .iter()
+            .filter_map(|reserved| reserved.intersection(old_area)?.intersection(new_area))
+            .map(|reserved| (reserved, reserved))
+            .collect::<Vec<_>>();
+        for (old_rect, new_rect) in moves.iter().chain(&reserved_moves) {
+            let row_length = old_rect.width() as usize * 4;
+            for y in 0..old_rect.height() as usize {
+                let from = (old_rect.y() as usize + y) * old_pitch + old_rect.x() as usize * 4;
+                let to = (new_rect.y() as usize + y) * new_pitch + new_rect.x() as usize * 4;
+                pixels[to..to + row_length].copy_from_slice(&self.pixels[from..from + row_length]);
+            }
+        }
+        pixels
+    }
+
+    /// Returns the pixels of the rect in the in-memory copy, and
+    /// their pitch. The rows of the rect are the width of the whole
+    /// texture apart, so the pitch is the texture's, not the rect's.
+    /// The slice ends at the end of the rect's last row, so that SDL
+    /// can't read past it.
+    fn rect_pixels(&self, rect: Rect) -> (&[u8], usize) {
+        let pitch = self.width as usize * 4;
+        let start = rect.y() as usize * pitch + rect.x() as usize * 4;
+        let end = start + (rect.height() as usize - 1) * pitch + rect.width() as usize * 4;
+        (&self.pixels[start..end], pitch)
+    }
+
+    /// Uploads the pixels written since the last upload to the
+    /// texture. If the upload fails, the pixels are uploaded again by
+    /// the next upload.
+    pub fn upload(&mut self, texture: &mut Texture) -> Result<(), FontTextureError> {
+        if let Some(rect) = self.dirty_rect {
+            let (pixels, pitch) = self.rect_pixels(rect);
+            texture
+                .update(rect, pixels, pitch)
+                .map_err(|err| FontTextureError::TextureUpdate(err.to_string()))?;
+            self.dirty_rect = None;
+            self.frame_stats.uploaded_bytes += rect.width() as usize * rect.height() as usize * 4;
+        }
+        Ok(())
+    }
+
+    /// Uploads the pixels written since the last upload to the
+    /// texture like [GlyphCache::upload], but by rendering them into
+    /// it from a temporary static texture, for glyph cache textures
+    /// created with [TextureAccess::Target]. Some drivers handle this
+    /// better than updating a target texture directly.
+    ///
+    /// # Safety
+    ///
+    /// The renderer pointer must be valid, and the texture must be a
+    /// target texture created for the renderer.
+    ///
+    /// [TextureAccess::Target]: sdl2::render::TextureAccess::Target
+    pub unsafe fn upload_rendered(
+        &mut self,
+        texture: &mut Texture,
+        renderer: *mut sys::SDL_Renderer,
+    ) -> Result<(), FontTextureError> {
+        let rect = match self.dirty_rect {
+            Some(rect) => rect,
+            None => return Ok(()),
+        };
+        let staging = sys::SDL_CreateTexture(
+            renderer,
+            texture.query().format as u32,
+            sys::SDL_TextureAccess::SDL_TEXTUREACCESS_STATIC as c_int,
+            rect.width() as c_int,
+            rect.height() as c_int,
+        );
+        if staging.is_null() {
+            return Err(FontTextureError::TextureUpdate(sdl2::get_error()));
+        }
+        let (pixels, pitch) = self.rect_pixels(rect);
+        let mut result = sys::SDL_UpdateTexture(
+            staging,
+            ptr::null(),
+            pixels.as_ptr() as *const c_void,
+            pitch as c_int,
+        );
+        if result == 0 {
+            // The glyphs' pixels replace the texture's, instead of
+            // being blended with them.
+            let blend_mode = sys::SDL_BlendMode::SDL_BLENDMODE_NONE as u32;
+            if crate::blend::set_texture_ptr_blend_mode(staging, blend_mode).is_err() {
+                result = -1;
+            }
+        }
+        if result == 0 {
+            // SDL resets the viewport, clip rect and scale when the
+            // render target changes, so they're restored for the
+            // previous target, if it's a texture. For the window,
+            // SDL restores them itself.
+            let previous_target = sys::SDL_GetRenderTarget(renderer);
+            let mut viewport = MaybeUninit::uninit();
+            let mut clip_rect = MaybeUninit::uninit();
+            let (mut scale_x, mut scale_y) = (1.0, 1.0);
+            sys::SDL_RenderGetViewport(renderer, viewport.as_mut_ptr());
+            sys::SDL_RenderGetClipRect(renderer, clip_rect.as_mut_ptr());
+            let clipped = sys::SDL_RenderIsClipEnabled(renderer) == sys::SDL_bool::SDL_TRUE;
+            sys::SDL_RenderGetScale(renderer, &mut scale_x, &mut scale_y);
+
+            result = sys::SDL_SetRenderTarget(renderer, texture.raw());
+            if result == 0 {
+                result = sys::SDL_RenderCopy(renderer, staging, ptr::null(), rect.raw());
+                sys::SDL_SetRenderTarget(renderer, previous_target);
+                if !previous_target.is_null() {
+                    sys::SDL_RenderSetViewport(renderer, viewport.as_ptr());
+                    sys::SDL_RenderSetScale(renderer, scale_x, scale_y);
+                    if clipped {
+                        sys::SDL_RenderSetClipRect(renderer, clip_rect.as_ptr());
+                    }
+                }
+            }
+        }
+        sys::SDL_DestroyTexture(staging);
+        if result != 0 {
+            return Err(FontTextureError::TextureUpdate(sdl2::get_error()));
+        }
+        self.dirty_rect = None;
+        self.frame_stats.uploaded_bytes += rect.width() as usize * rect.height() as usize * 4;
+        Ok(())
+    }
+}
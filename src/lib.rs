@@ -56,22 +56,92 @@
 //! rendering setup. It's often valid to simply ignore, or crash.
 //!
 //! The [`FontTexture`] is intended to be created once, at the
-//! beginning of your program, and then used throughout. Generally,
-//! you should treat [`FontTexture`] similarly to the [`Font`]-slice
-//! passed to fontdue, and associate each [`FontTexture`] with a
-//! specific `&[Font]`. See the [`FontTexture`] documentation for more
-//! information.
+//! beginning of your program, and then used throughout. It can be
+//! shared between different `&[Font]`s, as long as each set of
+//! glyphs is drawn with the fonts it was laid out with. See the
+//! [`FontTexture`] documentation for more information.
 //!
 //! See `examples/basic.rs` for a complete example program.
 //!
+//! # Rendering without a window
+//!
+//! Text can also be drawn onto a [`Surface`](sdl2::surface::Surface)
+//! in memory, e.g. for generating images, or in tests running
+//! without a display. SDL's software renderer draws on the surface,
+//! so neither a window nor a GPU is needed, and SDL doesn't even need
+//! to be initialized. The [`FontTexture`] is created with the
+//! surface canvas' [`TextureCreator`], and the pixels can be read
+//! back from the canvas or the surface afterwards:
+//!
+//! ```
+//! # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+//! # use fontdue_sdl2::FontTexture;
+//! # use sdl2::pixels::{Color, PixelFormatEnum};
+//! # use sdl2::surface::Surface;
+//! # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+//! # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+//! # let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+//! # layout.append(fonts, &TextStyle::with_user_data("Hello, World!", 32.0, 0, Color::WHITE));
+//! let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+//! let mut canvas = surface.into_canvas().unwrap();
+//! let texture_creator = canvas.texture_creator();
+//! let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+//! canvas.set_draw_color(Color::BLACK);
+//! canvas.clear();
+//! font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+//!
+//! let surface = canvas.into_surface();
+//! let pixels = surface.without_lock().unwrap();
+//! let bounds = fontdue_sdl2::text_bounds(layout.glyphs()).unwrap();
+//! let pitch = surface.pitch() as usize;
+//! let lit_pixels = (bounds.top()..bounds.bottom())
+//!     .flat_map(|y| (bounds.left()..bounds.right()).map(move |x| (x, y)))
+//!     .filter(|&(x, y)| pixels[y as usize * pitch + x as usize * 4] > 0)
+//!     .count();
+//! assert!(lit_pixels > 0);
+//! ```
+//!
+//! # Cargo features
+//!
+//! - `logging` (enabled by default): logs problems like glyphs that
+//!   don't fit in the glyph cache with the [log] crate. Without it,
+//!   the crate doesn't depend on `log`.
+//! - `rayon`: rasterizes the new glyphs of a draw on multiple
+//!   threads with [rayon], which speeds up draws with many new
+//!   glyphs, e.g. the first frame of a text-heavy screen. The glyphs
+//!   are still uploaded to the glyph cache texture all at once, on
+//!   the drawing thread.
+//! - `unsafe_textures`: enables the `unsafe_textures` feature of
+//!   [sdl2], which removes the lifetime from [`FontTexture`], like
+//!   from SDL's textures.
+//!
+//! # Limitations
+//!
+//! Glyphs are rasterized by fontdue, which only rasterizes glyph
+//! outlines into coverage. Color glyphs, like the emoji in CBDT or
+//! COLR fonts, aren't supported: fontdue doesn't read their color
+//! data, so they're drawn as outlines in the glyph's color, if the
+//! font has outlines for them at all.
+//!
 //! [fontdue]: https://docs.rs/fontdue
 //! [sdl2]: https://docs.rs/sdl2
+//! [log]: https://docs.rs/log
+//! [rayon]: https://docs.rs/rayon
 
-use fontdue::layout::GlyphPosition;
-use fontdue::Font;
+use fontdue::layout::{CoordinateSystem, GlyphPosition, GlyphRasterConfig, Layout, TextStyle};
+use fontdue::{Font, Metrics};
 use sdl2::pixels::{Color, PixelFormatEnum};
-use sdl2::rect::Rect;
-use sdl2::render::{BlendMode, Canvas, RenderTarget, Texture, TextureCreator};
+use sdl2::rect::{FPoint, FRect, Point, Rect};
+use sdl2::render::{
+    BlendMode, Canvas, RenderTarget, RendererInfo, Texture, TextureAccess, TextureCreator,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+#[macro_use]
+mod logging;
 
 #[cfg(not(feature = "unsafe_textures"))]
 mod public_api;
@@ -83,111 +153,2664 @@ mod public_api_no_lifetimes;
 use public_api_no_lifetimes as public_api;
 
 mod rect_allocator;
-use rect_allocator::{CacheReservation, RectAllocator};
+mod rect_packer;
+use oversized::OversizedGlyph;
+use quads::QuadBuffers;
+use rect_allocator::{CacheReservation, GlyphKey, RectAllocator};
+
+mod appended;
+mod blend;
+
+mod glyph_cache;
+use glyph_cache::{GlyphCache, GlyphOverride};
+
+mod builder;
+mod decoration;
+mod ellipsis;
+mod error;
+mod geometry;
+mod oversized;
+mod persist;
+mod prerasterize;
+mod quads;
+mod stats;
+mod warning;
+
+pub use appended::AppendedText;
+pub use builder::FontTextureBuilder;
+pub use decoration::{caret_rect, Decoration, DecorationStyle, UnderlineMetrics};
+pub use ellipsis::ellipsize;
+pub use error::FontTextureError;
+pub use geometry::{fit_scale, layout_draw_rects, text_bounds, transform_corners, FitMode};
+pub use prerasterize::{GlyphRasterizer, RasterizedGlyphs};
+pub use public_api::{FontTexture, SharedFontTexture, SharedFontTextureMut, SimpleText};
+pub use rect_packer::RectPacker;
+pub use stats::{CacheStats, DrawReport, FrameStats};
+pub use warning::Warning;
 
-pub use public_api::FontTexture;
 pub use fontdue;
 pub use sdl2;
 
-/// Called by [FontTexture::new].
+/// A [`Texture`] tied to the lifetime of its [`TextureCreator`], when
+/// the `unsafe_textures` feature isn't enabled.
+#[cfg(not(feature = "unsafe_textures"))]
+type OwnedTexture<'r> = Texture<'r>;
+#[cfg(feature = "unsafe_textures")]
+type OwnedTexture<'r> = Texture;
+
+/// The width and height of the glyph cache texture created by
+/// [FontTexture::new].
+pub(crate) const DEFAULT_TEXTURE_SIZE: u32 = 1024;
+
+/// The default amount of transparent pixels reserved around each
+/// glyph in the glyph cache, so that scaling or drawing glyphs at
+/// fractional positions with linear filtering doesn't bleed the
+/// neighboring glyphs in. See [FontTextureBuilder::padding].
+pub(crate) const DEFAULT_GLYPH_PADDING: u32 = 1;
+
+/// The largest width or height of a glyph that is rasterized. Any
+/// bigger glyph is most likely from a malformed layout, and too big
+/// to fit in a texture anyway, so it's skipped instead.
+const MAX_GLYPH_SIZE: u32 = 16384;
+
+/// Returns the glyph's area within the padded area reserved for it.
+fn unpadded_rect(padded_rect: Rect, padding: u32) -> Rect {
+    Rect::new(
+        padded_rect.x() + padding as i32,
+        padded_rect.y() + padding as i32,
+        padded_rect.width() - padding * 2,
+        padded_rect.height() - padding * 2,
+    )
+}
+
+/// Sets the filtering used when the texture is drawn scaled or at
+/// fractional positions.
+pub(crate) fn set_scale_mode(
+    texture: &mut Texture,
+    scale_mode: sdl2::sys::SDL_ScaleMode,
+) -> Result<(), FontTextureError> {
+    // Safety: the texture pointer is valid for the lifetime of the
+    // Texture.
+    let result = unsafe { sdl2::sys::SDL_SetTextureScaleMode(texture.raw(), scale_mode) };
+    if result != 0 {
+        Err(FontTextureError::TextureCreation(sdl2::get_error()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Queries the renderer behind the texture creator for its
+/// capabilities, e.g. the maximum texture size.
+pub(crate) fn renderer_info<T>(
+    texture_creator: &TextureCreator<T>,
+) -> Result<RendererInfo, FontTextureError> {
+    let mut info = std::mem::MaybeUninit::uninit();
+    // Safety: the renderer pointer is kept alive by the texture
+    // creator, and SDL_GetRendererInfo initializes the info struct
+    // when it returns 0.
+    unsafe {
+        if sdl2::sys::SDL_GetRendererInfo(texture_creator.raw(), info.as_mut_ptr()) != 0 {
+            return Err(FontTextureError::TextureCreation(sdl2::get_error()));
+        }
+        Ok(RendererInfo::from_ll(&info.assume_init()))
+    }
+}
+
+/// Called by [FontTexture::max_texture_size], and before creating
+/// glyph cache textures.
+fn max_texture_size<T>(
+    texture_creator: &TextureCreator<T>,
+) -> Result<Option<(u32, u32)>, FontTextureError> {
+    let info = renderer_info(texture_creator)?;
+    // A max size of 0 means the renderer doesn't have a limit.
+    if info.max_texture_width == 0 && info.max_texture_height == 0 {
+        return Ok(None);
+    }
+    let max_or_unlimited = |max: u32| if max == 0 { u32::MAX } else { max };
+    Ok(Some((
+        max_or_unlimited(info.max_texture_width),
+        max_or_unlimited(info.max_texture_height),
+    )))
+}
+
+/// Returns the pixel format for the glyph cache texture which the
+/// renderer can use without converting the pixels on upload: BGRA32
+/// if the renderer prefers it over RGBA32, and RGBA32 otherwise.
+pub(crate) fn native_glyph_cache_format<T>(
+    texture_creator: &TextureCreator<T>,
+) -> Result<PixelFormatEnum, FontTextureError> {
+    let info = renderer_info(texture_creator)?;
+    let preferred = info
+        .texture_formats
+        .iter()
+        .find(|&&format| format == PixelFormatEnum::RGBA32 || format == PixelFormatEnum::BGRA32);
+    Ok(preferred.copied().unwrap_or(PixelFormatEnum::RGBA32))
+}
+
+/// Checks that the glyph cache's area fits in the u32s it's counted
+/// in, and the size of its in-memory copy in a usize.
+fn check_glyph_cache_area(width: u32, height: u32) -> Result<(), FontTextureError> {
+    let bytes = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|area| area.checked_mul(4));
+    if width.checked_mul(height).is_none() || bytes.is_none() {
+        return Err(FontTextureError::AtlasTooLarge(format!(
+            "{}x{} was requested, which has too many pixels",
+            width, height
+        )));
+    }
+    Ok(())
+}
+
+/// Called by [FontTexture::new] and the other constructors of
+/// [FontTexture]. The format should be RGBA32 or BGRA32.
 pub(crate) fn create_font_texture<T>(
     texture_creator: &TextureCreator<T>,
-) -> Result<Texture, String> {
+    width: u32,
+    height: u32,
+    format: PixelFormatEnum,
+    access: TextureAccess,
+) -> Result<OwnedTexture<'_>, FontTextureError> {
     use sdl2::render::TextureValueError::*;
-    let mut texture = match texture_creator.create_texture_streaming(
-        Some(PixelFormatEnum::RGBA32), // = the pixels are always [r, g, b, a] when read as u8's.
-        1024,
-        1024,
-    ) {
+    if width == 0 || height == 0 {
+        return Err(FontTextureError::TextureCreation(format!(
+            "Glyph cache texture size {}x{} is empty",
+            width, height
+        )));
+    }
+    check_glyph_cache_area(width, height)?;
+    if let Some((max_width, max_height)) = max_texture_size(texture_creator)? {
+        if width > max_width || height > max_height {
+            return Err(FontTextureError::AtlasTooLarge(format!(
+                "{}x{} was requested, but the renderer's maximum texture size is {}x{}",
+                width, height, max_width, max_height,
+            )));
+        }
+    }
+    let mut texture = match texture_creator.create_texture(Some(format), access, width, height) {
         Ok(t) => t,
-        Err(WidthOverflows(_))
-        | Err(HeightOverflows(_))
-        | Err(WidthMustBeMultipleOfTwoForFormat(_, _)) => {
-            unreachable!()
+        Err(WidthOverflows(_)) | Err(HeightOverflows(_)) => {
+            return Err(FontTextureError::AtlasTooLarge(format!(
+                "{}x{} was requested, which SDL can't represent",
+                width, height
+            )))
         }
-        Err(SdlError(s)) => return Err(s),
+        Err(WidthMustBeMultipleOfTwoForFormat(_, _)) => unreachable!(),
+        Err(SdlError(s)) => return Err(FontTextureError::TextureCreation(s)),
     };
     texture.set_blend_mode(BlendMode::Blend);
     Ok(texture)
 }
 
-/// Called by [FontTexture::draw_text].
-fn draw_text<RT: RenderTarget>(
-    font_texture: &mut Texture,
-    rect_allocator: &mut RectAllocator,
-    canvas: &mut Canvas<RT>,
-    fonts: &[Font],
-    glyphs: &[GlyphPosition<Color>],
-) -> Result<(), String> {
-    struct RenderableGlyph {
-        texture_rect: Rect,
-        canvas_rect: Rect,
+/// Called by [FontTexture::from_texture]. Checks that the texture
+/// can be used as a glyph cache, and returns its size.
+pub(crate) fn adopt_font_texture(
+    texture: &mut Texture,
+) -> Result<(u32, u32, PixelFormatEnum), FontTextureError> {
+    let query = texture.query();
+    if query.format != PixelFormatEnum::RGBA32 && query.format != PixelFormatEnum::BGRA32 {
+        return Err(FontTextureError::TextureCreation(format!(
+            "Glyph cache texture has pixel format {:?}, but it should be RGBA32 or BGRA32",
+            query.format,
+        )));
+    }
+    if query.access != TextureAccess::Streaming {
+        return Err(FontTextureError::TextureCreation(format!(
+            "Glyph cache texture has access {:?}, but it should be Streaming",
+            query.access,
+        )));
     }
-    struct MissingGlyph {
-        color: Color,
-        canvas_rect: Rect,
+    check_glyph_cache_area(query.width, query.height)?;
+    texture.set_blend_mode(BlendMode::Blend);
+    Ok((query.width, query.height, query.format))
+}
+
+/// How the colors of the glyphs are applied, see
+/// [FontTexture::set_color_mode].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorMode {
+    /// Glyphs are rasterized into the glyph cache in their own color,
+    /// so each color gets its own cache entries. This is the default.
+    #[default]
+    Rasterized,
+    /// Glyphs are rasterized into the glyph cache in white, and
+    /// colored when drawn, using the texture's color mod. All colors
+    /// share the same cache entries, which saves space in the glyph
+    /// cache if text is drawn in many different colors.
+    Modulated,
+    /// Glyphs are rasterized with separate coverage for the red,
+    /// green and blue subpixels of LCD displays, and colored when
+    /// drawn, like with [ColorMode::Modulated]. Makes small text
+    /// crisper on displays with horizontal RGB subpixels, at the
+    /// cost of color fringes on the edges of glyphs.
+    ///
+    /// The glyphs are drawn in two passes with custom blend modes,
+    /// which override the blend mode set with
+    /// [FontTexture::set_blend_mode] and need a renderer that
+    /// supports them (the software renderer does not). The text
+    /// should be drawn on an opaque background, since subpixel
+    /// coverage can't be represented with a single alpha value.
+    Subpixel,
+}
+
+/// How glyphs that don't fit in the glyph cache are drawn, see
+/// [FontTexture::set_missing_glyph_style].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MissingGlyphStyle {
+    /// Missing glyphs aren't drawn at all.
+    None,
+    /// The outline of the glyph's rectangle is drawn in the glyph's
+    /// color. This is the default.
+    #[default]
+    Outline,
+    /// The glyph's rectangle is filled with the glyph's color.
+    /// Rotated glyphs are outlined instead.
+    Filled,
+}
+
+/// How the glyph cache texture is filtered when glyphs are drawn
+/// scaled or at fractional positions, see [FontTexture::set_scale_mode].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScaleMode {
+    /// Each pixel is the nearest pixel of the glyph, which keeps
+    /// upscaled glyphs blocky, and snaps glyphs at fractional
+    /// positions to whole pixels.
+    Nearest,
+    /// Pixels are interpolated between the pixels of the glyph, which
+    /// makes scaled glyphs smooth, and lets glyphs move by fractions
+    /// of a pixel.
+    Linear,
+    /// Anisotropic filtering, where supported by the renderer, and
+    /// linear filtering otherwise.
+    Best,
+}
+
+impl ScaleMode {
+    fn to_sdl(self) -> sdl2::sys::SDL_ScaleMode {
+        use sdl2::sys::SDL_ScaleMode::*;
+        match self {
+            ScaleMode::Nearest => SDL_ScaleModeNearest,
+            ScaleMode::Linear => SDL_ScaleModeLinear,
+            ScaleMode::Best => SDL_ScaleModeBest,
+        }
     }
+}
 
-    let mut result_glyphs = Vec::with_capacity(glyphs.len());
-    let mut missing_glyphs = Vec::new();
+/// How glyph positions are rounded to whole pixels, see
+/// [FontTexture::set_rounding].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Positions are rounded down, towards negative infinity.
+    Floor,
+    /// Positions are rounded to the nearest whole pixel, with halves
+    /// rounded away from zero. This is the default.
+    #[default]
+    Round,
+    /// Positions are rounded towards zero, which moves glyphs at
+    /// negative positions the opposite way from glyphs at positive
+    /// ones.
+    Truncate,
+}
 
-    for glyph in glyphs.iter().filter(|glyph| glyph.width * glyph.height > 0) {
-        let canvas_rect = Rect::new(
-            glyph.x as i32,
-            glyph.y as i32,
-            glyph.width as u32,
-            glyph.height as u32,
-        );
-        let color = glyph.user_data;
+impl Rounding {
+    /// Returns the position rounded to a whole pixel.
+    ///
+    /// [`Rounding::Round`] treats positive and negative positions
+    /// symmetrically, so mirrored positions stay mirrored:
+    ///
+    /// ```
+    /// # use fontdue_sdl2::Rounding;
+    /// for &x in &[0.4, 0.5, 1.6, 12.5] {
+    ///     assert_eq!(Rounding::Round.apply(-x), -Rounding::Round.apply(x));
+    /// }
+    /// assert_eq!(Rounding::Floor.apply(-0.5), -1.0);
+    /// assert_eq!(Rounding::Floor.apply(0.5), 0.0);
+    /// ```
+    pub fn apply(self, position: f32) -> f32 {
+        match self {
+            Rounding::Floor => position.floor(),
+            Rounding::Round => position.round(),
+            Rounding::Truncate => position.trunc(),
+        }
+    }
+}
+
+/// The configuration of a [FontTexture], which affects how glyphs are
+/// rasterized and drawn.
+#[derive(Clone, Copy)]
+pub(crate) struct Settings {
+    pub color_mode: ColorMode,
+    pub raster: RasterSettings,
+    /// Maps the coverage values from fontdue to the alpha values
+    /// stored in the glyph cache. Derived from the raster settings
+    /// with [Settings::update_coverage_table].
+    pub coverage_table: [u8; 256],
+    /// A custom remapping of the coverage values from fontdue, applied
+    /// before the gamma, see [FontTexture::set_coverage_curve]. None
+    /// if the coverage isn't remapped.
+    pub coverage_curve: Option<[u8; 256]>,
+    /// The ratio between the canvas' output size and its logical
+    /// size, see [FontTexture::set_dpi_scale].
+    pub dpi_scale: f32,
+    /// The size glyphs are rasterized at regardless of their laid out
+    /// size, see [FontTexture::set_fixed_raster_size].
+    pub fixed_raster_px: Option<f32>,
+    pub missing_glyph_style: MissingGlyphStyle,
+    /// The color drawn instead of fully transparent black, see
+    /// [FontTexture::set_default_color].
+    pub default_color: Option<Color>,
+    /// Whether the metrics of cached glyphs are kept, see
+    /// [FontTexture::set_retain_metrics].
+    pub retain_metrics: bool,
+    /// The most glyphs rasterized per draw, see
+    /// [FontTexture::set_raster_budget].
+    pub raster_budget: Option<usize>,
+    /// Whether glyphs are drawn with a single `SDL_RenderGeometry`
+    /// call per draw, see [FontTexture::set_batched].
+    pub batched: bool,
+    /// How glyph positions are rounded to whole pixels, if at all,
+    /// see [FontTexture::set_rounding].
+    pub rounding: Option<Rounding>,
+    /// Whether draws are kept pending until
+    /// [FontTexture::flush_uploads], see
+    /// [FontTexture::set_deferred_uploads].
+    pub deferred_uploads: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        let mut settings = Settings {
+            color_mode: ColorMode::default(),
+            raster: RasterSettings::default(),
+            coverage_table: [0; 256],
+            coverage_curve: None,
+            dpi_scale: 1.0,
+            fixed_raster_px: None,
+            missing_glyph_style: MissingGlyphStyle::default(),
+            default_color: None,
+            retain_metrics: false,
+            raster_budget: None,
+            batched: false,
+            rounding: Some(Rounding::Round),
+            deferred_uploads: false,
+        };
+        settings.update_coverage_table();
+        settings
+    }
+}
+
+impl Settings {
+    /// Returns the color the glyph is drawn with, given the color
+    /// from its user data.
+    pub fn glyph_color(&self, color: Color) -> Color {
+        match self.default_color {
+            Some(default_color) if color == Color::RGBA(0, 0, 0, 0) => default_color,
+            _ => color,
+        }
+    }
+
+    /// Returns the raster settings of glyphs rasterized for the color
+    /// mode. The background hint is only kept for
+    /// [ColorMode::Rasterized], since the glyphs need to be rasterized
+    /// in their own color to be blended against the background.
+    pub fn raster_settings(&self, color_mode: ColorMode) -> RasterSettings {
+        RasterSettings {
+            background: (self.raster.background).filter(|_| color_mode == ColorMode::Rasterized),
+            ..self.raster
+        }
+    }
+
+    /// Recalculates the coverage table, should be called after
+    /// changing the raster settings.
+    pub fn update_coverage_table(&mut self) {
+        let gamma = f32::from_bits(self.raster.gamma_bits);
+        for (coverage, alpha) in self.coverage_table.iter_mut().enumerate() {
+            let coverage = match &self.coverage_curve {
+                Some(curve) => curve[coverage],
+                None => coverage as u8,
+            };
+            if self.raster.monochrome {
+                *alpha = if coverage >= 128 { 0xFF } else { 0 };
+                continue;
+            }
+            let linear = coverage as f32 / 255.0;
+            *alpha = (linear.powf(1.0 / gamma) * 255.0).round() as u8;
+        }
+    }
+
+    /// Sets the coverage curve to the values of `curve`, or removes
+    /// it if `curve` doesn't change any value, and updates the
+    /// coverage table.
+    pub fn set_coverage_curve<F: Fn(u8) -> u8>(&mut self, curve: F) {
+        let mut table = [0; 256];
+        for (coverage, remapped) in table.iter_mut().enumerate() {
+            *remapped = curve(coverage as u8);
+        }
+        let is_identity = table.iter().enumerate().all(|(i, &c)| i == c as usize);
+        if is_identity {
+            self.coverage_curve = None;
+            self.raster.coverage_curve_hash = 0;
+        } else {
+            self.coverage_curve = Some(table);
+            self.raster.coverage_curve_hash = fnv1a_hash(&table);
+        }
+        self.update_coverage_table();
+    }
+}
+
+/// Returns the 64-bit FNV-1a hash of the bytes. Used for the hashes
+/// saved in glyph cache files, which need to stay the same between
+/// builds, unlike the hashes of [DefaultHasher].
+fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The settings which affect the pixels of rasterized glyphs. These
+/// are a part of the glyph cache key, so that changing the settings
+/// doesn't mix glyphs rasterized with different settings.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct RasterSettings {
+    /// Store the color channels multiplied by the coverage.
+    pub premultiplied_alpha: bool,
+    /// The gamma applied to the coverage, as bits so that this can be
+    /// hashed.
+    pub gamma_bits: u32,
+    /// The hash of [Settings::coverage_curve], or 0 if there's no
+    /// curve, so that glyphs rasterized with different curves get
+    /// separate cache entries.
+    pub coverage_curve_hash: u64,
+    /// The factor glyphs are rasterized larger by, before being
+    /// downsampled to their actual size, see
+    /// [FontTexture::set_supersampling]. 1 for no supersampling.
+    pub supersample: u8,
+    /// Threshold the coverage into fully opaque or transparent
+    /// pixels, see [FontTexture::set_monochrome].
+    pub monochrome: bool,
+    /// The color glyphs are blended against when they're rasterized,
+    /// see [FontTexture::set_background_hint].
+    pub background: Option<(u8, u8, u8)>,
+}
+
+impl Default for RasterSettings {
+    fn default() -> RasterSettings {
+        RasterSettings {
+            premultiplied_alpha: false,
+            gamma_bits: 1.0f32.to_bits(),
+            coverage_curve_hash: 0,
+            supersample: 1,
+            monochrome: false,
+            background: None,
+        }
+    }
+}
+
+/// Options for [draw_text] that only affect where glyphs end up on
+/// the canvas, not how they're rasterized or cached.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct DrawOptions<'a> {
+    /// A pivot point on the canvas and an angle in degrees
+    /// (clockwise) to rotate the whole glyph run around it.
+    pub rotation: Option<(Point, f64)>,
+    /// An offset added to the position of every glyph.
+    pub offset: (i32, i32),
+    /// A color to draw every glyph in, instead of their own. The
+    /// alpha is multiplied with the glyphs' own alpha. The glyphs are
+    /// colored like with [ColorMode::Modulated], regardless of the
+    /// color mode.
+    pub color_override: Option<Color>,
+    /// Start and end colors to draw the glyphs in, instead of their
+    /// own, interpolated by each glyph's horizontal position across
+    /// the bounds of the whole run. Colors the glyphs like
+    /// `color_override`. Ignored if `color_override` is set.
+    pub gradient: Option<(Color, Color)>,
+    /// A rectangle on the canvas to clip the glyphs to.
+    pub clip: Option<Rect>,
+    /// The area of the canvas the glyphs can be seen in, i.e. the
+    /// viewport, clipped to `clip`. Glyphs entirely outside it (e.g.
+    /// text scrolled off the edge of the canvas) are skipped, without
+    /// caching them. Set by [draw_text].
+    pub visible_area: Option<Rect>,
+    /// Angles in degrees (clockwise) to rotate each glyph around its
+    /// own center, by index. Glyphs without an angle aren't rotated.
+    /// Ignored if `rotation` is set.
+    pub glyph_angles: &'a [f64],
+    /// A scale applied to the positions and sizes of the glyphs
+    /// before drawing, with the glyphs rasterized at the scaled size.
+    pub scale: Option<f32>,
+    /// The most glyphs to rasterize in this draw. Glyphs that aren't
+    /// cached yet after that are deferred, i.e. not drawn. Set by
+    /// [draw_text] from [Settings::raster_budget].
+    pub raster_budget: Option<usize>,
+    /// A scale and then a translation applied to the rectangles the
+    /// glyphs are copied into, after `scale` and `offset`, for
+    /// [FontTexture::draw_text_fitted]. Unlike `scale`, this scales
+    /// the glyphs after they're rasterized, so it can be different
+    /// horizontally and vertically, and the translation can be
+    /// fractional.
+    pub transform: Option<CopyTransform>,
+    /// An affine transform applied to the corners of the rectangles
+    /// the glyphs are copied into, see [transform_corners], drawn as
+    /// triangles with [quads::render_quads]. If set, `rotation` and
+    /// `glyph_angles` are ignored.
+    pub matrix: Option<[f32; 6]>,
+    /// Whether the glyphs are drawn with a single
+    /// [quads::render_quads] call instead of a copy per glyph. Set by
+    /// [draw_text] from [Settings::batched].
+    pub batched: bool,
+}
+
+impl DrawOptions<'_> {
+    /// Returns the options without `glyph_angles`, which are only
+    /// needed by [cache_glyphs], so that they can be kept after the
+    /// draw returns.
+    fn without_glyph_angles(&self) -> DrawOptions<'static> {
+        DrawOptions {
+            rotation: self.rotation,
+            offset: self.offset,
+            color_override: self.color_override,
+            gradient: self.gradient,
+            clip: self.clip,
+            visible_area: self.visible_area,
+            glyph_angles: &[],
+            scale: self.scale,
+            raster_budget: self.raster_budget,
+            transform: self.transform,
+            matrix: self.matrix,
+            batched: self.batched,
+        }
+    }
+}
+
+/// See [DrawOptions::transform].
+#[derive(Clone, Copy)]
+pub(crate) struct CopyTransform {
+    pub scale: (f32, f32),
+    pub translation: (f32, f32),
+}
+
+impl CopyTransform {
+    fn apply(&self, rect: FRect) -> FRect {
+        FRect::new(
+            rect.x() * self.scale.0 + self.translation.0,
+            rect.y() * self.scale.1 + self.translation.1,
+            rect.width() * self.scale.0,
+            rect.height() * self.scale.1,
+        )
+    }
+
+    /// Like [CopyTransform::apply], rounded to whole pixels.
+    fn apply_to_rect(&self, rect: Rect) -> Rect {
+        let rect = self.apply(FRect::new(
+            rect.x() as f32,
+            rect.y() as f32,
+            rect.width() as f32,
+            rect.height() as f32,
+        ));
+        Rect::new(
+            rect.x().round() as i32,
+            rect.y().round() as i32,
+            rect.width().round().max(1.0) as u32,
+            rect.height().round().max(1.0) as u32,
+        )
+    }
+}
+
+/// Multiplies two values as if they were fractions of 255.
+fn multiply_u8(a: u8, b: u8) -> u8 {
+    (a as u16 * b as u16 / 0xFF) as u8
+}
+
+/// Interpolates linearly between the colors, with `t` clamped
+/// between 0 (`start`) and 1 (`end`).
+fn lerp_color(start: Color, end: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color::RGBA(
+        lerp(start.r, end.r),
+        lerp(start.g, end.g),
+        lerp(start.b, end.b),
+        lerp(start.a, end.a),
+    )
+}
+
+/// Scales down the color channels by the alpha, for blending with
+/// premultiplied alpha, where fading out the glyph requires scaling
+/// down the color channels as well.
+fn premultiply(Color { r, g, b, a }: Color) -> Color {
+    Color::RGBA(multiply_u8(r, a), multiply_u8(g, a), multiply_u8(b, a), a)
+}
+
+/// Rotates `point` around `pivot` by `angle` degrees clockwise, in
+/// the same way [Canvas::copy_ex] rotates textures.
+fn rotate_point(point: Point, pivot: Point, angle: f64) -> Point {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let dx = (point.x() - pivot.x()) as f64;
+    let dy = (point.y() - pivot.y()) as f64;
+    Point::new(
+        pivot.x() + (dx * cos - dy * sin).round() as i32,
+        pivot.y() + (dx * sin + dy * cos).round() as i32,
+    )
+}
+
+/// A glyph that is in the glyph cache, ready to be copied onto the
+/// canvas.
+#[derive(Clone, Copy)]
+struct RenderableGlyph {
+    texture_rect: Rect,
+    canvas_rect: Rect,
+    /// The fractional part of the glyph's position, which is lost in
+    /// `canvas_rect`.
+    fraction: (f32, f32),
+    /// The angle to rotate the glyph around its center, from
+    /// [DrawOptions::glyph_angles].
+    angle: f64,
+    modulation: Color,
+}
+
+/// A glyph that didn't fit in the glyph cache.
+struct MissingGlyph {
+    parent: char,
+    color: Color,
+    canvas_rect: Rect,
+    /// See [RenderableGlyph::angle].
+    angle: f64,
+}
+
+/// The glyphs of a draw, returned by [cache_glyphs]. Kept in the
+/// [GlyphCache] between draws, so that their memory can be reused
+/// instead of allocating new Vecs for every draw.
+#[derive(Default)]
+struct DrawBuffers {
+    glyphs: Vec<RenderableGlyph>,
+    missing_glyphs: Vec<MissingGlyph>,
+    oversized_glyphs: Vec<OversizedGlyph>,
+    /// The amount of glyphs left out because of the raster budget.
+    deferred_glyphs: usize,
+    /// The indices of the visible glyphs, and the rectangles they're
+    /// drawn into, see [GlyphCache::hit_rects].
+    hit_rects: Vec<(usize, Rect)>,
+}
+
+/// A draw made with [Settings::deferred_uploads], which is drawn by
+/// [flush_uploads] once the glyphs it uses are uploaded.
+pub(crate) struct PendingDraw {
+    buffers: DrawBuffers,
+    options: DrawOptions<'static>,
+    color_mode: ColorMode,
+    missing_glyph_style: MissingGlyphStyle,
+}
+
+/// Called by [FontTexture::cache_glyphs], and [draw_text] before
+/// drawing. Makes sure the glyphs are in the glyph cache, rasterizing
+/// the ones that aren't into its in-memory copy, to be uploaded by
+/// the caller. The color of each glyph is read from its user data
+/// with `color_of`.
+fn cache_glyphs<U: Copy, F: Fn(&U) -> Color>(
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    color_of: F,
+    options: &DrawOptions,
+) -> DrawBuffers {
+    let mut buffers = std::mem::take(&mut glyph_cache.draw_buffers);
+    buffers.glyphs.clear();
+    buffers.missing_glyphs.clear();
+    buffers.oversized_glyphs.clear();
+    buffers.deferred_glyphs = 0;
+    buffers.hit_rects.clear();
+    let DrawBuffers {
+        glyphs: result_glyphs,
+        missing_glyphs,
+        oversized_glyphs,
+        deferred_glyphs,
+        hit_rects,
+    } = &mut buffers;
+    let mut raster_budget = options.raster_budget.unwrap_or(usize::MAX);
+    #[cfg(feature = "rayon")]
+    let mut parallel_rasters = Vec::new();
+    glyph_cache.rect_allocator.begin_draw();
+    let gradient = match (options.color_override, options.gradient) {
+        (None, Some(colors)) => geometry::text_bounds(glyphs).map(|bounds| (colors, bounds)),
+        _ => None,
+    };
+
+    for (i, glyph) in glyphs.iter().enumerate() {
+        let override_color = options.color_override.or_else(|| {
+            let ((start, end), bounds) = gradient?;
+            let center_x = glyph.x + glyph.width as f32 / 2.0;
+            let t = (center_x - bounds.x() as f32) / bounds.width() as f32;
+            Some(lerp_color(start, end, t))
+        });
+        let scaled_glyph;
+        let glyph = match options.scale {
+            Some(scale) => {
+                scaled_glyph =
+                    geometry::scale_glyph(&mut glyph_cache.metrics_cache, fonts, glyph, scale);
+                &scaled_glyph
+            }
+            None => glyph,
+        };
+        let override_metrics = glyph_cache
+            .glyph_override(glyph.parent, glyph.key.px)
+            .map(|(metrics, _)| *metrics);
+        let overridden_glyph;
+        let glyph = match &override_metrics {
+            Some(metrics) => {
+                overridden_glyph = override_glyph(fonts, glyph, metrics);
+                &overridden_glyph
+            }
+            None => glyph,
+        };
+        let rounded_glyph;
+        let glyph = match settings.rounding {
+            Some(rounding) => {
+                rounded_glyph = GlyphPosition {
+                    x: rounding.apply(glyph.x),
+                    y: rounding.apply(glyph.y),
+                    ..*glyph
+                };
+                &rounded_glyph
+            }
+            None => glyph,
+        };
+        let mut canvas_rect = match geometry::glyph_canvas_rect(glyph) {
+            Some(rect) => rect,
+            None => continue,
+        };
+        canvas_rect.offset(options.offset.0, options.offset.1);
+        let fraction = (glyph.x - glyph.x.floor(), glyph.y - glyph.y.floor());
+        let angle = options.glyph_angles.get(i).copied().unwrap_or(0.0);
+        let transformed = options.rotation.is_some()
+            || angle != 0.0
+            || options.transform.is_some()
+            || options.matrix.is_some();
+        if let (Some(visible_area), false) = (options.visible_area, transformed) {
+            // Skip glyphs that wouldn't be visible at all. Rotated
+            // and transformed glyphs end up somewhere else, so they
+            // can't be skipped.
+            if !visible_area.has_intersection(canvas_rect) {
+                continue;
+            }
+        }
+        let hit_rect = match options.transform {
+            Some(transform) => transform.apply_to_rect(canvas_rect),
+            None => canvas_rect,
+        };
+        hit_rects.push((i, hit_rect));
+        let glyph_color = settings.glyph_color(color_of(&glyph.user_data));
+        let color = match override_color {
+            Some(Color { r, g, b, a }) => Color::RGBA(r, g, b, multiply_u8(a, glyph_color.a)),
+            None => glyph_color,
+        };
+        // The color baked into the cached glyph, and the color mod
+        // applied on top of that when copying it to the canvas.
+        let color_mode = match (override_color, settings.color_mode) {
+            (Some(_), ColorMode::Rasterized) => ColorMode::Modulated,
+            (_, color_mode) => color_mode,
+        };
+        let (raster_color, mut modulation) = match color_mode {
+            ColorMode::Rasterized => (
+                Color::RGB(color.r, color.g, color.b),
+                Color::RGBA(0xFF, 0xFF, 0xFF, color.a),
+            ),
+            ColorMode::Modulated | ColorMode::Subpixel => (Color::WHITE, color),
+        };
+        if settings.raster.premultiplied_alpha && color_mode != ColorMode::Subpixel {
+            modulation = premultiply(modulation);
+        }
 
-        match rect_allocator.get_rect_in_texture(*glyph) {
-            CacheReservation::AlreadyRasterized(texture_rect) => {
+        let raster = match glyph_raster(
+            settings,
+            &mut glyph_cache.metrics_cache,
+            fonts,
+            glyph,
+            color_mode,
+            raster_color,
+        ) {
+            Some(raster) => raster.overridden(glyph.parent, override_metrics),
+            None => continue,
+        };
+        if raster.is_too_large() {
+            glyph_cache.warn(Warning::GlyphTooLarge {
+                character: glyph.parent,
+                px: raster.key.glyph.px,
+                font_index: glyph.font_index,
+                width: raster.width,
+                height: raster.height,
+            });
+            continue;
+        }
+        if raster_budget == 0 && !glyph_cache.rect_allocator.contains(&raster.key) {
+            *deferred_glyphs += 1;
+            continue;
+        }
+        let padded_width = raster.width + glyph_cache.padding * 2;
+        let padded_height = raster.height + glyph_cache.padding * 2;
+        // New glyphs are rasterized before reserving space for them,
+        // so that blank ones, e.g. whitespace, don't evict other
+        // glyphs to make space they won't use. The ones rasterized in
+        // parallel can't be, see rasterize_in_parallel.
+        let rasterized_in_parallel = cfg!(feature = "rayon") && raster.key.override_char.is_none();
+        let packer = &glyph_cache.rect_allocator.packer;
+        let new_pixels = if !rasterized_in_parallel
+            && padded_width <= packer.width()
+            && padded_height <= packer.height()
+            && !glyph_cache.rect_allocator.contains(&raster.key)
+        {
+            match rasterize_glyph_or_override(glyph_cache, settings, fonts, &raster, glyph.key.px) {
+                Some(pixels) => Some(pixels),
+                None => {
+                    glyph_cache.rect_allocator.mark_blank(raster.key);
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+        let reservation =
+            glyph_cache
+                .rect_allocator
+                .get_rect_in_texture(raster.key, padded_width, padded_height);
+        match reservation {
+            CacheReservation::AlreadyRasterized(padded_rect) => {
                 result_glyphs.push(RenderableGlyph {
-                    texture_rect,
+                    texture_rect: unpadded_rect(padded_rect, glyph_cache.padding),
                     canvas_rect,
+                    fraction,
+                    angle,
+                    modulation,
                 });
             }
-            CacheReservation::EmptySpace(texture_rect) => {
-                let (metrics, pixels) = fonts[glyph.font_index].rasterize_config(glyph.key);
-
-                let mut full_color_pixels = Vec::with_capacity(pixels.len());
-                for coverage in pixels {
-                    full_color_pixels.push(color.r);
-                    full_color_pixels.push(color.g);
-                    full_color_pixels.push(color.b);
-                    full_color_pixels.push(coverage);
+            CacheReservation::Blank => {}
+            CacheReservation::EmptySpace(padded_rect) => {
+                raster_budget -= 1;
+                #[cfg(feature = "rayon")]
+                {
+                    if raster.key.override_char.is_none() {
+                        // Rasterized along with the other new glyphs
+                        // after this loop.
+                        parallel_rasters.push((raster, padded_rect));
+                        result_glyphs.push(RenderableGlyph {
+                            texture_rect: unpadded_rect(padded_rect, glyph_cache.padding),
+                            canvas_rect,
+                            fraction,
+                            angle,
+                            modulation,
+                        });
+                        continue;
+                    }
                 }
-                font_texture
-                    .update(texture_rect, &full_color_pixels, metrics.width * 4)
-                    .map_err(|err| format!("{}", err))?;
-
+                // Always rasterized above, since the glyph wasn't
+                // cached.
+                let full_color_pixels = match new_pixels {
+                    Some(pixels) => pixels,
+                    None => continue,
+                };
+                store_glyph(
+                    glyph_cache,
+                    settings,
+                    fonts,
+                    &raster,
+                    padded_rect,
+                    &full_color_pixels,
+                );
                 result_glyphs.push(RenderableGlyph {
-                    texture_rect,
+                    texture_rect: unpadded_rect(padded_rect, glyph_cache.padding),
                     canvas_rect,
+                    fraction,
+                    angle,
+                    modulation,
                 });
             }
             CacheReservation::OutOfSpace => {
-                log::error!(
-                    "Glyph cache cannot fit '{}' (size {}, font index {})",
+                glyph_cache.warn(Warning::OutOfSpace {
+                    character: glyph.parent,
+                    px: glyph.key.px,
+                    font_index: glyph.font_index,
+                });
+                missing_glyphs.push(MissingGlyph {
+                    parent: glyph.parent,
+                    color,
+                    canvas_rect,
+                    angle,
+                });
+            }
+            CacheReservation::TooLargeForAtlas => {
+                log_debug!(
+                    "Glyph {:?} (U+{:04X}, size {}, font {}) exceeds the glyph cache's size, drawing it uncached",
                     glyph.parent,
+                    glyph.parent as u32,
                     glyph.key.px,
                     glyph.font_index,
                 );
-                missing_glyphs.push(MissingGlyph { color, canvas_rect });
+                // Subpixel coverage makes no visible difference at
+                // sizes this big, so these glyphs are drawn like with
+                // ColorMode::Modulated, in a single pass.
+                let raster = match color_mode {
+                    ColorMode::Subpixel => {
+                        if settings.raster.premultiplied_alpha {
+                            modulation = premultiply(modulation);
+                        }
+                        glyph_raster(
+                            settings,
+                            &mut glyph_cache.metrics_cache,
+                            fonts,
+                            glyph,
+                            ColorMode::Modulated,
+                            raster_color,
+                        )
+                        .map(|raster| raster.overridden(glyph.parent, override_metrics))
+                    }
+                    _ => Some(raster),
+                };
+                let rasterized = raster.and_then(|raster| {
+                    let pixels = rasterize_glyph_or_override(
+                        glyph_cache,
+                        settings,
+                        fonts,
+                        &raster,
+                        glyph.key.px,
+                    )?;
+                    Some((raster, pixels))
+                });
+                if let Some((raster, pixels)) = rasterized {
+                    oversized_glyphs.push(OversizedGlyph {
+                        glyph: RenderableGlyph {
+                            texture_rect: Rect::new(0, 0, raster.width, raster.height),
+                            canvas_rect,
+                            fraction,
+                            angle,
+                            modulation,
+                        },
+                        pixels,
+                    });
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    rasterize_in_parallel(
+        glyph_cache,
+        settings,
+        fonts,
+        &parallel_rasters,
+        result_glyphs,
+    );
+    buffers
+}
+
+/// Writes the glyph's pixels into the area reserved for it in the
+/// glyph cache.
+fn store_glyph(
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    fonts: &[Font],
+    raster: &GlyphRaster,
+    padded_rect: Rect,
+    pixels: &[u8],
+) {
+    glyph_cache.write_padded_pixels(padded_rect, pixels);
+    glyph_cache.frame_stats.rasterized_glyphs += 1;
+    if settings.retain_metrics {
+        let font = &fonts[raster.font_index];
+        let metrics = font.metrics_indexed(raster.key.glyph.glyph_index, raster.key.glyph.px);
+        glyph_cache.rect_allocator.set_metrics(&raster.key, metrics);
+    }
+}
+
+/// Rasterizes the glyphs [cache_glyphs] reserved space for on
+/// multiple threads, and writes them into the glyph cache. The
+/// glyphs that turn out to be blank are removed from `glyphs`, like
+/// [cache_glyphs] would've left them out, and their space is freed
+/// again. Unlike with serial rasterization, the space is reserved
+/// before their coverage is known, since the glyphs' rects in the
+/// texture are needed in the order they're drawn in.
+#[cfg(feature = "rayon")]
+fn rasterize_in_parallel(
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    fonts: &[Font],
+    rasters: &[(GlyphRaster, Rect)],
+    glyphs: &mut Vec<RenderableGlyph>,
+) {
+    use rayon::prelude::*;
+    let rasterized = rasters
+        .par_iter()
+        .map(|(raster, _)| rasterize_glyph(settings, fonts, raster))
+        .collect::<Vec<_>>();
+    let mut blank_rects = Vec::new();
+    for ((raster, padded_rect), pixels) in rasters.iter().zip(rasterized) {
+        match pixels {
+            Some(pixels) => {
+                store_glyph(glyph_cache, settings, fonts, raster, *padded_rect, &pixels)
+            }
+            None => {
+                glyph_cache.rect_allocator.mark_blank(raster.key);
+                blank_rects.push(unpadded_rect(*padded_rect, glyph_cache.padding));
+            }
+        }
+    }
+    if !blank_rects.is_empty() {
+        glyphs.retain(|glyph| !blank_rects.contains(&glyph.texture_rect));
+    }
+}
+
+/// How a glyph is stored in the glyph cache: its key, the font it's
+/// rasterized with, and the size of its bitmap.
+#[derive(Clone, Copy)]
+struct GlyphRaster {
+    key: GlyphKey,
+    font_index: usize,
+    width: u32,
+    height: u32,
+}
+
+/// Returns the size a glyph of size `px` is rasterized at. With a
+/// DPI scale or a fixed raster size, the glyph is rasterized at that
+/// size, and then scaled to its logical size on the canvas.
+fn raster_px(settings: &Settings, px: f32) -> f32 {
+    settings.fixed_raster_px.unwrap_or(px) * settings.dpi_scale
+}
+
+/// Returns the key the glyph would be cached with, if drawn in the
+/// color with the current settings.
+fn cache_key(settings: &Settings, glyph: GlyphRasterConfig, color: Color) -> GlyphKey {
+    let raster_color = match settings.color_mode {
+        ColorMode::Rasterized => color,
+        ColorMode::Modulated | ColorMode::Subpixel => Color::WHITE,
+    };
+    GlyphKey {
+        glyph: GlyphRasterConfig {
+            px: raster_px(settings, glyph.px),
+            ..glyph
+        },
+        rgb: raster_color.rgb(),
+        raster_settings: settings.raster_settings(settings.color_mode),
+        subpixel: settings.color_mode == ColorMode::Subpixel,
+        override_char: None,
+    }
+}
+
+/// Called by [FontTexture::is_cached].
+fn is_cached(
+    glyph_cache: &GlyphCache,
+    settings: &Settings,
+    glyph: GlyphRasterConfig,
+    color: Color,
+) -> bool {
+    let key = cache_key(settings, glyph, color);
+    glyph_cache.rect_allocator.contains(&key)
+}
+
+/// Called by [FontTexture::can_fit], and [draw_text] before caching
+/// glyphs while draws are pending. Allocates space for the glyphs
+/// that aren't cached yet from a copy of the glyph cache's rect
+/// packer, so the glyph cache itself isn't changed. The glyphs are
+/// scaled by `scale` first, like [DrawOptions::scale].
+fn can_fit<U: Copy, F: Fn(&U) -> Color>(
+    glyph_cache: &GlyphCache,
+    settings: &Settings,
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    color_of: F,
+    scale: Option<f32>,
+) -> bool {
+    let mut packer = glyph_cache.rect_allocator.packer.clone();
+    let mut allocated_keys = HashSet::new();
+    let mut metrics_cache = geometry::MetricsCache::default();
+    for glyph in glyphs {
+        let scaled_glyph;
+        let glyph = match scale {
+            Some(scale) => {
+                scaled_glyph = geometry::scale_glyph(&mut metrics_cache, fonts, glyph, scale);
+                &scaled_glyph
+            }
+            None => glyph,
+        };
+        let color = settings.glyph_color(color_of(&glyph.user_data));
+        let raster_color = match settings.color_mode {
+            ColorMode::Rasterized => Color::RGB(color.r, color.g, color.b),
+            ColorMode::Modulated | ColorMode::Subpixel => Color::WHITE,
+        };
+        let raster = match glyph_raster(
+            settings,
+            &mut metrics_cache,
+            fonts,
+            glyph,
+            settings.color_mode,
+            raster_color,
+        ) {
+            Some(raster) if !raster.is_too_large() => raster,
+            _ => continue,
+        };
+        if glyph_cache.rect_allocator.contains(&raster.key) || !allocated_keys.insert(raster.key) {
+            continue;
+        }
+        let width = raster.width + glyph_cache.padding * 2;
+        let height = raster.height + glyph_cache.padding * 2;
+        if width > packer.width() || height > packer.height() {
+            // Drawn without caching, see CacheReservation::TooLargeForAtlas.
+            continue;
+        }
+        if packer.allocate(width, height).is_none() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Called by [FontTexture::set_glyph_override].
+fn set_glyph_override(
+    glyph_cache: &mut GlyphCache,
+    character: char,
+    px: f32,
+    rasterize: Box<dyn Fn() -> (Metrics, Vec<u8>)>,
+) {
+    let glyph_override = GlyphOverride {
+        rasterize,
+        rasterized: None,
+    };
+    let previous = glyph_cache
+        .glyph_overrides
+        .insert((character, px.to_bits()), glyph_override);
+    if previous.is_some() {
+        glyph_cache
+            .rect_allocator
+            .evict(|cached| cached.override_char == Some(character));
+    }
+}
+
+/// Called by [FontTexture::hit_test].
+fn hit_test(glyph_cache: &GlyphCache, point: Point) -> Option<usize> {
+    // Later glyphs are drawn on top of earlier ones.
+    glyph_cache
+        .hit_rects
+        .iter()
+        .rev()
+        .find(|(_, rect)| rect.contains_point(point))
+        .map(|&(index, _)| index)
+}
+
+/// Called by [FontTexture::glyph_rect]. Lays out the character on
+/// its own, caches it, and uploads it to the glyph cache texture.
+#[allow(clippy::too_many_arguments)]
+fn glyph_rect(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    fonts: &[Font],
+    font_index: usize,
+    character: char,
+    px: f32,
+    color: Color,
+) -> Result<Option<Rect>, FontTextureError> {
+    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    let mut text = [0; 4];
+    let text = character.encode_utf8(&mut text);
+    layout.append(
+        fonts,
+        &TextStyle::with_user_data(text, px, font_index, color),
+    );
+    let buffers = cache_glyphs(
+        glyph_cache,
+        settings,
+        fonts,
+        layout.glyphs(),
+        |color: &Color| *color,
+        &DrawOptions::default(),
+    );
+    let texture_rect = buffers.glyphs.first().map(|glyph| glyph.texture_rect);
+    glyph_cache.draw_buffers = buffers;
+    glyph_cache.upload(font_texture)?;
+    Ok(texture_rect)
+}
+
+/// Called by [FontTexture::update_reserved_area].
+fn update_reserved_area(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    rect: Rect,
+    rgba_pixels: &[u8],
+) -> Result<(), FontTextureError> {
+    let reserved = glyph_cache.rect_allocator.packer.reserved();
+    if !reserved.iter().any(|reserved| reserved.contains_rect(rect)) {
+        return Err(FontTextureError::TextureUpdate(format!(
+            "{:?} is not within a reserved area of the glyph cache",
+            rect
+        )));
+    }
+    let length = rect.width() as usize * rect.height() as usize * 4;
+    if rgba_pixels.len() != length {
+        return Err(FontTextureError::TextureUpdate(format!(
+            "{} bytes of pixels for {:?}, which needs {}",
+            rgba_pixels.len(),
+            rect,
+            length
+        )));
+    }
+    glyph_cache.write_pixels(rect, rgba_pixels);
+    glyph_cache.upload(font_texture)
+}
+
+/// Called by [FontTexture::glyph_metrics].
+fn glyph_metrics(
+    glyph_cache: &GlyphCache,
+    settings: &Settings,
+    glyph: GlyphRasterConfig,
+    color: Color,
+) -> Option<Metrics> {
+    let key = cache_key(settings, glyph, color);
+    glyph_cache.rect_allocator.metrics(&key)
+}
+
+/// Returns how the glyph should be stored in the glyph cache, or
+/// None if its bitmap would be empty.
+fn glyph_raster<U: Copy>(
+    settings: &Settings,
+    metrics_cache: &mut geometry::MetricsCache,
+    fonts: &[Font],
+    glyph: &GlyphPosition<U>,
+    color_mode: ColorMode,
+    raster_color: Color,
+) -> Option<GlyphRaster> {
+    let raster_px = raster_px(settings, glyph.key.px);
+    let (raster_config, width, height) = if raster_px == glyph.key.px {
+        (glyph.key, glyph.width, glyph.height)
+    } else {
+        let raster_config = GlyphRasterConfig {
+            px: raster_px,
+            ..glyph.key
+        };
+        let metrics = metrics_cache.metrics(&fonts[glyph.font_index], raster_config);
+        (raster_config, metrics.width, metrics.height)
+    };
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let (width, height) = (
+        width.min(u32::MAX as usize) as u32,
+        height.min(u32::MAX as usize) as u32,
+    );
+    let key = GlyphKey {
+        glyph: raster_config,
+        rgb: raster_color.rgb(),
+        raster_settings: settings.raster_settings(color_mode),
+        subpixel: color_mode == ColorMode::Subpixel,
+        override_char: None,
+    };
+    Some(GlyphRaster {
+        key,
+        font_index: glyph.font_index,
+        width,
+        height,
+    })
+}
+
+/// Returns the coverage bitmap with rows of `width` bytes, cropped or
+/// padded with zeroes to rows of `new_width` bytes, and `new_height`
+/// rows.
+fn fit_coverage(pixels: &[u8], width: usize, new_width: usize, new_height: usize) -> Vec<u8> {
+    let mut fitted = vec![0; new_width * new_height];
+    if width == 0 {
+        return fitted;
+    }
+    let row_length = width.min(new_width);
+    for (row, fitted_row) in pixels
+        .chunks_exact(width)
+        .zip(fitted.chunks_exact_mut(new_width))
+    {
+        fitted_row[..row_length].copy_from_slice(&row[..row_length]);
+    }
+    fitted
+}
+
+impl GlyphRaster {
+    /// Returns true if the glyph is bigger than [MAX_GLYPH_SIZE], so
+    /// it shouldn't be rasterized.
+    fn is_too_large(&self) -> bool {
+        self.width > MAX_GLYPH_SIZE || self.height > MAX_GLYPH_SIZE
+    }
+
+    /// Returns the raster for the character's glyph override with
+    /// the metrics instead, if there is one.
+    fn overridden(self, character: char, override_metrics: Option<Metrics>) -> GlyphRaster {
+        match override_metrics {
+            Some(metrics) => GlyphRaster {
+                key: GlyphKey {
+                    override_char: Some(character),
+                    ..self.key
+                },
+                width: metrics.width.min(u32::MAX as usize) as u32,
+                height: metrics.height.min(u32::MAX as usize) as u32,
+                ..self
+            },
+            None => self,
+        }
+    }
+}
+
+/// Returns the glyph moved and resized to fit the bitmap of a glyph
+/// override with the metrics, on the same baseline and at the same
+/// pen position as the font's glyph.
+fn override_glyph<U: Copy>(
+    fonts: &[Font],
+    glyph: &GlyphPosition<U>,
+    metrics: &Metrics,
+) -> GlyphPosition<U> {
+    let position = decoration::glyph_line_position(fonts, glyph);
+    GlyphPosition {
+        x: position.left + metrics.xmin as f32,
+        y: position.baseline - (metrics.height as i32 + metrics.ymin) as f32,
+        width: metrics.width,
+        height: metrics.height,
+        ..*glyph
+    }
+}
+
+/// Rasterizes the glyph like [rasterize_glyph], or from its glyph
+/// override, if the raster is for one. `px` is the size the glyph is
+/// laid out at, which the override is looked up with.
+fn rasterize_glyph_or_override(
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    fonts: &[Font],
+    raster: &GlyphRaster,
+    px: f32,
+) -> Option<Vec<u8>> {
+    let character = match raster.key.override_char {
+        Some(character) => character,
+        None => return rasterize_glyph(settings, fonts, raster),
+    };
+    let (metrics, coverage) = glyph_cache.glyph_override(character, px)?;
+    let coverage = if raster.key.subpixel {
+        // The same coverage for every subpixel.
+        coverage.iter().flat_map(|&c| [c, c, c]).collect()
+    } else {
+        coverage.clone()
+    };
+    coverage_pixels(settings, raster, *metrics, coverage)
+}
+
+/// Rasterizes the glyph into the RGBA pixels stored in the glyph
+/// cache. Returns None if the glyph has no coverage at all, e.g. for
+/// whitespace.
+fn rasterize_glyph(settings: &Settings, fonts: &[Font], raster: &GlyphRaster) -> Option<Vec<u8>> {
+    let font = &fonts[raster.font_index];
+    let supersample = raster.key.raster_settings.supersample;
+    let (metrics, pixels) = if raster.key.subpixel {
+        font.rasterize_config_subpixel(raster.key.glyph)
+    } else if supersample > 1 {
+        rasterize_supersampled(font, raster, supersample as u32)
+    } else {
+        font.rasterize_config(raster.key.glyph)
+    };
+    coverage_pixels(settings, raster, metrics, pixels)
+}
+
+/// Converts the glyph's coverage bitmap, with the metrics, into the
+/// RGBA pixels stored in the glyph cache. Returns None if the glyph
+/// has no coverage at all, or if the bitmap doesn't match the
+/// metrics.
+fn coverage_pixels(
+    settings: &Settings,
+    raster: &GlyphRaster,
+    metrics: Metrics,
+    mut pixels: Vec<u8>,
+) -> Option<Vec<u8>> {
+    let channels = if raster.key.subpixel { 3 } else { 1 };
+    if pixels.len() != metrics.width * metrics.height * channels {
+        // The bitmap doesn't match its own metrics, so there's no
+        // telling which pixel goes where. Skipped like a blank glyph,
+        // instead of writing garbage into the glyph cache.
+        log_warn!(
+            "Glyph (glyph index {}, size {}, font {}) has {} bytes of coverage, but it's {}x{}",
+            raster.key.glyph.glyph_index,
+            raster.key.glyph.px,
+            raster.font_index,
+            pixels.len(),
+            metrics.width,
+            metrics.height,
+        );
+        return None;
+    }
+    if (metrics.width, metrics.height) != (raster.width as usize, raster.height as usize) {
+        // The glyph's size in the layout doesn't match the font, so
+        // the layout is malformed. The pixels are cropped or padded to
+        // the size in the layout, which is what the glyph's space in
+        // the glyph cache was reserved with.
+        log_warn!(
+            "Glyph (glyph index {}, size {}, font {}) is {}x{} in the layout, but {}x{} in the font",
+            raster.key.glyph.glyph_index,
+            raster.key.glyph.px,
+            raster.font_index,
+            raster.width,
+            raster.height,
+            metrics.width,
+            metrics.height,
+        );
+        pixels = fit_coverage(
+            &pixels,
+            metrics.width * channels,
+            raster.width as usize * channels,
+            raster.height as usize,
+        );
+    }
+    if pixels.iter().all(|&coverage| coverage == 0) {
+        return None;
+    }
+    Some(if raster.key.subpixel {
+        subpixel_color_pixels(settings, &pixels)
+    } else {
+        let (r, g, b) = raster.key.rgb;
+        let background = raster.key.raster_settings.background;
+        full_color_pixels(settings, Color::RGB(r, g, b), background, &pixels)
+    })
+}
+
+/// Rasterizes the glyph at `factor` times its size, and box-filters
+/// the coverage down to the glyph's actual bitmap, which has the
+/// size and position fontdue would rasterize it at.
+fn rasterize_supersampled(font: &Font, raster: &GlyphRaster, factor: u32) -> (Metrics, Vec<u8>) {
+    let metrics = font.metrics_indexed(raster.key.glyph.glyph_index, raster.key.glyph.px);
+    let (large_metrics, large_pixels) = font.rasterize_config(GlyphRasterConfig {
+        px: raster.key.glyph.px * factor as f32,
+        ..raster.key.glyph
+    });
+    let factor = factor as i32;
+    let top = metrics.ymin + metrics.height as i32;
+    let large_top = large_metrics.ymin + large_metrics.height as i32;
+    let (large_width, large_height) = (large_metrics.width as i32, large_metrics.height as i32);
+    let mut pixels = Vec::with_capacity(metrics.width * metrics.height);
+    for y in 0..metrics.height as i32 {
+        // The bitmaps are top-down, while the metrics are y-up.
+        let large_y = large_top - (top - y) * factor;
+        for x in 0..metrics.width as i32 {
+            let large_x = (metrics.xmin + x) * factor - large_metrics.xmin;
+            let mut coverage = 0;
+            for sample_y in (large_y..large_y + factor).filter(|y| (0..large_height).contains(y)) {
+                for sample_x in (large_x..large_x + factor).filter(|x| (0..large_width).contains(x))
+                {
+                    coverage += large_pixels[(sample_y * large_width + sample_x) as usize] as i32;
+                }
+            }
+            pixels.push((coverage / (factor * factor)) as u8);
+        }
+    }
+    (metrics, pixels)
+}
+
+/// Converts the coverage of a glyph rasterized by fontdue into the
+/// RGBA pixels stored in the glyph cache. With a background, the
+/// pixels are the color blended against the background, and opaque.
+fn full_color_pixels(
+    settings: &Settings,
+    raster_color: Color,
+    background: Option<(u8, u8, u8)>,
+    pixels: &[u8],
+) -> Vec<u8> {
+    let mut full_color_pixels = Vec::with_capacity(pixels.len() * 4);
+    if let Some(background) = background {
+        let blended = background_blend_table(raster_color, background);
+        for &coverage in pixels {
+            let (r, g, b) = blended[settings.coverage_table[coverage as usize] as usize];
+            full_color_pixels.extend_from_slice(&[r, g, b, 0xFF]);
+        }
+        return full_color_pixels;
+    }
+    for &coverage in pixels {
+        let coverage = settings.coverage_table[coverage as usize];
+        let (r, g, b) = raster_color.rgb();
+        if settings.raster.premultiplied_alpha {
+            full_color_pixels.push(multiply_u8(r, coverage));
+            full_color_pixels.push(multiply_u8(g, coverage));
+            full_color_pixels.push(multiply_u8(b, coverage));
+        } else {
+            full_color_pixels.push(r);
+            full_color_pixels.push(g);
+            full_color_pixels.push(b);
+        }
+        full_color_pixels.push(coverage);
+    }
+    full_color_pixels
+}
+
+/// Returns the color of a pixel of each coverage value, blended
+/// against the background in linear space, for
+/// [FontTexture::set_background_hint].
+fn background_blend_table(color: Color, background: (u8, u8, u8)) -> [(u8, u8, u8); 256] {
+    let to_linear = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let to_srgb = |c: f32| {
+        let c = if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        (c.clamp(0.0, 1.0) * 255.0).round() as u8
+    };
+    let blend = |fg: u8, bg: u8, t: f32| {
+        let (fg, bg) = (to_linear(fg), to_linear(bg));
+        to_srgb(bg + (fg - bg) * t)
+    };
+    let (r, g, b) = background;
+    let mut table = [(0, 0, 0); 256];
+    for (coverage, blended) in table.iter_mut().enumerate() {
+        let t = coverage as f32 / 255.0;
+        *blended = (
+            blend(color.r, r, t),
+            blend(color.g, g, t),
+            blend(color.b, b, t),
+        );
+    }
+    table
+}
+
+/// Converts the subpixel coverage of a glyph rasterized by fontdue
+/// into the RGBA pixels stored in the glyph cache, for
+/// [ColorMode::Subpixel].
+fn subpixel_color_pixels(settings: &Settings, pixels: &[u8]) -> Vec<u8> {
+    // The coverage of each subpixel goes in its own color channel.
+    // The alpha is only used by the blend modes other than the
+    // subpixel ones, so it's just the highest coverage of the pixel.
+    let mut full_color_pixels = Vec::with_capacity(pixels.len() / 3 * 4);
+    for subpixels in pixels.chunks_exact(3) {
+        let [r, g, b] = [0, 1, 2].map(|i| settings.coverage_table[subpixels[i] as usize]);
+        full_color_pixels.extend_from_slice(&[r, g, b, r.max(g).max(b)]);
+    }
+    full_color_pixels
+}
+
+/// Called by [FontTexture::insert_rasterized].
+fn insert_rasterized_glyphs(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    rasterized: RasterizedGlyphs,
+) -> Result<(), FontTextureError> {
+    // Inserting isn't a draw, so this doesn't start one, and doesn't
+    // mark the glyphs already in the cache as used either. That way
+    // the glyphs of the latest draw are never evicted for these.
+    for glyph in rasterized.glyphs {
+        let raster = glyph.raster;
+        if glyph_cache.rect_allocator.contains(&raster.key) {
+            continue;
+        }
+        let pixels = match glyph.pixels {
+            Some(pixels) => pixels,
+            None => {
+                glyph_cache.rect_allocator.mark_blank(raster.key);
+                continue;
+            }
+        };
+        let reservation = glyph_cache.rect_allocator.get_rect_in_texture(
+            raster.key,
+            raster.width + glyph_cache.padding * 2,
+            raster.height + glyph_cache.padding * 2,
+        );
+        match reservation {
+            CacheReservation::EmptySpace(padded_rect) => {
+                glyph_cache.write_padded_pixels(padded_rect, &pixels);
+            }
+            CacheReservation::AlreadyRasterized(_) | CacheReservation::Blank => {}
+            CacheReservation::OutOfSpace => {
+                log_error!(
+                    "Glyph cache cannot fit a pre-rasterized glyph (glyph index {}, size {})",
+                    raster.key.glyph.glyph_index,
+                    raster.key.glyph.px,
+                );
+            }
+            CacheReservation::TooLargeForAtlas => {
+                log_error!(
+                    "Pre-rasterized glyph (glyph index {}, size {}) exceeds the glyph cache's size",
+                    raster.key.glyph.glyph_index,
+                    raster.key.glyph.px,
+                );
+            }
+        }
+    }
+    glyph_cache.upload(font_texture)
+}
+
+/// Called by [FontTexture::draw_text] and the other drawing
+/// functions of [FontTexture]. The color of each glyph is read from
+/// its user data with `color_of`.
+#[allow(clippy::too_many_arguments)]
+fn draw_text<RT: RenderTarget, U: Copy, F: Fn(&U) -> Color>(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    canvas: &mut Canvas<RT>,
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    color_of: F,
+    mut options: DrawOptions,
+) -> Result<DrawReport, FontTextureError> {
+    check_bound_fonts(glyph_cache, fonts)?;
+    glyph_cache.hit_rects.clear();
+    if !glyph_cache.frame_started {
+        glyph_cache.frame_stats = FrameStats::default();
+    }
+    // Skip touching the canvas state at all when there's nothing to
+    // draw, e.g. for empty text elements in a UI.
+    if glyphs.is_empty() {
+        return Ok(DrawReport::default());
+    }
+    // Drawing coordinates are relative to the viewport, so the
+    // visible area starts from the origin.
+    let viewport = canvas.viewport();
+    let viewport = Rect::new(0, 0, viewport.width(), viewport.height());
+    options.visible_area = match options.clip {
+        Some(clip) => clip.intersection(viewport),
+        None => Some(viewport),
+    };
+    if options.visible_area.is_none() {
+        // Nothing would be visible.
+        return Ok(DrawReport::default());
+    }
+    options.raster_budget = settings.raster_budget;
+    options.batched = settings.batched;
+    if let (Some(clip), Some(previous_clip)) = (options.clip, canvas.clip_rect()) {
+        if !previous_clip.has_intersection(clip) {
+            // Nothing would be visible.
+            return Ok(DrawReport::default());
+        }
+    }
+
+    if settings.deferred_uploads
+        && !glyph_cache.pending_draws.is_empty()
+        && !can_fit(
+            glyph_cache,
+            settings,
+            fonts,
+            glyphs,
+            &color_of,
+            options.scale,
+        )
+    {
+        // Caching these glyphs could evict glyphs the pending draws
+        // still need, so those are drawn first.
+        flush_uploads(font_texture, glyph_cache, canvas)?;
+    }
+    let buffers = cache_glyphs(glyph_cache, settings, fonts, glyphs, color_of, &options);
+    glyph_cache.hit_rects.extend_from_slice(&buffers.hit_rects);
+    let mut missing_chars = std::mem::take(&mut glyph_cache.missing_chars);
+    missing_chars.clear();
+    missing_chars.extend(buffers.missing_glyphs.iter().map(|glyph| glyph.parent));
+    let mut report = DrawReport {
+        drawn_glyphs: buffers.glyphs.len() + buffers.oversized_glyphs.len(),
+        missing_glyphs: missing_chars,
+        evicted_glyphs: glyph_cache.rect_allocator.draw_evictions(),
+        deferred_glyphs: buffers.deferred_glyphs,
+        draw_calls: 0,
+    };
+    glyph_cache.frame_stats.drawn_glyphs += report.drawn_glyphs;
+    glyph_cache.frame_stats.missing_glyphs += report.missing_glyphs.len();
+    if settings.deferred_uploads {
+        // The glyphs of the pending draw need to stay in the texture
+        // until it's flushed, even if they're evicted before that.
+        glyph_cache.rect_allocator.hold_frees();
+        glyph_cache.pending_draws.push(PendingDraw {
+            buffers,
+            options: options.without_glyph_angles(),
+            color_mode: settings.color_mode,
+            missing_glyph_style: settings.missing_glyph_style,
+        });
+        return Ok(report);
+    }
+
+    let draw_calls = upload_glyph_cache(font_texture, glyph_cache, canvas).and_then(|_| {
+        render_draw(
+            font_texture,
+            canvas,
+            &buffers,
+            &mut glyph_cache.quad_buffers,
+            &options,
+            settings.color_mode,
+            settings.missing_glyph_style,
+        )
+    });
+    // The buffers are kept for the next draw even if this one fails.
+    glyph_cache.draw_buffers = buffers;
+    report.draw_calls = match draw_calls {
+        Ok(draw_calls) => draw_calls,
+        Err(err) => {
+            glyph_cache.reuse_report(report);
+            return Err(err);
+        }
+    };
+    glyph_cache.frame_stats.draw_calls += report.draw_calls;
+    Ok(report)
+}
+
+/// Returns a hash identifying the font, for
+/// [FontTexture::bind_fonts]. The font's file hash alone doesn't tell
+/// apart fonts loaded from different faces of the same font
+/// collection file, so the face's name, glyph count, units per em,
+/// and line metrics are included too.
+fn font_identity(font: &Font) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    font.file_hash().hash(&mut hasher);
+    font.name().hash(&mut hasher);
+    font.glyph_count().hash(&mut hasher);
+    let units_per_em = font.units_per_em();
+    units_per_em.to_bits().hash(&mut hasher);
+    if let Some(line_metrics) = font.horizontal_line_metrics(units_per_em) {
+        let metrics = [
+            line_metrics.ascent,
+            line_metrics.descent,
+            line_metrics.line_gap,
+        ];
+        metrics.map(f32::to_bits).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Called by [FontTexture::bind_fonts].
+fn bind_fonts(glyph_cache: &mut GlyphCache, fonts: &[Font]) {
+    glyph_cache.bound_fonts = Some(fonts.iter().map(font_identity).collect());
+}
+
+/// Returns an error if fonts have been bound with
+/// [FontTexture::bind_fonts], and the given fonts aren't the same
+/// ones in the same order.
+fn check_bound_fonts(glyph_cache: &GlyphCache, fonts: &[Font]) -> Result<(), FontTextureError> {
+    let bound_fonts = match &glyph_cache.bound_fonts {
+        Some(bound_fonts) => bound_fonts,
+        None => return Ok(()),
+    };
+    if bound_fonts.len() != fonts.len() {
+        return Err(FontTextureError::FontMismatch(format!(
+            "{} fonts were given, but {} are bound",
+            fonts.len(),
+            bound_fonts.len()
+        )));
+    }
+    let mismatch = (bound_fonts.iter().zip(fonts))
+        .position(|(&bound_font, font)| bound_font != font_identity(font));
+    match mismatch {
+        Some(font_index) => Err(FontTextureError::FontMismatch(format!(
+            "the font at index {} is not the bound font",
+            font_index
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Uploads the pixels written to the glyph cache since the last
+/// upload to the glyph cache texture, by rendering them into it if
+/// it's a target texture.
+fn upload_glyph_cache<RT: RenderTarget>(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    canvas: &mut Canvas<RT>,
+) -> Result<(), FontTextureError> {
+    if font_texture.query().access == TextureAccess::Target {
+        // Safety: the renderer pointer is valid for the lifetime of
+        // the Canvas, and the texture is a target texture created for
+        // the canvas' renderer.
+        unsafe { glyph_cache.upload_rendered(font_texture, canvas.raw()) }
+    } else {
+        glyph_cache.upload(font_texture)
+    }
+}
+
+/// Draws the glyphs cached by [cache_glyphs] onto the canvas, clipped
+/// to [DrawOptions::clip]. Returns the amount of calls made to the
+/// renderer.
+fn render_draw<RT: RenderTarget>(
+    font_texture: &mut Texture,
+    canvas: &mut Canvas<RT>,
+    buffers: &DrawBuffers,
+    quad_buffers: &mut QuadBuffers,
+    options: &DrawOptions,
+    color_mode: ColorMode,
+    missing_glyph_style: MissingGlyphStyle,
+) -> Result<usize, FontTextureError> {
+    let previous_clip = options.clip.map(|_| canvas.clip_rect());
+    if let (Some(clip), Some(previous_clip)) = (options.clip, previous_clip) {
+        match previous_clip.map_or(Some(clip), |previous| previous.intersection(clip)) {
+            Some(clip) => canvas.set_clip_rect(clip),
+            // Nothing would be visible.
+            None => return Ok(0),
+        }
+    }
+
+    let copy_result = if color_mode == ColorMode::Subpixel {
+        copy_glyphs_subpixel(font_texture, canvas, &buffers.glyphs, quad_buffers, options)
+    } else {
+        copy_glyphs(
+            font_texture,
+            canvas,
+            &buffers.glyphs,
+            quad_buffers,
+            options,
+            |color| color,
+        )
+    }
+    .and_then(|draw_calls| {
+        oversized::draw_oversized_glyphs(font_texture, canvas, &buffers.oversized_glyphs, options)
+            .map(|_| draw_calls + buffers.oversized_glyphs.len())
+    });
+    draw_missing_glyphs(
+        canvas,
+        &buffers.missing_glyphs,
+        missing_glyph_style,
+        options,
+    );
+
+    if let Some(previous_clip) = previous_clip {
+        canvas.set_clip_rect(previous_clip);
+    }
+    copy_result
+}
+
+/// Called by [FontTexture::flush_uploads], and [draw_text] when the
+/// glyph cache is running out of space. Uploads the glyphs cached by
+/// the pending draws, and then draws them in the order they were
+/// made. Returns the amount of calls made to the renderer.
+fn flush_uploads<RT: RenderTarget>(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    canvas: &mut Canvas<RT>,
+) -> Result<usize, FontTextureError> {
+    upload_glyph_cache(font_texture, glyph_cache, canvas)?;
+    let pending_draws = std::mem::take(&mut glyph_cache.pending_draws);
+    // The glyphs are in the texture now, so the rects of the glyphs
+    // evicted in the meantime can be reused by the next draws.
+    glyph_cache.rect_allocator.release_frees();
+    let mut draw_calls = 0;
+    for draw in pending_draws {
+        draw_calls += render_draw(
+            font_texture,
+            canvas,
+            &draw.buffers,
+            &mut glyph_cache.quad_buffers,
+            &draw.options,
+            draw.color_mode,
+            draw.missing_glyph_style,
+        )?;
+    }
+    glyph_cache.frame_stats.draw_calls += draw_calls;
+    Ok(draw_calls)
+}
+
+/// Called by [FontTexture::resize]. Creates a new glyph cache texture
+/// with the same format, blend mode, and scale mode as the old one,
+/// and moves the cached glyphs into it.
+fn resize_font_texture<'t, T>(
+    font_texture: &Texture,
+    glyph_cache: &mut GlyphCache,
+    texture_creator: &'t TextureCreator<T>,
+    width: u32,
+    height: u32,
+) -> Result<OwnedTexture<'t>, FontTextureError> {
+    let query = font_texture.query();
+    let mut texture =
+        create_font_texture(texture_creator, width, height, query.format, query.access)?;
+    let blend_mode =
+        blend::raw_blend_mode(font_texture).map_err(FontTextureError::TextureCreation)?;
+    blend::set_raw_blend_mode(&mut texture, blend_mode)
+        .map_err(FontTextureError::TextureCreation)?;
+    let mut scale_mode = sdl2::sys::SDL_ScaleMode::SDL_ScaleModeNearest;
+    // Safety: the texture pointer is valid for the lifetime of the
+    // Texture.
+    if unsafe { sdl2::sys::SDL_GetTextureScaleMode(font_texture.raw(), &mut scale_mode) } == 0 {
+        set_scale_mode(&mut texture, scale_mode)?;
+    }
+    glyph_cache.resize(&mut texture, width, height)?;
+    Ok(texture)
+}
+
+/// Returns the settings for [FontTexture::draw_text_scaled], which
+/// rasterizes the glyphs at the canvas' scale, if it has one, like
+/// [Settings::dpi_scale] would.
+fn canvas_scale_settings<RT: RenderTarget>(settings: &Settings, canvas: &Canvas<RT>) -> Settings {
+    let (scale_x, scale_y) = canvas.scale();
+    let scale = scale_x.max(scale_y);
+    if scale == 1.0 || !scale.is_finite() || scale <= 0.0 {
+        return *settings;
+    }
+    Settings {
+        dpi_scale: scale,
+        ..*settings
+    }
+}
+
+/// Returns the settings for drawing into a target texture, which
+/// can't be deferred like [Settings::deferred_uploads], since the
+/// texture should have the text in it when the drawing function
+/// returns, and a flush would draw onto the canvas instead. The draws
+/// that are already pending are flushed onto the canvas first, so
+/// that caching the glyphs drawn into the texture can't evict the
+/// glyphs the pending draws use.
+fn target_settings<RT: RenderTarget>(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    canvas: &mut Canvas<RT>,
+) -> Result<Settings, FontTextureError> {
+    if !glyph_cache.pending_draws.is_empty() {
+        flush_uploads(font_texture, glyph_cache, canvas)?;
+    }
+    Ok(Settings {
+        deferred_uploads: false,
+        ..*settings
+    })
+}
+
+/// Called by [FontTexture::render_to_texture].
+fn render_to_texture<'t, RT: RenderTarget, T>(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    canvas: &mut Canvas<RT>,
+    texture_creator: &'t TextureCreator<T>,
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<Color>],
+) -> Result<Option<OwnedTexture<'t>>, FontTextureError> {
+    let bounds = match text_bounds(glyphs) {
+        Some(bounds) => bounds,
+        None => return Ok(None),
+    };
+    let mut texture = texture_creator
+        .create_texture_target(PixelFormatEnum::RGBA32, bounds.width(), bounds.height())
+        .map_err(|err| FontTextureError::TextureCreation(err.to_string()))?;
+    // Blending glyphs onto the transparent texture multiplies their
+    // color by their alpha, whether or not the glyphs themselves have
+    // premultiplied alpha. Renderers without custom blend modes get
+    // the pixels converted back to straight alpha instead.
+    let premultiplied = blend::set_premultiplied_blend_mode(&mut texture).is_ok();
+    if !premultiplied {
+        texture.set_blend_mode(BlendMode::Blend);
+    }
+
+    let mut settings = target_settings(font_texture, glyph_cache, settings, canvas)?;
+    if settings.color_mode == ColorMode::Subpixel {
+        // The subpixel blend modes leave the texture's alpha as it is,
+        // i.e. transparent.
+        settings.color_mode = ColorMode::Modulated;
+    }
+    let settings = &settings;
+    let options = DrawOptions {
+        offset: (-bounds.x(), -bounds.y()),
+        ..DrawOptions::default()
+    };
+    let mut draw_result = Ok(DrawReport::default());
+    let mut straight_pixels = Ok(Vec::new());
+    canvas
+        .with_texture_canvas(&mut texture, |texture_canvas| {
+            let previous_color = texture_canvas.draw_color();
+            texture_canvas.set_draw_color(Color::RGBA(0, 0, 0, 0));
+            texture_canvas.clear();
+            texture_canvas.set_draw_color(previous_color);
+            draw_result = draw_text(
+                font_texture,
+                glyph_cache,
+                settings,
+                texture_canvas,
+                fonts,
+                glyphs,
+                |color: &Color| *color,
+                options,
+            );
+            if !premultiplied && draw_result.is_ok() {
+                straight_pixels = texture_canvas
+                    .read_pixels(None, PixelFormatEnum::RGBA32)
+                    .map(unpremultiply);
+            }
+        })
+        .map_err(|err| FontTextureError::Draw(err.to_string()))?;
+    draw_result?;
+    if !premultiplied {
+        let pixels = straight_pixels.map_err(FontTextureError::Draw)?;
+        texture
+            .update(None, &pixels, bounds.width() as usize * 4)
+            .map_err(|err| FontTextureError::TextureUpdate(err.to_string()))?;
+    }
+
+    Ok(Some(texture))
+}
+
+/// Converts RGBA pixels with premultiplied alpha to straight alpha.
+fn unpremultiply(mut pixels: Vec<u8>) -> Vec<u8> {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u32;
+        for channel in &mut pixel[..3] {
+            if let Some(straight) = (*channel as u32 * 255 + alpha / 2).checked_div(alpha) {
+                *channel = straight.min(255) as u8;
+            }
+        }
+    }
+    pixels
+}
+
+/// Called by [FontTexture::draw_text_appended].
+#[allow(clippy::too_many_arguments)]
+fn draw_text_appended<RT: RenderTarget>(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    canvas: &mut Canvas<RT>,
+    target: &mut Texture,
+    appended: &mut AppendedText,
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<Color>],
+) -> Result<DrawReport, FontTextureError> {
+    let settings = &target_settings(font_texture, glyph_cache, settings, canvas)?;
+    let query = target.query();
+    let (width, height) = (query.width, query.height);
+    let mut cleared_rows = Vec::new();
+    if appended.drawn_glyphs == 0 || appended.drawn_glyphs > glyphs.len() {
+        // Nothing has been drawn yet, or the text was replaced with
+        // a shorter one, so the texture is drawn from scratch.
+        *appended = AppendedText::default();
+        cleared_rows.push(Rect::new(0, 0, width, height));
+    }
+    let new_glyphs = &glyphs[appended.drawn_glyphs..];
+    let previous_scroll = appended.scroll;
+    if let Some(bounds) = text_bounds(new_glyphs) {
+        appended.scroll = previous_scroll.max(bounds.bottom() - height as i32);
+    }
+    // The rows scrolled into view reuse the rows scrolled out of it.
+    let window_bottom = previous_scroll + height as i32;
+    cleared_rows.extend(appended::ring_rects(
+        window_bottom,
+        appended.scroll + height as i32,
+        width,
+        height,
+    ));
+    appended.drawn_glyphs = glyphs.len();
+
+    // The visible rows of the text wrap around the texture, so the
+    // glyphs are drawn in two passes: the rows from the scroll
+    // position to the bottom of the texture, and the rest from the
+    // top of the texture.
+    let top = appended.scroll.rem_euclid(height as i32);
+    let mut passes = vec![(
+        top - appended.scroll,
+        Rect::new(0, top, width, height - top as u32),
+    )];
+    if top > 0 {
+        passes.push((
+            top - appended.scroll - height as i32,
+            Rect::new(0, 0, width, top as u32),
+        ));
+    }
+    let mut report = DrawReport::default();
+    let mut drawn_indices = HashSet::new();
+    let mut draw_error = None;
+    canvas
+        .with_texture_canvas(target, |texture_canvas| {
+            if !cleared_rows.is_empty() {
+                let previous_color = texture_canvas.draw_color();
+                let previous_blend_mode = texture_canvas.blend_mode();
+                texture_canvas.set_draw_color(Color::RGBA(0, 0, 0, 0));
+                texture_canvas.set_blend_mode(BlendMode::None);
+                let result = texture_canvas.fill_rects(&cleared_rows);
+                texture_canvas.set_blend_mode(previous_blend_mode);
+                texture_canvas.set_draw_color(previous_color);
+                if let Err(err) = result {
+                    draw_error = Some(FontTextureError::Draw(err));
+                    return;
+                }
+            }
+            for &(offset_y, clip) in &passes {
+                let options = DrawOptions {
+                    offset: (0, offset_y),
+                    clip: Some(clip),
+                    ..DrawOptions::default()
+                };
+                let pass_result = draw_text(
+                    font_texture,
+                    glyph_cache,
+                    settings,
+                    texture_canvas,
+                    fonts,
+                    new_glyphs,
+                    |color: &Color| *color,
+                    options,
+                );
+                match pass_result {
+                    Ok(pass) => {
+                        // Glyphs on the rows where the texture wraps
+                        // around are drawn in both passes, but only
+                        // counted once.
+                        let drawn_twice = (glyph_cache.hit_rects.iter())
+                            .filter(|(index, _)| !drawn_indices.insert(*index))
+                            .count();
+                        report.drawn_glyphs += pass.drawn_glyphs.saturating_sub(drawn_twice);
+                        report.missing_glyphs.extend(pass.missing_glyphs);
+                        report.evicted_glyphs += pass.evicted_glyphs;
+                        report.deferred_glyphs += pass.deferred_glyphs;
+                        report.draw_calls += pass.draw_calls;
+                    }
+                    Err(err) => {
+                        draw_error = Some(err);
+                        return;
+                    }
+                }
+            }
+        })
+        .map_err(|err| FontTextureError::Draw(err.to_string()))?;
+    match draw_error {
+        Some(err) => Err(err),
+        None => Ok(report),
+    }
+}
+
+/// Called by [FontTexture::draw_text_merged].
+fn draw_text_merged<RT: RenderTarget, T>(
+    font_texture: &mut Texture,
+    glyph_cache: &mut GlyphCache,
+    settings: &Settings,
+    canvas: &mut Canvas<RT>,
+    texture_creator: &TextureCreator<T>,
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<Color>],
+) -> Result<(), FontTextureError> {
+    let previous_blend_mode =
+        blend::raw_blend_mode(font_texture).map_err(FontTextureError::Copy)?;
+    let subpixel = settings.color_mode == ColorMode::Subpixel;
+    let maximum = !subpixel
+        && match blend::set_maximum_blend_mode(font_texture) {
+            Ok(()) => true,
+            Err(err) => {
+                log_debug!("Renderer can't keep the maximum coverage: {}", err);
+                false
+            }
+        };
+    // Without the maximum blend operation, glyphs of the same
+    // translucency are merged by drawing them opaque, and then
+    // drawing the merged glyphs with the translucency.
+    let alpha_of = |glyph: &GlyphPosition<Color>| settings.glyph_color(glyph.user_data).a;
+    let alpha = glyphs.first().map_or(255, alpha_of);
+    let fade = !maximum && !subpixel && glyphs.iter().all(|glyph| alpha_of(glyph) == alpha);
+    if !maximum && !fade {
+        log_debug!("Can't merge overlapping glyphs, drawing them separately");
+        let options = DrawOptions::default();
+        let color_of = |color: &Color| *color;
+        return draw_text(
+            font_texture,
+            glyph_cache,
+            settings,
+            canvas,
+            fonts,
+            glyphs,
+            color_of,
+            options,
+        )
+        .map(|report| glyph_cache.reuse_report(report));
+    }
+
+    let opaque_glyphs;
+    let glyphs = if fade {
+        opaque_glyphs = glyphs
+            .iter()
+            .map(|glyph| GlyphPosition {
+                user_data: Color {
+                    a: 255,
+                    ..settings.glyph_color(glyph.user_data)
+                },
+                ..*glyph
+            })
+            .collect::<Vec<_>>();
+        &opaque_glyphs[..]
+    } else {
+        glyphs
+    };
+    let rendered = render_to_texture(
+        font_texture,
+        glyph_cache,
+        settings,
+        canvas,
+        texture_creator,
+        fonts,
+        glyphs,
+    );
+    blend::set_raw_blend_mode(font_texture, previous_blend_mode).map_err(FontTextureError::Copy)?;
+    if let (Some(mut texture), Some(bounds)) = (rendered?, text_bounds(glyphs)) {
+        let premultiplied = blend::raw_blend_mode(&texture).map_err(FontTextureError::Copy)?
+            != BlendMode::Blend as u32;
+        if fade {
+            texture.set_alpha_mod(alpha);
+            if premultiplied {
+                // The color is faded along with the alpha.
+                texture.set_color_mod(alpha, alpha, alpha);
+            }
+        } else if !settings.raster.premultiplied_alpha {
+            // Keeping the maximum doesn't multiply the color of
+            // straight alpha glyphs by their alpha like blending does.
+            texture.set_blend_mode(BlendMode::Blend);
+        }
+        canvas
+            .copy(&texture, None, bounds)
+            .map_err(FontTextureError::Copy)?;
+        #[cfg(feature = "unsafe_textures")]
+        // Safety: the texture was just created, and is only used
+        // above.
+        unsafe {
+            texture.destroy()
+        };
+    }
+    Ok(())
+}
+
+/// Returns the options for drawing the glyphs fitted into `target`
+/// with [FontTexture::draw_text_fitted], or None if no glyph would be
+/// drawn.
+fn fitted_draw_options<U: Copy>(
+    glyphs: &[GlyphPosition<U>],
+    target: Rect,
+    mode: FitMode,
+) -> Option<DrawOptions<'static>> {
+    let bounds = text_bounds(glyphs)?;
+    let (scale_x, scale_y) = fit_scale(bounds, target, mode);
+    // The glyphs are rasterized at the smaller of the scales, and
+    // stretched the rest of the way after rasterization, so that they
+    // stay sharp when scaled uniformly.
+    let scale = scale_x.min(scale_y);
+    let center = |rect: Rect| {
+        (
+            rect.x() as f32 + rect.width() as f32 / 2.0,
+            rect.y() as f32 + rect.height() as f32 / 2.0,
+        )
+    };
+    let (bounds_x, bounds_y) = center(bounds);
+    let (target_x, target_y) = center(target);
+    let transform = CopyTransform {
+        scale: (scale_x / scale, scale_y / scale),
+        translation: (target_x - bounds_x * scale_x, target_y - bounds_y * scale_y),
+    };
+    Some(DrawOptions {
+        scale: Some(scale),
+        transform: Some(transform),
+        clip: if mode == FitMode::Cover {
+            Some(target)
+        } else {
+            None
+        },
+        ..DrawOptions::default()
+    })
+}
+
+/// Returns the rectangle on the canvas to copy the glyph into, and
+/// the angle and center to rotate it around, if it's rotated.
+fn glyph_copy_transform(
+    glyph: &RenderableGlyph,
+    options: &DrawOptions,
+) -> (FRect, Option<(f64, FPoint)>) {
+    // Copying to a fractional position keeps text that moves by less
+    // than a pixel from snapping to whole pixels, as long as the
+    // texture is sampled with linear filtering.
+    let rect = glyph.canvas_rect;
+    let mut canvas_rect = FRect::new(
+        rect.x() as f32 + glyph.fraction.0,
+        rect.y() as f32 + glyph.fraction.1,
+        rect.width() as f32,
+        rect.height() as f32,
+    );
+    if let Some(transform) = &options.transform {
+        canvas_rect = transform.apply(canvas_rect);
+    }
+    let rotation = if let Some((pivot, angle)) = options.rotation {
+        let center = FPoint::new(
+            pivot.x() as f32 - canvas_rect.x(),
+            pivot.y() as f32 - canvas_rect.y(),
+        );
+        Some((angle, center))
+    } else if glyph.angle != 0.0 {
+        let center = FPoint::new(canvas_rect.width() / 2.0, canvas_rect.height() / 2.0);
+        Some((glyph.angle, center))
+    } else {
+        None
+    };
+    (canvas_rect, rotation)
+}
+
+/// Copies the glyphs from the glyph cache texture to the canvas. The
+/// glyphs' modulation is passed through `modulation_of` first.
+/// Returns the amount of calls made to the renderer.
+fn copy_glyphs<RT: RenderTarget>(
+    font_texture: &mut Texture,
+    canvas: &mut Canvas<RT>,
+    glyphs: &[RenderableGlyph],
+    quad_buffers: &mut QuadBuffers,
+    options: &DrawOptions,
+    modulation_of: fn(Color) -> Color,
+) -> Result<usize, FontTextureError> {
+    if options.matrix.is_some() {
+        return copy_glyphs_batched(
+            font_texture,
+            canvas,
+            glyphs,
+            quad_buffers,
+            options,
+            modulation_of,
+        );
+    }
+    if options.batched {
+        match copy_glyphs_batched(
+            font_texture,
+            canvas,
+            glyphs,
+            quad_buffers,
+            options,
+            modulation_of,
+        ) {
+            Ok(draw_calls) => return Ok(draw_calls),
+            Err(err) => log_debug!(
+                "Renderer can't draw geometry, copying glyphs separately: {}",
+                err
+            ),
+        }
+    }
+    // The alpha (and with ColorMode::Modulated, the color) is applied
+    // when copying, as a modulation of the glyph in the texture.
+    let mut current_modulation = None;
+    let mut result = Ok(());
+    for glyph in glyphs {
+        let (canvas_rect, rotation) = glyph_copy_transform(glyph, options);
+        let modulation = modulation_of(glyph.modulation);
+        if Some(modulation) != current_modulation {
+            let Color { r, g, b, a } = modulation;
+            font_texture.set_color_mod(r, g, b);
+            font_texture.set_alpha_mod(a);
+            current_modulation = Some(modulation);
+        }
+        result = match rotation {
+            Some((angle, center)) => canvas.copy_ex_f(
+                font_texture,
+                glyph.texture_rect,
+                canvas_rect,
+                angle,
+                center,
+                false,
+                false,
+            ),
+            None => canvas.copy_f(font_texture, glyph.texture_rect, canvas_rect),
+        };
+        if result.is_err() {
+            break;
+        }
+    }
+    if current_modulation.is_some() {
+        font_texture.set_color_mod(0xFF, 0xFF, 0xFF);
+        font_texture.set_alpha_mod(0xFF);
+    }
+    result.map(|_| glyphs.len()).map_err(FontTextureError::Copy)
+}
+
+/// Copies the glyphs from the glyph cache texture to the canvas like
+/// [copy_glyphs], but as triangles, in a single call to the renderer,
+/// so that they can be transformed by [DrawOptions::matrix]. The
+/// modulation is applied per vertex instead of as the texture's
+/// modulation.
+fn copy_glyphs_batched<RT: RenderTarget>(
+    font_texture: &mut Texture,
+    canvas: &mut Canvas<RT>,
+    glyphs: &[RenderableGlyph],
+    quad_buffers: &mut QuadBuffers,
+    options: &DrawOptions,
+    modulation_of: fn(Color) -> Color,
+) -> Result<usize, FontTextureError> {
+    if glyphs.is_empty() {
+        return Ok(0);
+    }
+    let query = font_texture.query();
+    let (width, height) = (query.width as f32, query.height as f32);
+    quad_buffers.quads.clear();
+    quad_buffers.quads.extend(glyphs.iter().map(|glyph| {
+        let (canvas_rect, rotation) = glyph_copy_transform(glyph, options);
+        let corners = match (options.matrix, rotation) {
+            (Some(matrix), _) => transform_corners(canvas_rect, matrix),
+            (None, Some((angle, center))) => {
+                // Rotated like copy_ex_f rotates the rect, around
+                // a center relative to the rect's top-left corner.
+                let pivot = (canvas_rect.x() + center.x(), canvas_rect.y() + center.y());
+                let (sin, cos) = (angle as f32).to_radians().sin_cos();
+                transform_corners(
+                    canvas_rect,
+                    [
+                        cos,
+                        sin,
+                        -sin,
+                        cos,
+                        pivot.0 - pivot.0 * cos + pivot.1 * sin,
+                        pivot.1 - pivot.0 * sin - pivot.1 * cos,
+                    ],
+                )
             }
+            (None, None) => transform_corners(canvas_rect, [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]),
+        };
+        let rect = glyph.texture_rect;
+        quads::Quad {
+            corners,
+            tex_rect: FRect::new(
+                rect.x() as f32 / width,
+                rect.y() as f32 / height,
+                rect.width() as f32 / width,
+                rect.height() as f32 / height,
+            ),
+            modulation: modulation_of(glyph.modulation),
         }
+    }));
+    // Safety: the renderer and texture pointers are valid for the
+    // lifetimes of the Canvas and Texture, and the font texture is
+    // created for the canvas' renderer.
+    unsafe { quads::render_quads(canvas.raw(), font_texture.raw(), quad_buffers) }
+        .map(|_| 1)
+        .map_err(FontTextureError::Copy)
+}
+
+/// Copies the glyphs from the glyph cache texture to the canvas, with
+/// [ColorMode::Subpixel]. Each subpixel is blended separately, which
+/// can't be done with a single blend mode without dual-source
+/// blending, so the glyphs are copied twice: first darkening the
+/// canvas by the coverage of each subpixel, and then adding the
+/// color multiplied by the coverage.
+fn copy_glyphs_subpixel<RT: RenderTarget>(
+    font_texture: &mut Texture,
+    canvas: &mut Canvas<RT>,
+    glyphs: &[RenderableGlyph],
+    quad_buffers: &mut QuadBuffers,
+    options: &DrawOptions,
+) -> Result<usize, FontTextureError> {
+    if glyphs.is_empty() {
+        return Ok(0);
     }
+    let mask_modulation = |Color { a, .. }| Color::RGB(a, a, a);
+    let color_modulation =
+        |Color { r, g, b, a }| Color::RGB(multiply_u8(r, a), multiply_u8(g, a), multiply_u8(b, a));
 
-    for glyph in result_glyphs {
-        canvas.copy(font_texture, glyph.texture_rect, glyph.canvas_rect)?;
+    let previous_blend_mode =
+        blend::raw_blend_mode(font_texture).map_err(FontTextureError::Copy)?;
+    let mut result = blend::set_subpixel_mask_blend_mode(font_texture)
+        .map_err(FontTextureError::Copy)
+        .and_then(|_| {
+            copy_glyphs(
+                font_texture,
+                canvas,
+                glyphs,
+                quad_buffers,
+                options,
+                mask_modulation,
+            )
+        });
+    if let Ok(mask_draw_calls) = result {
+        result = blend::set_subpixel_color_blend_mode(font_texture)
+            .map_err(FontTextureError::Copy)
+            .and_then(|_| {
+                copy_glyphs(
+                    font_texture,
+                    canvas,
+                    glyphs,
+                    quad_buffers,
+                    options,
+                    color_modulation,
+                )
+            })
+            .map(|color_draw_calls| mask_draw_calls + color_draw_calls);
     }
+    blend::set_raw_blend_mode(font_texture, previous_blend_mode).map_err(FontTextureError::Copy)?;
+    result
+}
 
+/// Draws the fallback rectangles for glyphs that didn't fit in the
+/// glyph cache.
+fn draw_missing_glyphs<RT: RenderTarget>(
+    canvas: &mut Canvas<RT>,
+    glyphs: &[MissingGlyph],
+    style: MissingGlyphStyle,
+    options: &DrawOptions,
+) {
+    // The draw color is only touched when there's something to draw.
+    // SDL stores it as the same 8-bit channels it's set with, so the
+    // color read here is restored exactly.
+    if glyphs.is_empty() || style == MissingGlyphStyle::None {
+        return;
+    }
     let previous_color = canvas.draw_color();
-    for glyph in missing_glyphs {
+    // Blended like the glyphs, so that the rectangles of translucent
+    // glyphs are translucent too, whatever the canvas' blend mode is.
+    let previous_blend_mode = canvas.blend_mode();
+    canvas.set_blend_mode(BlendMode::Blend);
+    for glyph in glyphs {
         canvas.set_draw_color(glyph.color);
-        let _ = canvas.draw_rect(glyph.canvas_rect);
+        if let Some(matrix) = options.matrix {
+            // Drawn as an outline regardless of the style, like the
+            // rotated rectangles below.
+            let rect = glyph.canvas_rect;
+            let rect = FRect::new(
+                rect.x() as f32,
+                rect.y() as f32,
+                rect.width() as f32,
+                rect.height() as f32,
+            );
+            let corners = transform_corners(rect, matrix);
+            let _ = canvas
+                .draw_flines(&[corners[0], corners[1], corners[2], corners[3], corners[0]][..]);
+        } else if let Some((pivot, angle)) = options.rotation {
+            let rect = glyph.canvas_rect;
+            let corners = [
+                rect.top_left(),
+                rect.top_right(),
+                rect.bottom_right(),
+                rect.bottom_left(),
+                rect.top_left(),
+            ];
+            let corners = corners.map(|corner| rotate_point(corner, pivot, angle));
+            let _ = canvas.draw_lines(&corners[..]);
+        } else if glyph.angle != 0.0 {
+            // Rotated around the center, like the glyph would be.
+            let rect = match &options.transform {
+                Some(transform) => transform.apply_to_rect(glyph.canvas_rect),
+                None => glyph.canvas_rect,
+            };
+            let corners = [
+                rect.top_left(),
+                rect.top_right(),
+                rect.bottom_right(),
+                rect.bottom_left(),
+                rect.top_left(),
+            ];
+            let corners = corners.map(|corner| rotate_point(corner, rect.center(), glyph.angle));
+            let _ = canvas.draw_lines(&corners[..]);
+        } else {
+            let rect = match &options.transform {
+                Some(transform) => transform.apply_to_rect(glyph.canvas_rect),
+                None => glyph.canvas_rect,
+            };
+            let _ = if style == MissingGlyphStyle::Filled {
+                canvas.fill_rect(rect)
+            } else {
+                canvas.draw_rect(rect)
+            };
+        }
     }
     canvas.set_draw_color(previous_color);
+    canvas.set_blend_mode(previous_blend_mode);
+}
+
+/// Called by [FontTexture::draw_debug_atlas].
+fn draw_debug_atlas<RT: RenderTarget>(
+    font_texture: &Texture,
+    glyph_cache: &GlyphCache,
+    canvas: &mut Canvas<RT>,
+    dest: Rect,
+) -> Result<(), FontTextureError> {
+    canvas
+        .copy(font_texture, None, dest)
+        .map_err(FontTextureError::Copy)?;
 
+    let packer = &glyph_cache.rect_allocator.packer;
+    let scale_x = dest.width() as f32 / packer.width() as f32;
+    let scale_y = dest.height() as f32 / packer.height() as f32;
+    let outlines = packer
+        .empty_rects()
+        .iter()
+        .map(|rect| {
+            FRect::new(
+                dest.x() as f32 + rect.x() as f32 * scale_x,
+                dest.y() as f32 + rect.y() as f32 * scale_y,
+                rect.width() as f32 * scale_x,
+                rect.height() as f32 * scale_y,
+            )
+        })
+        .collect::<Vec<FRect>>();
+    let previous_color = canvas.draw_color();
+    canvas.set_draw_color(Color::RGBA(0, 0xFF, 0, 0xFF));
+    let result = canvas.draw_frects(&outlines);
+    canvas.set_draw_color(previous_color);
+    result.map_err(FontTextureError::Draw)
+}
+
+/// Called by [FontTexture::draw_text_with_highlight].
+fn draw_highlights<RT: RenderTarget, U: Copy>(
+    canvas: &mut Canvas<RT>,
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    ranges: &[Range<usize>],
+    highlight_color: Color,
+) -> Result<(), FontTextureError> {
+    let rects = ranges
+        .iter()
+        .flat_map(|range| decoration::highlight_rects(fonts, glyphs, range.clone()))
+        .collect::<Vec<Rect>>();
+    let previous_color = canvas.draw_color();
+    canvas.set_draw_color(highlight_color);
+    let result = canvas.fill_rects(&rects);
+    canvas.set_draw_color(previous_color);
+    result.map_err(FontTextureError::Draw)
+}
+
+/// Called by [FontTexture::draw_text_decorated].
+fn draw_decorations<RT: RenderTarget, U: Copy>(
+    canvas: &mut Canvas<RT>,
+    glyph_cache: &GlyphCache,
+    fonts: &[Font],
+    glyphs: &[GlyphPosition<U>],
+    decorations: &[Decoration],
+) -> Result<(), FontTextureError> {
+    let previous_color = canvas.draw_color();
+    for decoration in decorations {
+        let rects =
+            decoration::decoration_rects(fonts, glyphs, decoration, &glyph_cache.underline_metrics);
+        canvas.set_draw_color(decoration.color);
+        let result = canvas.fill_rects(&rects);
+        if result.is_err() {
+            canvas.set_draw_color(previous_color);
+            return result.map_err(FontTextureError::Draw);
+        }
+    }
+    canvas.set_draw_color(previous_color);
     Ok(())
 }
@@ -0,0 +1,43 @@
+//! Logging macros which forward to the [log] crate when the `logging`
+//! feature is enabled, and do nothing otherwise, so that the crate
+//! can be built without depending on `log`.
+//!
+//! [log]: https://docs.rs/log
+
+#[cfg(feature = "logging")]
+macro_rules! log_error {
+    ($($arg:tt)+) => (log::error!($($arg)+))
+}
+
+#[cfg(feature = "logging")]
+macro_rules! log_warn {
+    ($($arg:tt)+) => (log::warn!($($arg)+))
+}
+
+#[cfg(feature = "logging")]
+macro_rules! log_debug {
+    ($($arg:tt)+) => (log::debug!($($arg)+))
+}
+
+// The arguments are still type-checked, but never formatted, so the
+// values only used in log messages don't cause unused warnings.
+#[cfg(not(feature = "logging"))]
+macro_rules! log_error {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! log_warn {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! log_debug {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
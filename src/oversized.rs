@@ -0,0 +1,160 @@
+//! Drawing glyphs which are too large to fit in the glyph cache
+//! texture at all, through temporary textures which only live for the
+//! duration of the draw.
+//!
+//! The textures are created with the raw SDL functions, since a
+//! [TextureCreator](sdl2::render::TextureCreator) can only be
+//! acquired from some kinds of [Canvas].
+
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::FRect;
+use sdl2::render::{Canvas, RenderTarget, Texture};
+use sdl2::sys;
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+use crate::quads::{self, Quad, QuadBuffers};
+use crate::{blend, DrawOptions, FontTextureError, RenderableGlyph};
+
+/// A glyph that didn't fit in the glyph cache, along with its
+/// rasterized pixels. The `texture_rect` of the glyph covers the
+/// whole temporary texture.
+pub(crate) struct OversizedGlyph {
+    pub glyph: RenderableGlyph,
+    /// The glyph's RGBA pixels, 4 bytes per pixel.
+    pub pixels: Vec<u8>,
+}
+
+/// A texture owned by this struct, destroyed when it's dropped.
+struct TemporaryTexture {
+    raw: *mut sys::SDL_Texture,
+}
+
+impl TemporaryTexture {
+    /// Creates a static RGBA texture with the glyph's pixels, with
+    /// the same blend and scale modes as `font_texture`.
+    fn new<RT: RenderTarget>(
+        canvas: &Canvas<RT>,
+        font_texture: &Texture,
+        glyph: &OversizedGlyph,
+    ) -> Result<TemporaryTexture, FontTextureError> {
+        let (width, height) = (
+            glyph.glyph.texture_rect.width(),
+            glyph.glyph.texture_rect.height(),
+        );
+        // Safety: the renderer pointer is valid for the lifetime of
+        // the Canvas.
+        let raw = unsafe {
+            sys::SDL_CreateTexture(
+                canvas.raw(),
+                PixelFormatEnum::RGBA32 as u32,
+                sys::SDL_TextureAccess::SDL_TEXTUREACCESS_STATIC as c_int,
+                width as c_int,
+                height as c_int,
+            )
+        };
+        if raw.is_null() {
+            return Err(FontTextureError::TextureCreation(sdl2::get_error()));
+        }
+        let texture = TemporaryTexture { raw };
+
+        let blend_mode = blend::raw_blend_mode(font_texture).map_err(FontTextureError::Copy)?;
+        let mut scale_mode = sys::SDL_ScaleMode::SDL_ScaleModeNearest;
+        // Safety: both texture pointers are valid, and the blend mode
+        // was just returned by SDL.
+        unsafe {
+            blend::set_texture_ptr_blend_mode(texture.raw, blend_mode)
+                .map_err(FontTextureError::TextureCreation)?;
+            if sys::SDL_GetTextureScaleMode(font_texture.raw(), &mut scale_mode) == 0 {
+                sys::SDL_SetTextureScaleMode(texture.raw, scale_mode);
+            }
+        }
+
+        // Safety: the pixel buffer has 4 bytes for every pixel of the
+        // texture.
+        let result = unsafe {
+            sys::SDL_UpdateTexture(
+                texture.raw,
+                ptr::null(),
+                glyph.pixels.as_ptr() as *const c_void,
+                width as c_int * 4,
+            )
+        };
+        if result != 0 {
+            return Err(FontTextureError::TextureUpdate(sdl2::get_error()));
+        }
+        Ok(texture)
+    }
+
+    /// Copies the whole texture onto the canvas, like
+    /// [copy_glyphs](crate::copy_glyphs) copies glyphs from the glyph
+    /// cache.
+    fn copy<RT: RenderTarget>(
+        &self,
+        canvas: &mut Canvas<RT>,
+        glyph: &RenderableGlyph,
+        options: &DrawOptions,
+    ) -> Result<(), FontTextureError> {
+        let (canvas_rect, rotation) = crate::glyph_copy_transform(glyph, options);
+        if let Some(matrix) = options.matrix {
+            let mut buffers = QuadBuffers::default();
+            buffers.quads.push(Quad {
+                corners: crate::transform_corners(canvas_rect, matrix),
+                tex_rect: FRect::new(0.0, 0.0, 1.0, 1.0),
+                modulation: glyph.modulation,
+            });
+            // Safety: the renderer and texture pointers are valid, and
+            // the texture was created for this renderer.
+            return unsafe { quads::render_quads(canvas.raw(), self.raw, &mut buffers) }
+                .map_err(FontTextureError::Copy);
+        }
+        let (angle, center) = match &rotation {
+            Some((angle, center)) => (*angle, center.raw()),
+            None => (0.0, ptr::null()),
+        };
+        let Color { r, g, b, a } = glyph.modulation;
+        // Safety: the renderer and texture pointers are valid, and the
+        // texture was created for this renderer.
+        let result = unsafe {
+            sys::SDL_SetTextureColorMod(self.raw, r, g, b);
+            sys::SDL_SetTextureAlphaMod(self.raw, a);
+            sys::SDL_RenderCopyExF(
+                canvas.raw(),
+                self.raw,
+                ptr::null(),
+                canvas_rect.raw(),
+                angle,
+                center,
+                sys::SDL_RendererFlip::SDL_FLIP_NONE,
+            )
+        };
+        if result != 0 {
+            Err(FontTextureError::Copy(sdl2::get_error()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Drop for TemporaryTexture {
+    fn drop(&mut self) {
+        // Safety: the texture was created in TemporaryTexture::new,
+        // and isn't used after this.
+        unsafe { sys::SDL_DestroyTexture(self.raw) };
+    }
+}
+
+/// Draws the glyphs that were too large for the glyph cache, each
+/// through its own temporary texture.
+pub(crate) fn draw_oversized_glyphs<RT: RenderTarget>(
+    font_texture: &Texture,
+    canvas: &mut Canvas<RT>,
+    glyphs: &[OversizedGlyph],
+    options: &DrawOptions,
+) -> Result<(), FontTextureError> {
+    for glyph in glyphs {
+        let texture = TemporaryTexture::new(canvas, font_texture, glyph)?;
+        texture.copy(canvas, &glyph.glyph, options)?;
+    }
+    Ok(())
+}
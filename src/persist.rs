@@ -0,0 +1,197 @@
+//! Saving the glyphs in a glyph cache into a file, and loading them
+//! back, so that the glyphs don't need to be rasterized again, e.g.
+//! on the next start of the application.
+//!
+//! The format is a header, followed by the glyphs, each one being its
+//! key, size, and RGBA pixels (or only the key, for blank glyphs).
+//! All numbers are little-endian.
+
+use fontdue::layout::GlyphRasterConfig;
+use fontdue::Font;
+use std::io::{self, Read, Write};
+
+use crate::prerasterize::RasterizedGlyph;
+use crate::{GlyphCache, GlyphKey, GlyphRaster, RasterSettings, RasterizedGlyphs};
+
+const MAGIC: &[u8; 8] = b"fdsdl2gc";
+const VERSION: u32 = 4;
+
+/// Writes the glyphs in the glyph cache into the writer.
+pub(crate) fn write_glyph_cache<W: Write>(
+    glyph_cache: &GlyphCache,
+    mut writer: W,
+) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+
+    // Glyph overrides can't be rasterized again from the fonts, so
+    // they're left out.
+    let reservations = glyph_cache
+        .rect_allocator
+        .reservations()
+        .filter(|(key, _)| key.override_char.is_none())
+        .collect::<Vec<_>>();
+    writer.write_all(&(reservations.len() as u32).to_le_bytes())?;
+    for (key, padded_rect) in reservations {
+        let rect = crate::unpadded_rect(padded_rect, glyph_cache.padding);
+        write_key(&mut writer, key)?;
+        writer.write_all(&rect.width().to_le_bytes())?;
+        writer.write_all(&rect.height().to_le_bytes())?;
+        writer.write_all(&glyph_cache.read_pixels(rect))?;
+    }
+
+    let blank_glyphs = glyph_cache
+        .rect_allocator
+        .blank_glyphs()
+        .filter(|key| key.override_char.is_none())
+        .collect::<Vec<_>>();
+    writer.write_all(&(blank_glyphs.len() as u32).to_le_bytes())?;
+    for key in blank_glyphs {
+        write_key(&mut writer, key)?;
+    }
+    writer.flush()
+}
+
+/// Reads glyphs written by [write_glyph_cache]. Glyphs from fonts
+/// that aren't in `fonts` are skipped, since their fonts can't be
+/// identified, or have changed since the glyphs were written.
+pub(crate) fn read_glyphs<R: Read>(fonts: &[Font], mut reader: R) -> io::Result<RasterizedGlyphs> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid_data("not a glyph cache file"));
+    }
+    let version = read_u32(&mut reader)?;
+    if version != VERSION {
+        return Err(invalid_data(&format!(
+            "unsupported glyph cache version {}",
+            version
+        )));
+    }
+
+    let mut glyphs = Vec::new();
+    let glyph_count = read_u32(&mut reader)?;
+    for _ in 0..glyph_count {
+        let key = read_key(&mut reader)?;
+        let width = read_u32(&mut reader)?;
+        let height = read_u32(&mut reader)?;
+        let length = (width as u64 * height as u64 * 4) as usize;
+        let mut pixels = Vec::new();
+        (&mut reader).take(length as u64).read_to_end(&mut pixels)?;
+        if pixels.len() != length {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        if let Some(font_index) = font_index(fonts, &key) {
+            glyphs.push(RasterizedGlyph {
+                raster: GlyphRaster {
+                    key,
+                    font_index,
+                    width,
+                    height,
+                },
+                pixels: Some(pixels),
+            });
+        }
+    }
+
+    let blank_count = read_u32(&mut reader)?;
+    for _ in 0..blank_count {
+        let key = read_key(&mut reader)?;
+        if let Some(font_index) = font_index(fonts, &key) {
+            glyphs.push(RasterizedGlyph {
+                raster: GlyphRaster {
+                    key,
+                    font_index,
+                    width: 0,
+                    height: 0,
+                },
+                pixels: None,
+            });
+        }
+    }
+
+    Ok(RasterizedGlyphs { glyphs })
+}
+
+/// Returns the index of the font the glyph is from, if it's in the
+/// fonts and has the glyph.
+fn font_index(fonts: &[Font], key: &GlyphKey) -> Option<usize> {
+    fonts.iter().position(|font| {
+        font.file_hash() == key.glyph.font_hash && key.glyph.glyph_index < font.glyph_count()
+    })
+}
+
+fn write_key<W: Write>(writer: &mut W, key: &GlyphKey) -> io::Result<()> {
+    let (r, g, b) = key.rgb;
+    writer.write_all(&key.glyph.glyph_index.to_le_bytes())?;
+    writer.write_all(&key.glyph.px.to_bits().to_le_bytes())?;
+    writer.write_all(&(key.glyph.font_hash as u64).to_le_bytes())?;
+    writer.write_all(&[r, g, b])?;
+    writer.write_all(&[key.raster_settings.premultiplied_alpha as u8])?;
+    writer.write_all(&key.raster_settings.gamma_bits.to_le_bytes())?;
+    writer.write_all(&key.raster_settings.coverage_curve_hash.to_le_bytes())?;
+    writer.write_all(&[key.raster_settings.supersample])?;
+    writer.write_all(&[key.raster_settings.monochrome as u8])?;
+    let (has_background, (r, g, b)) = match key.raster_settings.background {
+        Some(background) => (1, background),
+        None => (0, (0, 0, 0)),
+    };
+    writer.write_all(&[has_background, r, g, b])?;
+    writer.write_all(&[key.subpixel as u8])
+}
+
+fn read_key<R: Read>(reader: &mut R) -> io::Result<GlyphKey> {
+    let mut glyph_index = [0; 2];
+    reader.read_exact(&mut glyph_index)?;
+    let px = f32::from_bits(read_u32(reader)?);
+    let font_hash = read_u64(reader)? as usize;
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    let [r, g, b, premultiplied_alpha] = bytes;
+    let gamma_bits = read_u32(reader)?;
+    let coverage_curve_hash = read_u64(reader)?;
+    let mut flags = [0; 2];
+    reader.read_exact(&mut flags)?;
+    let [supersample, monochrome] = flags;
+    let mut background = [0; 4];
+    reader.read_exact(&mut background)?;
+    let [has_background, background_r, background_g, background_b] = background;
+    let mut subpixel = [0; 1];
+    reader.read_exact(&mut subpixel)?;
+    let [subpixel] = subpixel;
+    Ok(GlyphKey {
+        glyph: GlyphRasterConfig {
+            glyph_index: u16::from_le_bytes(glyph_index),
+            px,
+            font_hash,
+        },
+        rgb: (r, g, b),
+        raster_settings: RasterSettings {
+            premultiplied_alpha: premultiplied_alpha != 0,
+            gamma_bits,
+            coverage_curve_hash,
+            supersample,
+            monochrome: monochrome != 0,
+            background: Some((background_r, background_g, background_b))
+                .filter(|_| has_background != 0),
+        },
+        subpixel: subpixel != 0,
+        override_char: None,
+    })
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
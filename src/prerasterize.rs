@@ -0,0 +1,89 @@
+use fontdue::layout::GlyphPosition;
+use fontdue::Font;
+use sdl2::pixels::Color;
+
+use crate::geometry::MetricsCache;
+use crate::{ColorMode, GlyphRaster, Settings, Warning};
+
+/// Rasterizes glyphs the same way as the
+/// [`FontTexture`](crate::FontTexture) it was created from, but
+/// without needing access to it, so that the rasterization can be
+/// done on another thread. Created with
+/// [`FontTexture::rasterizer`](crate::FontTexture::rasterizer).
+///
+/// The rasterizer is a snapshot of the settings of the
+/// [`FontTexture`](crate::FontTexture) when it was created. Glyphs
+/// rasterized with outdated settings are still valid, but they won't
+/// be used until the settings are changed back.
+#[derive(Clone)]
+pub struct GlyphRasterizer {
+    pub(crate) settings: Settings,
+}
+
+/// Glyphs rasterized by a [`GlyphRasterizer`], ready to be added to
+/// the glyph cache with
+/// [`FontTexture::insert_rasterized`](crate::FontTexture::insert_rasterized).
+pub struct RasterizedGlyphs {
+    pub(crate) glyphs: Vec<RasterizedGlyph>,
+}
+
+pub(crate) struct RasterizedGlyph {
+    pub raster: GlyphRaster,
+    /// The RGBA pixels of the glyph, or None if the glyph has no
+    /// coverage at all.
+    pub pixels: Option<Vec<u8>>,
+}
+
+impl GlyphRasterizer {
+    /// Rasterizes the glyphs, which should be laid out with the
+    /// fonts, like the ones passed to
+    /// [`FontTexture::draw_text`](crate::FontTexture::draw_text).
+    /// This is the expensive part of drawing new glyphs, which
+    /// [`FontTexture::insert_rasterized`](crate::FontTexture::insert_rasterized)
+    /// can then skip.
+    pub fn rasterize(&self, fonts: &[Font], glyphs: &[GlyphPosition<Color>]) -> RasterizedGlyphs {
+        let mut rasterized = Vec::with_capacity(glyphs.len());
+        let mut metrics_cache = MetricsCache::default();
+        for glyph in glyphs {
+            if crate::geometry::glyph_canvas_rect(glyph).is_none() {
+                continue;
+            }
+            // Same as the colors baked into the glyph cache by
+            // draw_text, see ColorMode.
+            let color_mode = self.settings.color_mode;
+            let raster_color = match color_mode {
+                ColorMode::Rasterized => {
+                    let (r, g, b) = self.settings.glyph_color(glyph.user_data).rgb();
+                    Color::RGB(r, g, b)
+                }
+                ColorMode::Modulated | ColorMode::Subpixel => Color::WHITE,
+            };
+            let raster = crate::glyph_raster(
+                &self.settings,
+                &mut metrics_cache,
+                fonts,
+                glyph,
+                color_mode,
+                raster_color,
+            );
+            match raster {
+                Some(raster) if raster.is_too_large() => {
+                    let warning = Warning::GlyphTooLarge {
+                        character: glyph.parent,
+                        px: raster.key.glyph.px,
+                        font_index: glyph.font_index,
+                        width: raster.width,
+                        height: raster.height,
+                    };
+                    log_error!("{}", warning);
+                }
+                Some(raster) => {
+                    let pixels = crate::rasterize_glyph(&self.settings, fonts, &raster);
+                    rasterized.push(RasterizedGlyph { raster, pixels });
+                }
+                None => {}
+            }
+        }
+        RasterizedGlyphs { glyphs: rasterized }
+    }
+}
@@ -1,45 +1,1003 @@
-use crate::RectAllocator;
-use fontdue::layout::GlyphPosition;
-use fontdue::Font;
-use sdl2::pixels::Color;
-use sdl2::render::{Canvas, RenderTarget, Texture, TextureCreator};
+use crate::{
+    AppendedText, CacheStats, ColorMode, Decoration, DrawOptions, DrawReport, FitMode,
+    FontTextureBuilder, FontTextureError, FrameStats, GlyphCache, GlyphRasterizer,
+    MissingGlyphStyle, RasterizedGlyphs, RectAllocator, Rounding, ScaleMode, Settings,
+    UnderlineMetrics, Warning,
+};
+use fontdue::layout::{
+    CoordinateSystem, GlyphPosition, GlyphRasterConfig, Layout, LayoutSettings, TextStyle,
+};
+use fontdue::{Font, Metrics};
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{BlendMode, Canvas, RenderTarget, Texture, TextureAccess, TextureCreator};
+use std::borrow::Borrow;
+use std::cell::{RefCell, RefMut};
+use std::io::{Read, Write};
+use std::mem;
+use std::ops::{Deref, DerefMut, Range};
+use std::rc::Rc;
 
 /// A text-rendering-enabled wrapper for [`Texture`].
 pub struct FontTexture<'r> {
     /// The texture containing rendered glyphs in a tightly packed
     /// manner.
     pub texture: Texture<'r>,
-    rect_allocator: RectAllocator,
+    glyph_cache: GlyphCache,
+    settings: Settings,
 }
 
-impl FontTexture<'_> {
+impl<'r> FontTexture<'r> {
     /// Creates a new [`FontTexture`] for rendering text.
     ///
     /// Consider the lifetimes of this structure and the given
     /// [`TextureCreator`] as you would a [`Texture`] created with
     /// one, that is why this structure is named "FontTexture".
     ///
-    /// # Important note
+    /// # Sharing between font sets
     ///
-    /// Only use a single `&[Font]` for each [`FontTexture`]. Glyphs
-    /// with the same index but different font are hard to
-    /// differentiate, so using different sets of Fonts when rendering
-    /// with a single FontTexture will lead to wrong results.
+    /// Glyphs are cached by the hash of the font file they're from
+    /// (see [`Font::file_hash`]), so a single [`FontTexture`] can
+    /// draw glyphs laid out with different `&[Font]`s, as long as the
+    /// `fonts` passed to the drawing functions are the same ones the
+    /// glyphs were laid out with. The only exception is loading the
+    /// same font file more than once with different
+    /// [`FontSettings`](fontdue::FontSettings) (e.g. a different
+    /// `collection_index`), since those fonts have the same hash, and
+    /// their glyphs would be mixed up in the cache. Use separate
+    /// [`FontTexture`]s for them.
     ///
     /// # Errors
     ///
-    /// The function will return an error if the Texture can't be
-    /// created, and the Err(String) will contain an error string from
-    /// SDL.
-    pub fn new<T>(texture_creator: &TextureCreator<T>) -> Result<FontTexture, String> {
-        let texture = crate::create_font_texture(texture_creator)?;
-        let rect_allocator = RectAllocator::new(1024, 1024);
+    /// The function will return a
+    /// [`FontTextureError::TextureCreation`] if the Texture can't be
+    /// created, containing an error string from SDL.
+    pub fn new<T>(
+        texture_creator: &TextureCreator<T>,
+    ) -> Result<FontTexture<'_>, FontTextureError> {
+        let size = crate::DEFAULT_TEXTURE_SIZE;
+        FontTexture::with_size(texture_creator, size, size)
+    }
+
+    /// Creates a new [`FontTexture`] with a glyph cache texture of
+    /// the given size. [`FontTexture::new`] uses 1024x1024, which is
+    /// 4 MiB of VRAM: a small HUD might get by with much less, while
+    /// text-heavy applications might want more.
+    ///
+    /// See [`FontTexture::new`] for more information.
+    ///
+    /// ```
+    /// # use fontdue_sdl2::{FontTexture, FontTextureError};
+    /// # use sdl2::pixels::PixelFormatEnum;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// let font_texture = FontTexture::with_size(&texture_creator, 256, 256).unwrap();
+    /// assert_eq!(font_texture.cache_stats().total_area, 256 * 256);
+    ///
+    /// // 65536 * 65536 pixels would overflow the u32 areas are counted in.
+    /// let too_large = FontTexture::with_size(&texture_creator, 65536, 65536);
+    /// assert!(matches!(too_large, Err(FontTextureError::AtlasTooLarge(_))));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The function will return a [`FontTextureError::AtlasTooLarge`]
+    /// if the size exceeds the maximum texture size of the renderer
+    /// (see [`FontTexture::max_texture_size`]) or has too many pixels
+    /// in total, and a
+    /// [`FontTextureError::TextureCreation`] if the size is zero, or
+    /// the Texture can't be created for some other reason.
+    pub fn with_size<T>(
+        texture_creator: &TextureCreator<T>,
+        width: u32,
+        height: u32,
+    ) -> Result<FontTexture<'_>, FontTextureError> {
+        let format = PixelFormatEnum::RGBA32;
+        let texture = crate::create_font_texture(
+            texture_creator,
+            width,
+            height,
+            format,
+            TextureAccess::Streaming,
+        )?;
+        let glyph_cache = GlyphCache::new(width, height, format);
+        Ok(FontTexture {
+            texture,
+            glyph_cache,
+            settings: Settings::default(),
+        })
+    }
+
+    /// Returns the maximum size of the textures the renderer behind
+    /// the texture creator can create, or None if it doesn't have a
+    /// limit. Glyph cache textures larger than this can't be created,
+    /// e.g. by [`FontTexture::with_size`] or [`FontTexture::resize`],
+    /// so sizes picked at runtime should be clamped to it.
+    ///
+    /// ```no_run
+    /// # use fontdue_sdl2::{FontTexture, FontTextureError};
+    /// # let sdl_context = sdl2::init().unwrap();
+    /// # let video_subsystem = sdl_context.video().unwrap();
+    /// # let window = video_subsystem.window("", 800, 600).build().unwrap();
+    /// # let canvas = window.into_canvas().build().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// let max_size = FontTexture::max_texture_size(&texture_creator).unwrap();
+    /// let (max_width, max_height) = max_size.unwrap_or((u32::MAX, u32::MAX));
+    /// if max_width < 16384 || max_height < 16384 {
+    ///     let too_large = FontTexture::with_size(&texture_creator, 16384, 16384);
+    ///     assert!(matches!(too_large, Err(FontTextureError::AtlasTooLarge(_))));
+    /// }
+    /// let font_texture =
+    ///     FontTexture::with_size(&texture_creator, max_width.min(16384), max_height.min(16384));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::TextureCreation`] if the
+    /// renderer's information can't be queried.
+    pub fn max_texture_size<T>(
+        texture_creator: &TextureCreator<T>,
+    ) -> Result<Option<(u32, u32)>, FontTextureError> {
+        crate::max_texture_size(texture_creator)
+    }
+
+    /// Creates a new [`FontTexture`] like
+    /// [`FontTexture::with_size`], but with the glyph cache texture in
+    /// the renderer's preferred channel order (RGBA or BGRA), so
+    /// that SDL doesn't need to convert the pixels when uploading new
+    /// glyphs. On many platforms this is BGRA.
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::with_size`].
+    pub fn with_native_format<T>(
+        texture_creator: &TextureCreator<T>,
+        width: u32,
+        height: u32,
+    ) -> Result<FontTexture<'_>, FontTextureError> {
+        let format = crate::native_glyph_cache_format(texture_creator)?;
+        let texture = crate::create_font_texture(
+            texture_creator,
+            width,
+            height,
+            format,
+            TextureAccess::Streaming,
+        )?;
+        let glyph_cache = GlyphCache::new(width, height, format);
+        Ok(FontTexture {
+            texture,
+            glyph_cache,
+            settings: Settings::default(),
+        })
+    }
+
+    /// Creates a new [`FontTexture`] which uses the given texture
+    /// as its glyph cache, instead of creating a new one. The whole
+    /// texture is used, and its previous contents are overwritten as
+    /// glyphs are cached.
+    ///
+    /// The texture's blend mode is set to
+    /// [`BlendMode::Blend`], like in [`FontTexture::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::TextureCreation`] if the texture
+    /// isn't a streaming texture with the
+    /// [`RGBA32`](sdl2::pixels::PixelFormatEnum::RGBA32) or
+    /// [`BGRA32`](sdl2::pixels::PixelFormatEnum::BGRA32) pixel format.
+    pub fn from_texture<'t>(mut texture: Texture<'t>) -> Result<FontTexture<'t>, FontTextureError> {
+        let (width, height, format) = crate::adopt_font_texture(&mut texture)?;
         Ok(FontTexture {
             texture,
-            rect_allocator,
+            glyph_cache: GlyphCache::new(width, height, format),
+            settings: Settings::default(),
         })
     }
 
+    /// Returns the width and height of the glyph cache texture, i.e.
+    /// [`FontTexture::texture`], without querying SDL for them.
+    pub fn texture_dimensions(&self) -> (u32, u32) {
+        let packer = &self.glyph_cache.rect_allocator.packer;
+        (packer.width(), packer.height())
+    }
+
+    /// Returns the size of the glyph cache texture in bytes, for
+    /// budgeting video memory. The texture has 4 bytes per pixel.
+    ///
+    /// Note that the glyph cache also keeps a copy of the texture's
+    /// pixels in regular memory, which takes the same amount of
+    /// space.
+    pub fn memory_usage(&self) -> usize {
+        let (width, height) = self.texture_dimensions();
+        width as usize * height as usize * 4
+    }
+
+    /// Sets how the colors of glyphs are applied. See [`ColorMode`]
+    /// for the options. The default is [`ColorMode::Rasterized`].
+    ///
+    /// This can be changed at any time, glyphs already in the cache
+    /// stay valid.
+    ///
+    /// With every color mode, the alpha of the glyphs' colors is
+    /// applied when they're drawn, and isn't part of the cached
+    /// glyphs, so fading text in or out doesn't rasterize it again:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// font_texture.begin_frame();
+    /// for alpha in 0..=255 {
+    ///     let color = Color::RGBA(0xFF, 0x80, 0x00, alpha);
+    ///     let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    ///     layout.append(fonts, &TextStyle::with_user_data("A", 32.0, 0, color));
+    ///     font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// }
+    /// assert_eq!(font_texture.cache_stats().glyph_count, 1);
+    /// assert_eq!(font_texture.last_frame_stats().rasterized_glyphs, 1);
+    /// ```
+    pub fn set_color_mode(&mut self, color_mode: ColorMode) {
+        self.settings.color_mode = color_mode;
+    }
+
+    /// Sets whether glyphs are stored with premultiplied alpha, i.e.
+    /// with their color channels multiplied by their coverage. This
+    /// also sets the blend mode of the texture to a custom blend mode
+    /// for premultiplied alpha.
+    ///
+    /// Use this when compositing text into an intermediate texture
+    /// which is then drawn with premultiplied alpha blending. Straight
+    /// alpha glyphs get dark halos around them in that case.
+    /// ([`FontTexture::render_to_texture`] handles this by itself.)
+    ///
+    /// This can be changed at any time, glyphs already in the cache
+    /// stay valid. Note that [`Texture::blend_mode`] panics for the
+    /// custom blend mode set by this function.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::TextureCreation`] if the
+    /// renderer doesn't support the required blend mode.
+    pub fn set_premultiplied_alpha(
+        &mut self,
+        premultiplied_alpha: bool,
+    ) -> Result<(), FontTextureError> {
+        if premultiplied_alpha {
+            crate::blend::set_premultiplied_blend_mode(&mut self.texture)
+                .map_err(FontTextureError::TextureCreation)?;
+        } else {
+            self.texture.set_blend_mode(BlendMode::Blend);
+        }
+        self.settings.raster.premultiplied_alpha = premultiplied_alpha;
+        Ok(())
+    }
+
+    /// Sets the gamma applied to the coverage of glyphs when they're
+    /// rasterized: `alpha = coverage ^ (1 / gamma)`. The default is
+    /// 1.0, which leaves the coverage as is.
+    ///
+    /// fontdue outputs linear coverage, but SDL blends in sRGB space,
+    /// which makes anti-aliased text look thinner and lighter than
+    /// text rendered by most native rasterizers. A gamma of around
+    /// 2.2 compensates for this, making small text look closer to
+    /// its intended weight.
+    ///
+    /// This can be changed at any time, glyphs already in the cache
+    /// stay valid, and glyphs rasterized with a different gamma get
+    /// their own cache entries.
+    pub fn set_gamma(&mut self, gamma: f32) {
+        self.settings.raster.gamma_bits = gamma.to_bits();
+        self.settings.update_coverage_table();
+    }
+
+    /// Sets the color of the background text is drawn over, if it's
+    /// always the same, e.g. a panel of a fixed color. The default is
+    /// None, for text drawn over any background.
+    ///
+    /// With a background hint, glyphs are rasterized as their color
+    /// blended against the background in linear space, which is how
+    /// light mixes, and stored fully opaque. Drawing them just
+    /// replaces the background, avoiding the dark or light fringes
+    /// that blending in sRGB space leaves around the edges of small
+    /// text. The gamma (see [`FontTexture::set_gamma`]) should
+    /// usually be left at 1.0 with a background hint, since it
+    /// compensates for the same thing.
+    ///
+    /// Only glyphs rasterized in their own color, i.e. with
+    /// [`ColorMode::Rasterized`], can be blended against the
+    /// background, so the hint is ignored with the other color modes.
+    /// The glyphs' whole bitmaps are opaque, so glyphs overlapping
+    /// each other, or anything else, cover it with the background.
+    /// Glyphs rasterized with different background hints get their
+    /// own cache entries.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{FontTexture, layout_draw_rects};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let panel = Color::RGB(0x20, 0x24, 0x30);
+    /// font_texture.set_background_hint(Some(panel));
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("H", 32.0, 0, Color::WHITE));
+    ///
+    /// let (_, glyph_rect) = layout_draw_rects(layout.glyphs())[0];
+    /// let mut draw_over = |background: Color| {
+    ///     canvas.set_draw_color(background);
+    ///     canvas.clear();
+    ///     font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///     canvas.read_pixels(glyph_rect, PixelFormatEnum::RGBA32).unwrap()
+    /// };
+    /// // The glyph's edges are already blended against the panel, so
+    /// // the glyph covers whatever it's drawn over.
+    /// assert_eq!(draw_over(panel), draw_over(Color::RED));
+    /// ```
+    pub fn set_background_hint(&mut self, background: Option<Color>) {
+        self.settings.raster.background = background.map(|color| color.rgb());
+    }
+
+    /// Sets a curve which remaps the coverage of glyphs when they're
+    /// rasterized, before the gamma (see [`FontTexture::set_gamma`])
+    /// is applied. The curve is called once for each of the 256
+    /// coverage values, and the results are stored in a table, so it
+    /// doesn't need to be fast. Passing a curve which doesn't change
+    /// any values (e.g. `|coverage| coverage`) removes the curve.
+    ///
+    /// Boosting low coverage values darkens the thin strokes of small
+    /// text, which can otherwise look faint, similar to the "stem
+    /// darkening" done by many native rasterizers:
+    ///
+    /// ```no_run
+    /// # let sdl_context = sdl2::init().unwrap();
+    /// # let video_subsystem = sdl_context.video().unwrap();
+    /// # let window = video_subsystem.window("", 800, 600).build().unwrap();
+    /// # let canvas = window.into_canvas().build().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = fontdue_sdl2::FontTexture::new(&texture_creator).unwrap();
+    /// font_texture.set_coverage_curve(|coverage| {
+    ///     let coverage = coverage as f32 / 255.0;
+    ///     (coverage.powf(0.7) * 255.0).round() as u8
+    /// });
+    /// ```
+    ///
+    /// The glyphs rasterized with a curve are cached separately from
+    /// the ones rasterized with other curves, and can be saved with
+    /// [`FontTexture::dump_cache`] and loaded back, as long as the
+    /// same curve is set again before loading them.
+    pub fn set_coverage_curve(&mut self, curve: impl Fn(u8) -> u8) {
+        self.settings.set_coverage_curve(curve);
+    }
+
+    /// Sets whether glyphs are rasterized without anti-aliasing: each
+    /// pixel with at least half coverage is fully opaque, and the
+    /// rest are fully transparent. The default is false. The gamma
+    /// (see [`FontTexture::set_gamma`]) doesn't affect monochrome
+    /// glyphs, but a coverage curve (see
+    /// [`FontTexture::set_coverage_curve`]) is applied before the
+    /// threshold.
+    ///
+    /// This is meant for pixel art styled UIs and bitmap-like fonts,
+    /// which stay crisp when scaled up with nearest neighbor filtering
+    /// (SDL's default), e.g. with
+    /// [`Canvas::set_scale`](sdl2::render::Canvas::set_scale).
+    pub fn set_monochrome(&mut self, monochrome: bool) {
+        self.settings.raster.monochrome = monochrome;
+        self.settings.update_coverage_table();
+    }
+
+    /// Sets the factor glyphs are supersampled by: each glyph is
+    /// rasterized at `factor` times its size, and then downsampled to
+    /// its actual size, averaging `factor * factor` pixels into each
+    /// pixel of the glyph. The default is 1, which rasterizes glyphs
+    /// at their actual size. The factor is clamped between 1 and 4,
+    /// since the glyphs hardly get any smoother past that, while the
+    /// rasterization keeps getting slower.
+    ///
+    /// Supersampling makes the curves and diagonals of large text,
+    /// e.g. headings, look smoother than fontdue's own anti-aliasing
+    /// at the same size, at the cost of rasterizing `factor * factor`
+    /// times the pixels. Glyphs drawn with [`ColorMode::Subpixel`]
+    /// aren't supersampled.
+    pub fn set_supersampling(&mut self, factor: u8) {
+        self.settings.raster.supersample = factor.clamp(1, 4);
+    }
+
+    /// Sets the scale glyphs are rasterized at, relative to the font
+    /// size they're laid out with. The default is 1.0.
+    ///
+    /// On HiDPI displays, the canvas' output size is bigger than the
+    /// window size, and drawing with [`Canvas::set_scale`] set to the
+    /// ratio between the two keeps everything at the same logical
+    /// size. Setting the DPI scale to the same ratio makes the glyphs
+    /// get rasterized at the full resolution of the display, while
+    /// the text is still laid out in logical units:
+    ///
+    /// ```no_run
+    /// # let sdl_context = sdl2::init().unwrap();
+    /// # let video_subsystem = sdl_context.video().unwrap();
+    /// # let window = video_subsystem.window("", 800, 600).allow_highdpi().build().unwrap();
+    /// # let mut canvas = window.into_canvas().build().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = fontdue_sdl2::FontTexture::new(&texture_creator).unwrap();
+    /// let (output_width, _) = canvas.output_size().unwrap();
+    /// let (window_width, _) = canvas.window().size();
+    /// let scale = output_width as f32 / window_width as f32;
+    /// canvas.set_scale(scale, scale).unwrap();
+    /// font_texture.set_dpi_scale(scale);
+    /// ```
+    ///
+    /// This can be changed at any time, glyphs rasterized at
+    /// different scales get their own cache entries.
+    pub fn set_dpi_scale(&mut self, scale: f32) {
+        self.settings.dpi_scale = scale;
+    }
+
+    /// Makes glyphs get rasterized at the given size (in px),
+    /// regardless of the size they're laid out with, and then scaled
+    /// to their laid out size when drawn. Pass None to go back to
+    /// rasterizing glyphs at their own size, which is the default.
+    ///
+    /// This is useful for zooming text smoothly, e.g. in a map view:
+    /// normally each font size gets its own glyphs in the glyph
+    /// cache, which floods the cache when the size changes every
+    /// frame. With a fixed raster size, each glyph is only rasterized
+    /// once, at the cost of crispness at sizes far from the raster
+    /// size. Setting a size also switches the texture to linear
+    /// filtering, so that the scaled glyphs look smooth.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::TextureCreation`] if the
+    /// texture's filtering can't be changed.
+    pub fn set_fixed_raster_size(&mut self, px: Option<f32>) -> Result<(), FontTextureError> {
+        if px.is_some() {
+            let linear = sdl2::sys::SDL_ScaleMode::SDL_ScaleModeLinear;
+            crate::set_scale_mode(&mut self.texture, linear)?;
+        }
+        self.settings.fixed_raster_px = px;
+        Ok(())
+    }
+
+    /// Sets the minimum width and height of the empty areas tracked
+    /// by the glyph cache's allocator, see
+    /// [`RectPacker::set_min_split_size`](crate::RectPacker::set_min_split_size).
+    /// The default is 0, which keeps every empty area.
+    ///
+    /// A value somewhat smaller than the smallest glyphs drawn (e.g.
+    /// 4) speeds up caching new glyphs when the cache holds a lot of
+    /// them, at the cost of some space in the cache.
+    pub fn set_min_split_size(&mut self, min_split_size: u32) {
+        self.glyph_cache
+            .rect_allocator
+            .packer
+            .set_min_split_size(min_split_size);
+    }
+
+    /// Sets the color drawn instead of `Color::RGBA(0, 0, 0, 0)`, the
+    /// "zero" color, in the user data of glyphs (or returned by the
+    /// `color_of` of e.g. [`FontTexture::draw_text_with_user_data`]).
+    /// The default is None, which draws the zero color as is, i.e.
+    /// not at all.
+    ///
+    /// The zero color is the one replaced, because it's the only
+    /// color that never draws anything, with any blend mode, so it
+    /// can't be the color of any visible text. That makes it free to
+    /// use as a placeholder, for laying out text without picking a
+    /// color for every [`TextStyle`](fontdue::layout::TextStyle).
+    /// Note that text faded out by lowering the alpha of its black
+    /// color all the way to 0 is then drawn in the default color
+    /// instead.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let unset = Color::RGBA(0, 0, 0, 0);
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 32.0, 0, unset));
+    ///
+    /// font_texture.set_default_color(Some(Color::WHITE));
+    /// canvas.set_draw_color(Color::BLACK);
+    /// canvas.clear();
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+    /// assert!(pixels.chunks_exact(4).any(|pixel| pixel == [0xFF; 4]));
+    /// ```
+    ///
+    /// Layouts made with [`TextStyle::new`](fontdue::layout::TextStyle::new)
+    /// have `()` user data instead of colors, so this doesn't apply to
+    /// them. They can be drawn in a single color with
+    /// [`FontTexture::draw_text_with_user_data`]:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::new("Hello", 32.0, 0));
+    /// font_texture
+    ///     .draw_text_with_user_data(&mut canvas, fonts, layout.glyphs(), |_| Color::WHITE)
+    ///     .unwrap();
+    /// ```
+    pub fn set_default_color(&mut self, color: Option<Color>) {
+        self.settings.default_color = color;
+    }
+
+    /// Sets whether the [`Metrics`] of glyphs are kept when they're
+    /// rasterized into the glyph cache, so that they can be looked up
+    /// with [`FontTexture::glyph_metrics`]. The default is false.
+    /// Only glyphs rasterized while this is enabled have their
+    /// metrics kept, not e.g. ones added with
+    /// [`FontTexture::insert_rasterized`].
+    pub fn set_retain_metrics(&mut self, retain_metrics: bool) {
+        self.settings.retain_metrics = retain_metrics;
+    }
+
+    /// Sets how the glyph cache texture is filtered when glyphs are
+    /// drawn scaled (e.g. with
+    /// [`Canvas::set_scale`](sdl2::render::Canvas::set_scale)) or at
+    /// fractional positions. See [`ScaleMode`] for the options. The
+    /// default is decided by SDL's `SDL_RENDER_SCALE_QUALITY` hint at
+    /// the time the [`FontTexture`] is created, which is
+    /// [`ScaleMode::Nearest`] unless the hint is set.
+    ///
+    /// Pixel art styled text (see [`FontTexture::set_monochrome`])
+    /// stays crisp with [`ScaleMode::Nearest`], while zooming text
+    /// smoothly looks best with [`ScaleMode::Linear`]. With linear
+    /// filtering, the glyphs' padding (see
+    /// [`FontTextureBuilder::padding`]) keeps neighboring glyphs in
+    /// the glyph cache from bleeding in.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::TextureCreation`] if the
+    /// texture's filtering can't be changed.
+    pub fn set_scale_mode(&mut self, scale_mode: ScaleMode) -> Result<(), FontTextureError> {
+        crate::set_scale_mode(&mut self.texture, scale_mode.to_sdl())
+    }
+
+    /// Sets the most glyphs rasterized in a single draw. The default
+    /// is None, which rasterizes every glyph that isn't cached yet.
+    ///
+    /// Rasterizing is the slow part of drawing new glyphs, so e.g. a
+    /// big paragraph of text appearing all at once can take long
+    /// enough to drop frames on slow hardware. With a budget, the
+    /// glyphs beyond it are deferred: they're left out of the draw,
+    /// and rasterized in later draws as the budget allows, so the
+    /// text fills in over a few frames instead. Glyphs that are
+    /// already cached are always drawn.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("abcdefghijkl", 16.0, 0, Color::WHITE));
+    /// font_texture.set_raster_budget(Some(5));
+    ///
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!((report.drawn_glyphs, report.deferred_glyphs), (5, 7));
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!((report.drawn_glyphs, report.deferred_glyphs), (10, 2));
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!((report.drawn_glyphs, report.deferred_glyphs), (12, 0));
+    /// ```
+    ///
+    /// The budget doesn't apply to [`FontTexture::cache_glyphs`], so
+    /// glyphs can still be preloaded all at once, e.g. behind a
+    /// loading screen.
+    pub fn set_raster_budget(&mut self, budget: Option<usize>) {
+        self.settings.raster_budget = budget;
+    }
+
+    /// Sets whether the glyphs of a draw are drawn all at once, as
+    /// triangles with a single `SDL_RenderGeometry` call, instead of
+    /// copying each glyph separately. The default is false.
+    ///
+    /// Every copy is a separate draw call for the renderer, so
+    /// batching makes drawing long texts, e.g. paragraphs with
+    /// hundreds of glyphs, considerably faster, with identical
+    /// output. `SDL_RenderGeometry` needs SDL 2.0.18 or newer, and if
+    /// the renderer can't draw geometry, the glyphs are copied
+    /// separately instead. The amount of calls made is reported in
+    /// [`DrawReport::draw_calls`].
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("abcdefghijkl", 16.0, 0, Color::WHITE));
+    ///
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!(report.draw_calls, 12);
+    /// font_texture.set_batched(true);
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!(report.draw_calls, 1);
+    /// ```
+    pub fn set_batched(&mut self, batched: bool) {
+        self.settings.batched = batched;
+    }
+
+    /// Sets a handler for the problems encountered while drawing,
+    /// e.g. glyphs that don't fit in the glyph cache, instead of
+    /// logging them. See [`Warning`] for the problems. The handler
+    /// belongs to the glyph cache, so it's shared by all the
+    /// [`SharedFontTexture`] handles of the [`FontTexture`].
+    ///
+    /// This is useful for routing the warnings through the
+    /// application's own diagnostics, or for checking for them in
+    /// tests:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{FontTexture, Warning};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 64, 64).unwrap();
+    /// let warnings = Rc::new(RefCell::new(Vec::new()));
+    /// let handler_warnings = warnings.clone();
+    /// font_texture.set_warning_handler(move |warning| handler_warnings.borrow_mut().push(warning));
+    ///
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("ABCDEFGH", 32.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert!(warnings
+    ///     .borrow()
+    ///     .iter()
+    ///     .any(|warning| matches!(warning, Warning::OutOfSpace { .. })));
+    /// ```
+    pub fn set_warning_handler(&mut self, handler: impl FnMut(Warning) + 'static) {
+        self.glyph_cache.warning_handler = Some(Box::new(handler));
+    }
+
+    /// Sets how the positions of glyphs are rounded to whole pixels
+    /// before drawing. The default is [`Rounding::Round`], which
+    /// rounds both the horizontal and vertical position to the
+    /// nearest pixel, centering glyphs best, and placing glyphs at
+    /// mirrored positive and negative positions symmetrically. None
+    /// draws glyphs at their exact positions, including the
+    /// fractional part, see [`FontTexture::draw_text`].
+    ///
+    /// Rounding keeps glyphs at fractional positions crisp with
+    /// [`ScaleMode::Linear`], at the cost of text moving in whole
+    /// pixel steps.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("l", 32.0, 0, Color::WHITE));
+    /// let mut draw_at = |x: f32| {
+    ///     let mut glyph = layout.glyphs()[0];
+    ///     glyph.x = x;
+    ///     canvas.set_draw_color(Color::BLACK);
+    ///     canvas.clear();
+    ///     font_texture.draw_text(&mut canvas, fonts, &[glyph]).unwrap();
+    ///     canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap()
+    /// };
+    /// assert_eq!(draw_at(10.4), draw_at(10.0));
+    /// assert_eq!(draw_at(10.6), draw_at(11.0));
+    /// assert_ne!(draw_at(10.0), draw_at(11.0));
+    /// // Mirrored positions are rounded the same way on both sides of 0.
+    /// assert_eq!(draw_at(-2.4), draw_at(-2.0));
+    /// assert_eq!(draw_at(-2.6), draw_at(-3.0));
+    /// assert_ne!(draw_at(-2.0), draw_at(-3.0));
+    /// ```
+    pub fn set_rounding(&mut self, rounding: Option<Rounding>) {
+        self.settings.rounding = rounding;
+    }
+
+    /// Sets whether draws are kept pending until
+    /// [`FontTexture::flush_uploads`], instead of uploading the newly
+    /// rasterized glyphs to the glyph cache texture and drawing them
+    /// right away. The default is false.
+    ///
+    /// With deferred uploads, the drawing functions only rasterize
+    /// the glyphs and reserve space for them in the glyph cache, so
+    /// the texture is updated at most once per flush, at a point in
+    /// the frame of your choosing. The draws are made by the flush,
+    /// in the order they were made in, and the result is the same as
+    /// without deferred uploads:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 24.0, 0, Color::WHITE));
+    /// let hello = layout.glyphs().clone();
+    /// layout.clear();
+    /// layout.append(fonts, &TextStyle::with_user_data("World", 24.0, 0, Color::YELLOW));
+    /// let world = layout.glyphs().clone();
+    ///
+    /// let mut render = |deferred: bool| {
+    ///     let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    ///     let mut canvas = surface.into_canvas().unwrap();
+    ///     let texture_creator = canvas.texture_creator();
+    ///     let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    ///     font_texture.set_deferred_uploads(deferred);
+    ///     font_texture.draw_text(&mut canvas, fonts, &hello).unwrap();
+    ///     font_texture.draw_text_clipped(&mut canvas, fonts, &world, Rect::new(0, 0, 40, 64)).unwrap();
+    ///     font_texture.flush_uploads(&mut canvas).unwrap();
+    ///     canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap()
+    /// };
+    /// assert_eq!(render(true), render(false));
+    /// ```
+    ///
+    /// Since nothing is drawn until the flush, the canvas' draw
+    /// state, e.g. the render target, should be the same at the
+    /// flush as it was at the draws. If the glyph cache runs out of
+    /// space for a new draw, the pending draws are flushed onto its
+    /// canvas first, so that the glyphs they use aren't evicted.
+    /// [`DrawReport::draw_calls`] is 0 for pending draws.
+    ///
+    /// The functions that draw into textures
+    /// ([`FontTexture::render_to_texture`],
+    /// [`FontTexture::draw_text_merged`], and
+    /// [`FontTexture::draw_text_appended`]) aren't deferred, since
+    /// the texture should have the text in it when they return. They
+    /// flush the pending draws onto the canvas first, like a draw
+    /// that runs out of space.
+    ///
+    /// Glyphs evicted while there are pending draws, e.g. by
+    /// [`FontTexture::evict_font`], [`FontTexture::prune_unused`],
+    /// or [`FontTexture::set_glyph_override`], keep their space in
+    /// the glyph cache until the flush, so the pending draws still
+    /// find them in the texture:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 24.0, 0, Color::WHITE));
+    /// let hello = layout.glyphs().clone();
+    /// layout.clear();
+    /// layout.append(fonts, &TextStyle::with_user_data("\nWorld", 24.0, 0, Color::YELLOW));
+    /// let world = layout.glyphs().clone();
+    ///
+    /// let mut render = |deferred: bool| {
+    ///     let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    ///     let mut canvas = surface.into_canvas().unwrap();
+    ///     let texture_creator = canvas.texture_creator();
+    ///     let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    ///     font_texture.set_deferred_uploads(deferred);
+    ///     font_texture.draw_text(&mut canvas, fonts, &hello).unwrap();
+    ///     font_texture.evict_font(&fonts[0]);
+    ///     font_texture.draw_text(&mut canvas, fonts, &world).unwrap();
+    ///     font_texture.flush_uploads(&mut canvas).unwrap();
+    ///     canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap()
+    /// };
+    /// assert_eq!(render(true), render(false));
+    /// ```
+    ///
+    /// Pending draws have to be flushed before
+    /// [`FontTexture::defragment`] and [`FontTexture::resize`], which
+    /// move all the glyphs, and fail if there are pending draws.
+    pub fn set_deferred_uploads(&mut self, deferred: bool) {
+        self.settings.deferred_uploads = deferred;
+    }
+
+    /// Draws the bitmap returned by `rasterize` in place of the
+    /// character's glyph from the font, when the character is laid
+    /// out at the size `px`. For e.g. icons inline with text, at
+    /// codepoints from the Unicode private use area.
+    ///
+    /// `rasterize` returns the bitmap's metrics and coverage, like
+    /// [`Font::rasterize`]: one byte per pixel, in rows of
+    /// `metrics.width` bytes. Only the `xmin`, `ymin`, `width` and
+    /// `height` of the metrics are used, to place the bitmap relative
+    /// to the glyph's pen position and baseline. The bitmap is cached
+    /// and colored like a regular glyph, and `rasterize` is only
+    /// called when the bitmap is first needed.
+    ///
+    /// ```
+    /// # use fontdue::{Font, Metrics, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// // A filled 12x12 square, sitting on the baseline.
+    /// font_texture.set_glyph_override('\u{E000}', 24.0, || {
+    ///     let metrics = Metrics { xmin: 1, ymin: 0, width: 12, height: 12, ..Metrics::default() };
+    ///     (metrics, vec![0xFF; 12 * 12])
+    /// });
+    ///
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("a\u{E000}b", 24.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// # canvas.set_draw_color(Color::RGBA(0, 0, 0, 0));
+    /// # canvas.clear();
+    /// # layout.clear();
+    /// # layout.append(fonts, &TextStyle::with_user_data("\u{E000}", 24.0, 0, Color::WHITE));
+    /// # font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// # let pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+    /// # assert_eq!(pixels.chunks_exact(4).filter(|p| p == &[0xFF; 4]).count(), 12 * 12);
+    /// ```
+    ///
+    /// The glyph still takes up the space of the font's glyph for the
+    /// character in the layout, e.g. the font's replacement glyph if
+    /// it doesn't have the character, so the bitmap should be about
+    /// as wide as that. Glyph overrides aren't saved by
+    /// [`FontTexture::dump_cache`]. Setting a new override for the
+    /// character evicts the glyphs drawn with the previous one.
+    ///
+    /// If the coverage isn't `metrics.width * metrics.height` bytes
+    /// long, a warning is logged and the glyph is skipped, without
+    /// touching the glyphs already in the cache:
+    ///
+    /// ```
+    /// # use fontdue::{Font, Metrics, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::with_size(&texture_creator, 64, 64).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let square = Metrics { width: 12, height: 12, ..Metrics::default() };
+    /// font_texture.set_glyph_override('\u{E000}', 24.0, move || (square, vec![0xFF; 12 * 12]));
+    /// // Claims to be 12x12, but only has coverage for 10 pixels.
+    /// font_texture.set_glyph_override('\u{E001}', 24.0, move || (square, vec![0xFF; 10]));
+    ///
+    /// let mut draw = |font_texture: &mut FontTexture, text: &str| {
+    ///     let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    ///     layout.append(fonts, &TextStyle::with_user_data(text, 24.0, 0, Color::WHITE));
+    ///     canvas.set_draw_color(Color::BLACK);
+    ///     canvas.clear();
+    ///     font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///     let pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+    ///     pixels.chunks_exact(4).filter(|p| p[0] > 0).count()
+    /// };
+    /// assert_eq!(draw(&mut font_texture, "\u{E000}"), 12 * 12);
+    /// assert_eq!(draw(&mut font_texture, "\u{E001}"), 0);
+    /// assert_eq!(font_texture.cache_stats().glyph_count, 1);
+    /// assert_eq!(draw(&mut font_texture, "\u{E000}"), 12 * 12);
+    /// ```
+    pub fn set_glyph_override(
+        &mut self,
+        character: char,
+        px: f32,
+        rasterize: impl Fn() -> (Metrics, Vec<u8>) + 'static,
+    ) {
+        crate::set_glyph_override(&mut self.glyph_cache, character, px, Box::new(rasterize));
+    }
+
+    /// Sets how glyphs that don't fit in the glyph cache are drawn.
+    /// See [`MissingGlyphStyle`] for the options. The default is
+    /// [`MissingGlyphStyle::Outline`].
+    ///
+    /// The rectangles are drawn in the glyphs' colors, blended with
+    /// [`BlendMode::Blend`] regardless of the canvas' blend mode, so
+    /// translucent text gets translucent rectangles. The canvas' draw
+    /// color and blend mode are restored afterwards, and they aren't
+    /// touched at all if no glyphs are missing:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{FontTexture, MissingGlyphStyle};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::render::BlendMode;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// // Too small for all of the glyphs.
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 64, 64).unwrap();
+    /// font_texture.set_missing_glyph_style(MissingGlyphStyle::Filled);
+    ///
+    /// let half_white = Color::RGBA(0xFF, 0xFF, 0xFF, 0x80);
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("ABCDEFGH", 32.0, 0, half_white));
+    /// canvas.set_draw_color(Color::BLACK);
+    /// canvas.clear();
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert!(!report.missing_glyphs.is_empty());
+    ///
+    /// // The filled rectangles are gray, not white.
+    /// let pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+    /// assert!(pixels.chunks_exact(4).all(|pixel| pixel[0] <= 0x80));
+    /// assert!(pixels.chunks_exact(4).any(|pixel| pixel[0] == 0x80));
+    ///
+    /// let draw_color = Color::RGBA(0x12, 0x34, 0x56, 0x78);
+    /// canvas.set_draw_color(draw_color);
+    /// canvas.set_blend_mode(BlendMode::Add);
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert!(!report.missing_glyphs.is_empty());
+    /// assert_eq!(canvas.draw_color(), draw_color);
+    /// assert_eq!(canvas.blend_mode(), BlendMode::Add);
+    /// ```
+    pub fn set_missing_glyph_style(&mut self, style: MissingGlyphStyle) {
+        self.settings.missing_glyph_style = style;
+    }
+
+    /// Sets the blend mode used when drawing glyphs onto the canvas.
+    /// The default is [`BlendMode::Blend`]. For example,
+    /// [`BlendMode::Add`] makes overlapping text accumulate into
+    /// brighter colors, for glowing text effects.
+    ///
+    /// This replaces the blend mode set by
+    /// [`FontTexture::set_premultiplied_alpha`], but doesn't turn off
+    /// the premultiplication of the glyphs' colors.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.texture.set_blend_mode(blend_mode);
+    }
+
     /// Renders text to the given canvas, using the given fonts and
     /// glyphs.
     ///
@@ -50,28 +1008,2815 @@ impl FontTexture<'_> {
     /// [`Layout::append`](fontdue::layout::Layout::append).
     ///
     /// The glyphs should be from
-    /// [`Layout::glyphs`](fontdue::layout::Layout::glyphs).
+    /// [`Layout::glyphs`](fontdue::layout::Layout::glyphs). The
+    /// alpha of each glyph's [`Color`] is used as an opacity
+    /// multiplier, so e.g. `Color::RGBA(0xFF, 0, 0, 0x80)` draws
+    /// half-transparent red text. Since the alpha is applied when
+    /// drawing, fading text in or out doesn't rasterize the glyphs
+    /// again.
+    ///
+    /// The glyphs can be any iterator of glyphs or references to
+    /// them, not just a slice like
+    /// [`Layout::glyphs`](fontdue::layout::Layout::glyphs), so they
+    /// can be filtered or transformed on the way without collecting
+    /// them into a [`Vec`] first:
+    ///
+    /// ```no_run
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use sdl2::pixels::Color;
+    /// # let sdl_context = sdl2::init().unwrap();
+    /// # let video_subsystem = sdl_context.video().unwrap();
+    /// # let window = video_subsystem.window("", 800, 600).build().unwrap();
+    /// # let mut canvas = window.into_canvas().build().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// # let mut font_texture = fontdue_sdl2::FontTexture::new(&texture_creator).unwrap();
+    /// # let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// # layout.append(fonts, &TextStyle::with_user_data("Hello, World!", 32.0, 0, Color::WHITE));
+    /// // Skip the punctuation.
+    /// let glyphs = layout.glyphs().iter().filter(|glyph| glyph.parent.is_alphanumeric());
+    /// font_texture.draw_text(&mut canvas, fonts, glyphs).unwrap();
+    /// ```
+    ///
+    /// Glyph positions are rounded to the nearest whole pixel by
+    /// default. With rounding turned off (see
+    /// [`FontTexture::set_rounding`]), glyphs are drawn at their exact
+    /// positions, including the fractional part (e.g. from a
+    /// fractional
+    /// [`LayoutSettings::x`](fontdue::layout::LayoutSettings::x)). For
+    /// text moving by less than a pixel at a time to move smoothly
+    /// instead of snapping to whole pixels, the texture should then
+    /// be sampled with linear filtering, e.g. by setting the
+    /// `SDL_RENDER_SCALE_QUALITY` hint to `"linear"` before creating
+    /// the [`FontTexture`]:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{FontTexture, ScaleMode};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// font_texture.set_scale_mode(ScaleMode::Linear).unwrap();
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("l", 32.0, 0, Color::WHITE));
+    /// let mut draw_at = |font_texture: &mut FontTexture, x: f32| {
+    ///     let mut glyph = layout.glyphs()[0];
+    ///     glyph.x = x;
+    ///     canvas.set_draw_color(Color::BLACK);
+    ///     canvas.clear();
+    ///     font_texture.draw_text(&mut canvas, fonts, &[glyph]).unwrap();
+    ///     canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap()
+    /// };
+    /// // Rounded to whole pixels by default:
+    /// assert_eq!(draw_at(&mut font_texture, 10.25), draw_at(&mut font_texture, 10.0));
+    /// assert_eq!(draw_at(&mut font_texture, 10.75), draw_at(&mut font_texture, 11.0));
+    ///
+    /// // Passed on to the renderer as is, which is where they're
+    /// // blended between pixels, unless it's a software renderer
+    /// // like this one, which snaps them to whole pixels itself.
+    /// font_texture.set_rounding(None);
+    /// assert_ne!(draw_at(&mut font_texture, 10.75), draw_at(&mut font_texture, 11.0));
+    /// ```
+    ///
+    /// Glyphs at whole pixel positions, which is where fontdue's
+    /// layout puts them, are copied onto the canvas pixel for pixel,
+    /// regardless of their size or where they ended up in the glyph
+    /// cache. E.g. white text drawn on black has exactly the coverage
+    /// rasterized by fontdue:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(512, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// for px in [9.0, 13.0, 17.0, 20.0] {
+    ///     let style = TextStyle::with_user_data("i l 1 | . W ", px, 0, Color::WHITE);
+    ///     layout.append(fonts, &style);
+    /// }
+    /// canvas.set_draw_color(Color::BLACK);
+    /// canvas.clear();
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///
+    /// for glyph in layout.glyphs().iter().filter(|glyph| glyph.width > 0) {
+    ///     let (metrics, coverage) = fonts[0].rasterize_config(glyph.key);
+    ///     let (width, height) = (metrics.width as u32, metrics.height as u32);
+    ///     let rect = Rect::new(glyph.x as i32, glyph.y as i32, width, height);
+    ///     let pixels = canvas.read_pixels(rect, PixelFormatEnum::RGBA32).unwrap();
+    ///     for (pixel, coverage) in pixels.chunks_exact(4).zip(coverage) {
+    ///         // Blending can round the last bit either way.
+    ///         assert!((pixel[0] as i32 - coverage as i32).abs() <= 1);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Glyphs are rasterized into the glyph cache the first time
+    /// they're drawn. When it's full, the glyphs drawn least recently
+    /// are evicted to make space, all the glyphs of the oldest draw
+    /// at a time, and they'll be rasterized again if they're drawn
+    /// later. Glyphs used by the current draw are never evicted, and
+    /// only the glyphs that don't fit even after evicting everything
+    /// else are drawn as missing glyphs (see
+    /// [`FontTexture::set_missing_glyph_style`]).
+    ///
+    /// The memory used during a draw is kept for the next one, so
+    /// drawing text that's already in the glyph cache doesn't
+    /// allocate at all:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # use std::alloc::{GlobalAlloc, Layout as AllocLayout, System};
+    /// # use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    /// # struct CountingAllocator;
+    /// # static COUNTING: AtomicBool = AtomicBool::new(false);
+    /// # static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    /// # unsafe impl GlobalAlloc for CountingAllocator {
+    /// #     unsafe fn alloc(&self, layout: AllocLayout) -> *mut u8 {
+    /// #         if COUNTING.load(Ordering::SeqCst) {
+    /// #             ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+    /// #         }
+    /// #         System.alloc(layout)
+    /// #     }
+    /// #     unsafe fn realloc(&self, ptr: *mut u8, layout: AllocLayout, size: usize) -> *mut u8 {
+    /// #         if COUNTING.load(Ordering::SeqCst) {
+    /// #             ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+    /// #         }
+    /// #         System.realloc(ptr, layout, size)
+    /// #     }
+    /// #     unsafe fn dealloc(&self, ptr: *mut u8, layout: AllocLayout) {
+    /// #         System.dealloc(ptr, layout)
+    /// #     }
+    /// # }
+    /// # #[global_allocator]
+    /// # static ALLOCATOR: CountingAllocator = CountingAllocator;
+    /// # fn main() {
+    /// # let surface = Surface::new(512, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello, World!", 32.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///
+    /// // Count the allocations made by the next draws.
+    /// COUNTING.store(true, Ordering::SeqCst);
+    /// for _ in 0..10 {
+    ///     font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// }
+    /// COUNTING.store(false, Ordering::SeqCst);
+    /// assert_eq!(ALLOCATIONS.load(Ordering::SeqCst), 0);
+    ///
+    /// // The same goes for batched draws.
+    /// font_texture.set_batched(true);
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!(font_texture.last_frame_stats().draw_calls, 1);
+    /// COUNTING.store(true, Ordering::SeqCst);
+    /// for _ in 0..10 {
+    ///     font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// }
+    /// COUNTING.store(false, Ordering::SeqCst);
+    /// assert_eq!(ALLOCATIONS.load(Ordering::SeqCst), 0);
+    /// # }
+    /// ```
+    ///
+    /// Glyph positions can be negative or beyond the canvas' size,
+    /// e.g. for text scrolled partially off the edge of the canvas:
+    /// the glyphs on the edge are cut off by SDL, and the ones
+    /// entirely outside the canvas' viewport are skipped, so they
+    /// don't take up space in the glyph cache.
+    ///
+    /// Glyphs which are too large to ever fit in the glyph cache
+    /// texture (e.g. a 1500px glyph with the default 1024x1024
+    /// texture) are drawn through a temporary texture created just
+    /// for that draw. They aren't cached, so they're rasterized again
+    /// on every draw, which is slow: for big display text drawn every
+    /// frame, consider [`FontTexture::render_to_texture`] or a bigger
+    /// glyph cache (see [`FontTexture::with_size`]). With
+    /// [`ColorMode::Subpixel`], such glyphs are drawn without
+    /// subpixel rendering. They're also drawn after the rest of the
+    /// glyphs of the draw, so they end up on top of any glyphs they
+    /// overlap, regardless of the order of the glyphs.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(128, 128, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// // A 100px "H" doesn't fit in a 64x64 glyph cache.
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 64, 64).unwrap();
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("H", 100.0, 0, Color::WHITE));
+    /// canvas.set_draw_color(Color::BLACK);
+    /// canvas.clear();
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!(report.drawn_glyphs, 1);
+    /// assert!(report.missing_glyphs.is_empty());
+    /// assert_eq!(font_texture.cache_stats().glyph_count, 0);
+    ///
+    /// // The glyph is drawn exactly as rasterized by fontdue.
+    /// let glyph = layout.glyphs()[0];
+    /// let (metrics, coverage) = fonts[0].rasterize_config(glyph.key);
+    /// let (width, height) = (metrics.width as u32, metrics.height as u32);
+    /// let rect = Rect::new(glyph.x as i32, glyph.y as i32, width, height);
+    /// let pixels = canvas.read_pixels(rect, PixelFormatEnum::RGBA32).unwrap();
+    /// for (pixel, coverage) in pixels.chunks_exact(4).zip(coverage) {
+    ///     assert!((pixel[0] as i32 - coverage as i32).abs() <= 1);
+    /// }
+    /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the Texture cannot be
-    /// written to, or a copy from the texture to the canvas
-    /// fails. This should only really happen under very exceptional
-    /// circumstances, so text rendering is interrupted by these
-    /// errors. The Err(String) will contain an informational string
-    /// from SDL.
+    /// This function will return a
+    /// [`FontTextureError::TextureUpdate`] if the Texture cannot be
+    /// written to, or a [`FontTextureError::Copy`] if a copy from
+    /// the texture to the canvas fails. This should only really
+    /// happen under very exceptional circumstances, so text rendering
+    /// is interrupted by these errors. The errors will contain an
+    /// informational string from SDL. A
+    /// [`FontTextureError::TextureCreation`] is returned if the
+    /// temporary texture for a glyph too large for the glyph cache
+    /// can't be created. If fonts have been bound with
+    /// [`FontTexture::bind_fonts`], a [`FontTextureError::FontMismatch`]
+    /// is returned for other fonts, before anything is drawn.
     pub fn draw_text<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: impl IntoIterator<Item = impl Borrow<GlyphPosition<Color>>>,
+    ) -> Result<(), FontTextureError> {
+        let mut glyph_buffer = mem::take(&mut self.glyph_cache.glyph_buffer);
+        glyph_buffer.clear();
+        glyph_buffer.extend(glyphs.into_iter().map(|glyph| *glyph.borrow()));
+        let result = crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            &glyph_buffer,
+            |color: &Color| *color,
+            DrawOptions::default(),
+        );
+        self.glyph_cache.glyph_buffer = glyph_buffer;
+        result.map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but with any
+    /// kind of user data in the glyphs, not just [`Color`]. The color
+    /// of each glyph is read from its user data with `color_of`.
+    ///
+    /// This allows storing other information about the text, e.g.
+    /// link targets or hover states, in the user data of the
+    /// [`Layout`](fontdue::layout::Layout):
+    ///
+    /// ```no_run
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::Color;
+    /// # let sdl_context = sdl2::init().unwrap();
+    /// # let video_subsystem = sdl_context.video().unwrap();
+    /// # let window = video_subsystem.window("fontdue-sdl2 example", 800, 600).build().unwrap();
+    /// # let mut canvas = window.into_canvas().build().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// #[derive(Clone, Copy)]
+    /// struct RunData {
+    ///     color: Color,
+    ///     link_id: Option<u32>,
+    /// }
+    ///
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// let link = RunData { color: Color::RGB(0x66, 0xAA, 0xFF), link_id: Some(1) };
+    /// layout.append(fonts, &TextStyle::with_user_data("Click me", 32.0, 0, link));
+    /// let _ = font_texture.draw_text_with_user_data(&mut canvas, fonts, layout.glyphs(), |d| d.color);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_with_user_data<RT: RenderTarget, U: Copy>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<U>],
+        color_of: impl Fn(&U) -> Color,
+    ) -> Result<(), FontTextureError> {
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            color_of,
+            DrawOptions::default(),
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders several glyph slices (e.g. the layouts of many small
+    /// UI labels) like [`FontTexture::draw_text`], but in a single
+    /// draw: new glyphs are uploaded to the texture once, and the
+    /// texture's color and alpha mods are only changed when the color
+    /// actually changes between glyphs, instead of being set and
+    /// reset for every slice.
+    ///
+    /// The slices are drawn in order, so later ones are drawn over
+    /// earlier ones where they overlap.
+    ///
+    /// With [`FontTexture::set_batched`], all the slices are drawn
+    /// with as few calls to the renderer as a single slice:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, LayoutSettings, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 256, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let labels = (0..20)
+    ///     .map(|i| {
+    ///         let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    ///         let (x, y) = ((i % 4) as f32 * 64.0, (i / 4) as f32 * 40.0);
+    ///         layout.reset(&LayoutSettings { x, y, ..LayoutSettings::default() });
+    ///         let text = format!("Label {}", i);
+    ///         layout.append(fonts, &TextStyle::with_user_data(&text, 14.0, 0, Color::WHITE));
+    ///         layout.glyphs().clone()
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// font_texture.set_batched(true);
+    ///
+    /// font_texture.begin_frame();
+    /// for label in &labels {
+    ///     font_texture.draw_text(&mut canvas, fonts, label).unwrap();
+    /// }
+    /// assert_eq!(font_texture.last_frame_stats().draw_calls, 20);
+    ///
+    /// let slices = labels.iter().map(|label| &label[..]).collect::<Vec<_>>();
+    /// font_texture.begin_frame();
+    /// font_texture.draw_texts(&mut canvas, fonts, &slices).unwrap();
+    /// assert_eq!(font_texture.last_frame_stats().draw_calls, 1);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_texts<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        texts: &[&[GlyphPosition<Color>]],
+    ) -> Result<(), FontTextureError> {
+        let glyphs = texts.iter().flat_map(|glyphs| glyphs.iter());
+        self.draw_text(canvas, fonts, glyphs)
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but also returns
+    /// a [`DrawReport`] describing what was drawn.
+    ///
+    /// Use this to find out whether any glyphs didn't fit in the
+    /// glyph cache, or whether glyphs had to be evicted from it to
+    /// make space, e.g. to switch to a [`FontTexture`] with a bigger
+    /// cache (see [`FontTexture::with_size`]):
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 64, 64).unwrap();
+    /// let mut draw = |text: &str| {
+    ///     let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    ///     layout.append(fonts, &TextStyle::with_user_data(text, 24.0, 0, Color::WHITE));
+    ///     font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap()
+    /// };
+    /// assert_eq!(draw("ABCD").evicted_glyphs, 0);
+    /// assert_eq!(draw("EFGH").evicted_glyphs, 0);
+    ///
+    /// // The cache is full, so the glyphs of the oldest draw make way.
+    /// let report = draw("IJKL");
+    /// assert_eq!(report.evicted_glyphs, 4);
+    /// assert!(report.missing_glyphs.is_empty());
+    ///
+    /// // Glyphs of the same draw are never evicted, so these don't all
+    /// // fit, and the ones that didn't are listed.
+    /// let text = "ABCDEFGHIJKLM";
+    /// let report = draw(text);
+    /// assert!(!report.missing_glyphs.is_empty());
+    /// assert!(report.missing_glyphs.iter().all(|&c| text.contains(c)));
+    /// assert_eq!(report.drawn_glyphs + report.missing_glyphs.len(), text.len());
+    /// ```
+    ///
+    /// Glyphs from malformed layouts, e.g. with a position that isn't
+    /// finite, or a size too large to rasterize, are logged and
+    /// skipped, and aren't counted as drawn:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("AB", 16.0, 0, Color::WHITE));
+    /// let mut glyphs = layout.glyphs().clone();
+    /// glyphs[0].width = 100_000;
+    /// glyphs[1].x = f32::NAN;
+    ///
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, &glyphs).unwrap();
+    /// assert_eq!(report.drawn_glyphs, 0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_checked<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<DrawReport, FontTextureError> {
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            DrawOptions::default(),
+        )
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but moved by
+    /// `offset` pixels (x, y) on the canvas.
+    ///
+    /// This allows drawing the same laid out glyphs in many places,
+    /// e.g. repeated labels, without laying them out again. The glyph
+    /// cache isn't affected by the offset.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{text_bounds, FontTexture};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 128, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Label", 24.0, 0, Color::WHITE));
+    /// let bounds = text_bounds(layout.glyphs()).unwrap();
+    /// canvas.set_draw_color(Color::BLACK);
+    /// canvas.clear();
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let label = canvas.read_pixels(bounds, PixelFormatEnum::RGBA32).unwrap();
+    ///
+    /// canvas.clear();
+    /// let offsets = [(10, 0), (100, 40), (180, 90)];
+    /// for offset in offsets {
+    ///     font_texture.draw_text_at(&mut canvas, fonts, layout.glyphs(), offset).unwrap();
+    /// }
+    /// for (x, y) in offsets {
+    ///     let mut moved = bounds;
+    ///     moved.offset(x, y);
+    ///     assert_eq!(canvas.read_pixels(moved, PixelFormatEnum::RGBA32).unwrap(), label);
+    /// }
+    /// assert_eq!(font_texture.cache_stats().glyph_count, 5);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_at<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        offset: (i32, i32),
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            offset,
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but only inside
+    /// the `clip` rectangle. Useful for text in scroll views, or
+    /// anything else that should cut off text at its edges.
+    ///
+    /// The canvas's clip rectangle is set to `clip` (or its
+    /// intersection with the canvas's current clip rectangle, if it
+    /// has one) for the duration of the draw, and restored
+    /// afterwards. Glyphs entirely outside of it are skipped.
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_clipped<RT: RenderTarget>(
         &mut self,
         canvas: &mut Canvas<RT>,
         fonts: &[Font],
         glyphs: &[GlyphPosition<Color>],
-    ) -> Result<(), String> {
+        clip: Rect,
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            clip: Some(clip),
+            ..DrawOptions::default()
+        };
         crate::draw_text(
             &mut self.texture,
-            &mut self.rect_allocator,
+            &mut self.glyph_cache,
+            &self.settings,
             canvas,
             fonts,
             glyphs,
+            |color: &Color| *color,
+            options,
         )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but with the
+    /// positions and sizes of the glyphs multiplied by `scale`. The
+    /// glyphs are rasterized at the scaled size, so they stay sharp.
+    ///
+    /// This is useful for text laid out in logical units, drawn on a
+    /// canvas in physical pixels, e.g. when the whole UI is scaled
+    /// by a user setting. If the canvas has a scale set with
+    /// [`Canvas::set_scale`], the glyphs are also rasterized at that
+    /// scale, instead of the one set with
+    /// [`FontTexture::set_dpi_scale`], so that they stay sharp when
+    /// the renderer scales them up. Text drawn on a 2x scaled canvas
+    /// looks like the same text drawn twice as big on an unscaled one:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 16.0, 0, Color::WHITE));
+    /// let render = |canvas_scale: f32, text_scale: f32| {
+    ///     let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    ///     let mut canvas = surface.into_canvas().unwrap();
+    ///     canvas.set_scale(canvas_scale, canvas_scale).unwrap();
+    ///     let texture_creator = canvas.texture_creator();
+    ///     let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    ///     font_texture.draw_text_scaled(&mut canvas, fonts, layout.glyphs(), text_scale).unwrap();
+    ///     canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap()
+    /// };
+    /// let unscaled = render(1.0, 2.0);
+    /// let scaled = render(2.0, 1.0);
+    /// // The coverage is about the same, the glyphs' sizes are just
+    /// // rounded to logical pixels on the scaled canvas...
+    /// let coverage = |pixels: &[u8]| pixels.chunks(4).map(|p| p[3] as f32).sum::<f32>();
+    /// assert!((coverage(&scaled) / coverage(&unscaled) - 1.0).abs() < 0.15);
+    /// // ...and not in blocks of 2x2 pixels, like glyphs rasterized at
+    /// // the logical size and scaled up would be.
+    /// let alpha = |x: usize, y: usize| scaled[(y * 128 + x) * 4 + 3];
+    /// let blocky = (0..32).all(|y| (0..64).all(|x| {
+    ///     let (x, y) = (x * 2, y * 2);
+    ///     [alpha(x + 1, y), alpha(x, y + 1), alpha(x + 1, y + 1)] == [alpha(x, y); 3]
+    /// }));
+    /// assert!(!blocky);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_scaled<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        scale: f32,
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            scale: Some(scale),
+            ..DrawOptions::default()
+        };
+        let settings = crate::canvas_scale_settings(&self.settings, canvas);
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but scaled and
+    /// moved so that the bounds of the glyphs (see
+    /// [`text_bounds`](crate::text_bounds)) fit into `target`, which
+    /// is a rectangle on the canvas. The text is centered in `target`,
+    /// and scaled as described by `mode`:
+    ///
+    /// - [`FitMode::Contain`] fits the whole text inside `target`.
+    /// - [`FitMode::Cover`] covers all of `target`, clipping off the
+    ///   parts of the text that don't fit.
+    /// - [`FitMode::Stretch`] fills `target` exactly, with the text's
+    ///   aspect ratio changed.
+    ///
+    /// The glyphs are rasterized at the scaled size like with
+    /// [`FontTexture::draw_text_scaled`], so they stay sharp. With
+    /// [`FitMode::Stretch`], they're rasterized at the smaller of the
+    /// horizontal and vertical scale, and stretched the rest of the
+    /// way when copied to the canvas, which looks best with
+    /// [`ScaleMode::Linear`] (see [`FontTexture::set_scale_mode`]).
+    ///
+    /// Nothing is drawn if no glyph would be drawn, e.g. if `glyphs`
+    /// only has spaces.
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_fitted<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        target: Rect,
+        mode: FitMode,
+    ) -> Result<(), FontTextureError> {
+        let options = match crate::fitted_draw_options(glyphs, target, mode) {
+            Some(options) => options,
+            None => return Ok(()),
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but with the
+    /// affine transform `matrix` applied to the corners of each
+    /// glyph, which allows shearing the text, e.g. for faux-italic
+    /// text with fonts that have no italic style. See
+    /// [`transform_corners`](crate::transform_corners) for the layout
+    /// of the matrix, and an example of a shear.
+    ///
+    /// The glyphs are rasterized and cached exactly like with
+    /// [`FontTexture::draw_text`], and drawn as textured triangles
+    /// with `SDL_RenderGeometry`, which needs SDL 2.0.18 or newer.
+    /// Transforms that scale the text up look best with
+    /// [`ScaleMode::Linear`] (see [`FontTexture::set_scale_mode`]),
+    /// and make it blurry, so for scaling the text uniformly, prefer
+    /// [`FontTexture::draw_text_scaled`], which rasterizes the glyphs
+    /// at the scaled size.
+    ///
+    /// Glyphs that don't fit in the glyph cache are drawn as
+    /// transformed outlines, regardless of the
+    /// [`MissingGlyphStyle`].
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`]. Also returns
+    /// [`FontTextureError::Copy`] if the renderer can't draw
+    /// geometry.
+    pub fn draw_text_matrix<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        matrix: [f32; 6],
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            matrix: Some(matrix),
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but rotates the
+    /// whole glyph run `angle` degrees clockwise around `center`,
+    /// which is a point on the canvas.
+    ///
+    /// The glyphs are rasterized and cached exactly like with
+    /// [`FontTexture::draw_text`], only their placement on the canvas
+    /// differs, so drawing the same text at many angles doesn't use
+    /// up any more of the glyph cache.
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_transformed<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        center: Point,
+        angle: f64,
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            rotation: Some((center, angle)),
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but rotates
+    /// each glyph around its own center by the angle (in degrees,
+    /// clockwise) at the same index in `angles`. Glyphs past the end
+    /// of `angles` aren't rotated.
+    ///
+    /// This is useful for vertical writing, where the text is laid
+    /// out one glyph at a time, with most glyphs upright, but some
+    /// (e.g. brackets and dashes) rotated by 90 degrees.
+    ///
+    /// The rectangles of glyphs that don't fit in the glyph cache
+    /// (see [`FontTexture::set_missing_glyph_style`]) are rotated
+    /// too, and always drawn as outlines:
+    ///
+    /// ```
+    /// # use fontdue::{Font, Metrics};
+    /// # use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+    /// # use fontdue_sdl2::FontTextureBuilder;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// // Only a 64x4 strip of the glyph cache is left for glyphs.
+    /// let mut font_texture = FontTextureBuilder::new()
+    ///     .size(64, 64)
+    ///     .reserved_areas(&[Rect::new(0, 0, 64, 60)])
+    ///     .build(&texture_creator)
+    ///     .unwrap();
+    /// // A 10x30 glyph, which doesn't fit.
+    /// font_texture.set_glyph_override('\u{E000}', 32.0, || {
+    ///     let metrics = Metrics { width: 10, height: 30, ..Metrics::default() };
+    ///     (metrics, vec![0xFF; 10 * 30])
+    /// });
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.reset(&LayoutSettings { x: 24.0, ..LayoutSettings::default() });
+    /// layout.append(fonts, &TextStyle::with_user_data("\u{E000}", 32.0, 0, Color::WHITE));
+    /// canvas.set_draw_color(Color::BLACK);
+    /// canvas.clear();
+    /// font_texture
+    ///     .draw_text_with_glyph_angles(&mut canvas, fonts, layout.glyphs(), &[90.0])
+    ///     .unwrap();
+    ///
+    /// // The outline is 30 pixels wide and 10 tall.
+    /// let pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+    /// let drawn = (0..64 * 64).filter(|i| pixels[i * 4] > 0);
+    /// let (xs, ys): (Vec<_>, Vec<_>) = drawn.map(|i| (i % 64, i / 64)).unzip();
+    /// let width = xs.iter().max().unwrap() - xs.iter().min().unwrap() + 1;
+    /// let height = ys.iter().max().unwrap() - ys.iter().min().unwrap() + 1;
+    /// assert!((29..=31).contains(&width), "{}", width);
+    /// assert!((9..=11).contains(&height), "{}", height);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_with_glyph_angles<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        angles: &[f64],
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            glyph_angles: angles,
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], with a drop
+    /// shadow behind it. The shadow is the text drawn in
+    /// `shadow_color`, moved by `shadow_offset` pixels (x, y). The
+    /// alpha of `shadow_color` is multiplied with the alpha of each
+    /// glyph's color.
+    ///
+    /// The shadow is drawn using white glyphs tinted with the texture's
+    /// color mod, like with [`ColorMode::Modulated`]. With that color
+    /// mode, the shadow doesn't need any extra space in the glyph
+    /// cache. With [`ColorMode::Rasterized`], each glyph needs one
+    /// extra white glyph in the cache, shared by all shadow colors.
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_with_shadow<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        shadow_offset: (i32, i32),
+        shadow_color: Color,
+    ) -> Result<(), FontTextureError> {
+        let shadow_options = DrawOptions {
+            offset: shadow_offset,
+            color_override: Some(shadow_color),
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            shadow_options,
+        )?;
+        self.draw_text(canvas, fonts, glyphs)
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but with a
+    /// horizontal gradient from `start_color` on the left edge of the
+    /// text to `end_color` on the right edge, instead of the glyphs'
+    /// own colors. Each glyph is drawn in the color at its center, and
+    /// the alphas of the gradient are multiplied with the alpha of
+    /// each glyph's color.
+    ///
+    /// The glyphs are drawn using white glyphs tinted with the
+    /// texture's color mod, like with [`ColorMode::Modulated`], so
+    /// each glyph shape only needs one entry in the glyph cache,
+    /// regardless of the colors of the gradient.
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_gradient<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        start_color: Color,
+        end_color: Color,
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            gradient: Some((start_color, end_color)),
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], and then draws
+    /// the given [`Decoration`]s, i.e. underlines and
+    /// strikethroughs, over it.
+    ///
+    /// Underlines are positioned and sized by the metrics set with
+    /// [`FontTexture::set_underline_metrics`], if the font has them.
+    /// Otherwise the lines are only approximated from the font's line
+    /// metrics, since fontdue doesn't read the font's own underline
+    /// and strikethrough metrics: underlines go a bit below the
+    /// baseline, strikethroughs around half of the x-height, and both
+    /// are about 1/16th of the line's height thick. These look right
+    /// for most fonts, but can be off by a pixel or two from where
+    /// the font's designer would put them. Where
+    /// the font or size changes within a decoration, each part gets
+    /// its own line, placed by its font's metrics. If a decoration spans
+    /// multiple lines of text, a line is drawn on each of them, from
+    /// the leftmost to the rightmost decorated glyph on that line,
+    /// regardless of the order of the glyphs (e.g. for right-to-left
+    /// text). Glyphs which aren't drawn, e.g. spaces and zero-width
+    /// combining marks, are still included by their advance. The
+    /// glyphs should be laid out with
+    /// [`CoordinateSystem::PositiveYDown`](fontdue::layout::CoordinateSystem::PositiveYDown).
+    ///
+    /// E.g. a run mixing a bold and a thin font gets a thick
+    /// underline under the bold part, and a thin one under the rest:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{Decoration, DecorationStyle, FontTexture, UnderlineMetrics};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 96, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// let bold = include_bytes!("../examples/roboto/Roboto-Bold.ttf") as &[u8];
+    /// let thin = include_bytes!("../examples/playfair-display/PlayfairDisplay-Regular.ttf") as &[u8];
+    /// let fonts = &[
+    ///     Font::from_bytes(bold, Default::default()).unwrap(),
+    ///     Font::from_bytes(thin, Default::default()).unwrap(),
+    /// ];
+    /// let metrics = [
+    ///     UnderlineMetrics::from_font_data(bold, 0).unwrap(),
+    ///     UnderlineMetrics::from_font_data(thin, 0).unwrap(),
+    /// ];
+    /// font_texture.set_underline_metrics(&fonts[0], Some(metrics[0]));
+    /// font_texture.set_underline_metrics(&fonts[1], Some(metrics[1]));
+    ///
+    /// // The text itself is transparent, to only see the underlines.
+    /// let text_color = Color::RGBA(0, 0, 0, 0);
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("bold ", 48.0, 0, text_color));
+    /// layout.append(fonts, &TextStyle::with_user_data("thin", 48.0, 1, text_color));
+    /// let underline = Decoration {
+    ///     glyphs: 0..layout.glyphs().len(),
+    ///     style: DecorationStyle::Underline,
+    ///     color: Color::RED,
+    /// };
+    /// canvas.set_draw_color(Color::BLACK);
+    /// canvas.clear();
+    /// font_texture
+    ///     .draw_text_decorated(&mut canvas, fonts, layout.glyphs(), &[underline])
+    ///     .unwrap();
+    ///
+    /// // Each font's underline is where, and as thick as, its metrics say.
+    /// let pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+    /// let baseline = layout.lines().unwrap()[0].baseline_y;
+    /// for (glyph_index, metrics) in [(1, metrics[0]), (6, metrics[1])] {
+    ///     let glyph = layout.glyphs()[glyph_index];
+    ///     let x = glyph.x as usize + glyph.width / 2;
+    ///     let red_rows = (0..96).filter(|y| pixels[(y * 256 + x) * 4] == 0xFF);
+    ///     let red_rows = red_rows.collect::<Vec<_>>();
+    ///     let top = baseline - metrics.position * 48.0;
+    ///     let thickness = (metrics.thickness * 48.0).round() as usize;
+    ///     assert!((red_rows[0] as f32 - top).abs() <= 1.0);
+    ///     assert_eq!(red_rows.len(), thickness);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`]. Additionally, a
+    /// [`FontTextureError::Draw`] is returned if drawing the lines
+    /// fails.
+    pub fn draw_text_decorated<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        decorations: &[Decoration],
+    ) -> Result<(), FontTextureError> {
+        self.draw_text(canvas, fonts, glyphs)?;
+        crate::draw_decorations(canvas, &self.glyph_cache, fonts, glyphs, decorations)
+    }
+
+    /// Binds the fonts to the [`FontTexture`], so that drawing with
+    /// any other fonts returns a [`FontTextureError::FontMismatch`],
+    /// instead of drawing the wrong glyphs.
+    ///
+    /// The glyphs of a layout refer to their fonts by their index in
+    /// the font slice the layout was made with, so drawing them with
+    /// a different font slice, e.g. with the fonts in another order,
+    /// draws glyphs from the wrong fonts. Once the fonts are bound,
+    /// every draw checks that the given fonts are the bound ones, in
+    /// the same order. Fonts loaded separately from the same font
+    /// file are the same font. Binding other fonts replaces the
+    /// previous ones.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{FontTexture, FontTextureError};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// let regular = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// let bold = include_bytes!("../examples/roboto/Roboto-Bold.ttf") as &[u8];
+    /// let fonts = &[Font::from_bytes(regular, Default::default()).unwrap()];
+    /// let other_fonts = &[Font::from_bytes(bold, Default::default()).unwrap()];
+    /// font_texture.bind_fonts(fonts);
+    ///
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 32.0, 0, Color::WHITE));
+    /// assert!(font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).is_ok());
+    /// assert!(matches!(
+    ///     font_texture.draw_text(&mut canvas, other_fonts, layout.glyphs()),
+    ///     Err(FontTextureError::FontMismatch(_))
+    /// ));
+    ///
+    /// // The same fonts in a different order don't match either.
+    /// let both = [fonts[0].clone(), other_fonts[0].clone()];
+    /// let swapped = [other_fonts[0].clone(), fonts[0].clone()];
+    /// font_texture.bind_fonts(&both);
+    /// assert!(font_texture.draw_text(&mut canvas, &both, layout.glyphs()).is_ok());
+    /// assert!(matches!(
+    ///     font_texture.draw_text(&mut canvas, &swapped, layout.glyphs()),
+    ///     Err(FontTextureError::FontMismatch(_))
+    /// ));
+    /// ```
+    pub fn bind_fonts(&mut self, fonts: &[Font]) {
+        crate::bind_fonts(&mut self.glyph_cache, fonts);
+    }
+
+    /// Sets the position and thickness of the underlines
+    /// [`FontTexture::draw_text_decorated`] draws under the font's
+    /// glyphs, usually read from the font file with
+    /// [`UnderlineMetrics::from_font_data`]. None goes back to
+    /// estimating them from the font's line metrics, which is also
+    /// the default.
+    ///
+    /// ```
+    /// # use fontdue::Font;
+    /// # use fontdue_sdl2::{FontTexture, UnderlineMetrics};
+    /// # use sdl2::pixels::PixelFormatEnum;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// let data = include_bytes!("../examples/playfair-display/PlayfairDisplay-Regular.ttf") as &[u8];
+    /// let font = Font::from_bytes(data, Default::default()).unwrap();
+    /// font_texture.set_underline_metrics(&font, UnderlineMetrics::from_font_data(data, 0));
+    /// ```
+    pub fn set_underline_metrics(&mut self, font: &Font, metrics: Option<UnderlineMetrics>) {
+        match metrics {
+            Some(metrics) => {
+                self.glyph_cache
+                    .underline_metrics
+                    .insert(font.file_hash(), metrics);
+            }
+            None => {
+                self.glyph_cache.underline_metrics.remove(&font.file_hash());
+            }
+        }
+    }
+
+    /// Fills the background of the glyphs in the given ranges (e.g.
+    /// a text selection) with the highlight color, and then renders
+    /// the text over it like [`FontTexture::draw_text`].
+    ///
+    /// The ranges are indices into the glyph slice. The highlight
+    /// spans from the ascent to the descent of the line, and if a
+    /// range spans multiple lines of text, each line gets its own
+    /// rectangle. The rectangles span from the leftmost to the
+    /// rightmost glyph of the range on each line, so right-to-left
+    /// text, with glyph positions decreasing along the slice, is
+    /// highlighted the same way. Glyphs which aren't drawn, e.g.
+    /// spaces and zero-width combining marks, are highlighted by
+    /// their advance like any other glyph. The glyphs should be laid
+    /// out with
+    /// [`CoordinateSystem::PositiveYDown`](fontdue::layout::CoordinateSystem::PositiveYDown).
+    ///
+    /// For example, the first two glyphs of right-to-left text are
+    /// highlighted at its right end, just like the last two glyphs of
+    /// the same text laid out left-to-right:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, LayoutSettings, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.reset(&LayoutSettings { x: 8.0, y: 8.0, ..LayoutSettings::default() });
+    /// layout.append(fonts, &TextStyle::with_user_data("ABCD", 32.0, 0, Color::WHITE));
+    /// let ltr = layout.glyphs().clone();
+    /// // The same glyphs, in right-to-left order.
+    /// let rtl = ltr.iter().rev().copied().collect::<Vec<_>>();
+    ///
+    /// let draw = |glyphs: &[_], range| {
+    ///     let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    ///     let mut canvas = surface.into_canvas().unwrap();
+    ///     let texture_creator = canvas.texture_creator();
+    ///     let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    ///     let blue = Color::RGB(0, 0, 255);
+    ///     font_texture
+    ///         .draw_text_with_highlight(&mut canvas, fonts, glyphs, &[range], blue)
+    ///         .unwrap();
+    ///     canvas.into_surface().without_lock().unwrap().to_vec()
+    /// };
+    /// let rtl_pixels = draw(&rtl, 0..2);
+    /// assert_eq!(rtl_pixels, draw(&ltr, 2..4));
+    ///
+    /// // The highlight starts at "C", so "A" and "B" aren't highlighted.
+    /// let blue_columns = rtl_pixels
+    ///     .chunks(4)
+    ///     .enumerate()
+    ///     .filter(|(_, pixel)| pixel == &[0, 0, 255, 255])
+    ///     .map(|(i, _)| i % 128);
+    /// let leftmost_blue = blue_columns.min().unwrap();
+    /// assert!(leftmost_blue as f32 >= ltr[2].x - 2.0);
+    /// ```
+    ///
+    /// Glyphs with no area, like the zero-width space and the em space
+    /// after the accented "e" below, aren't drawn, but a highlight
+    /// ending on them still spans their advance:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, LayoutSettings, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.reset(&LayoutSettings { x: 8.0, y: 8.0, ..LayoutSettings::default() });
+    /// let text = "e\u{301}\u{200B}\u{2003}x";
+    /// layout.append(fonts, &TextStyle::with_user_data(text, 32.0, 0, Color::WHITE));
+    /// let glyphs = layout.glyphs();
+    /// assert_eq!(glyphs[3].width * glyphs[3].height, 0);
+    ///
+    /// let blue = Color::RGB(0, 0, 255);
+    /// font_texture
+    ///     .draw_text_with_highlight(&mut canvas, fonts, glyphs, &[0..4], blue)
+    ///     .unwrap();
+    /// // Only "e", the accent and "x" are rasterized and drawn.
+    /// assert_eq!(font_texture.last_frame_stats().rasterized_glyphs, 3);
+    /// assert_eq!(font_texture.last_frame_stats().drawn_glyphs, 3);
+    ///
+    /// // The highlight spans the em space, up to the "x".
+    /// let pixels = canvas.into_surface().without_lock().unwrap().to_vec();
+    /// let rightmost_blue = pixels
+    ///     .chunks(4)
+    ///     .enumerate()
+    ///     .filter(|(_, pixel)| pixel == &[0, 0, 255, 255])
+    ///     .map(|(i, _)| i % 128)
+    ///     .max()
+    ///     .unwrap();
+    /// assert!(rightmost_blue + 2 >= glyphs[4].x as usize);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`]. Additionally, a
+    /// [`FontTextureError::Draw`] is returned if drawing the
+    /// highlights fails.
+    pub fn draw_text_with_highlight<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        ranges: &[Range<usize>],
+        highlight_color: Color,
+    ) -> Result<(), FontTextureError> {
+        crate::draw_highlights(canvas, fonts, glyphs, ranges, highlight_color)?;
+        self.draw_text(canvas, fonts, glyphs)
+    }
+
+    /// Renders a single line of text like [`FontTexture::draw_text`],
+    /// but truncated with an ellipsis ("…") if it's wider than
+    /// `max_width` pixels. See [`ellipsize`](crate::ellipsize) for
+    /// the details.
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`].
+    pub fn draw_text_ellipsized<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        max_width: f32,
+    ) -> Result<(), FontTextureError> {
+        let glyphs = crate::ellipsize(fonts, glyphs, max_width);
+        self.draw_text(canvas, fonts, &glyphs)
+    }
+
+    /// Renders text like [`FontTexture::draw_text`], but merges the
+    /// glyphs where they overlap, instead of blending them over each
+    /// other. Useful for text with overlapping glyphs, e.g. tightly
+    /// kerned italics or script fonts, drawn in a translucent color:
+    /// blending the anti-aliased edges of overlapping glyphs over each
+    /// other makes the overlaps more opaque than the rest of the text,
+    /// which shows up as darker (or lighter) seams.
+    ///
+    /// The glyphs are first drawn into a temporary texture, keeping
+    /// the maximum coverage of the overlapping glyphs, and then the
+    /// texture is drawn onto the canvas, like with
+    /// [`FontTexture::render_to_texture`]. This is a lot slower than
+    /// [`FontTexture::draw_text`], since the texture is created for
+    /// every draw. Keeping the maximum coverage also needs a renderer
+    /// which supports the `SDL_BLENDOPERATION_MAXIMUM` blend
+    /// operation, e.g. Direct3D. Other renderers can only merge
+    /// glyphs of the same translucency, by drawing them opaque into
+    /// the texture, and then drawing the texture with the
+    /// translucency. Other glyphs, and glyphs drawn with
+    /// [`ColorMode::Subpixel`], are drawn separately like with
+    /// [`FontTexture::draw_text`].
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{GlyphPosition, Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let translucent = Color::RGBA(0, 0, 0, 128);
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("mmm", 48.0, 0, translucent));
+    /// // Squeezed together, so that the glyphs overlap.
+    /// let squeezed = layout
+    ///     .glyphs()
+    ///     .iter()
+    ///     .enumerate()
+    ///     .map(|(i, glyph)| GlyphPosition { x: glyph.x - i as f32 * 12.0, ..*glyph })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let darkest_pixel = |glyphs: &[_], merged: bool| {
+    ///     let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    ///     let mut canvas = surface.into_canvas().unwrap();
+    ///     canvas.set_draw_color(Color::WHITE);
+    ///     canvas.clear();
+    ///     let texture_creator = canvas.texture_creator();
+    ///     let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    ///     if merged {
+    ///         font_texture
+    ///             .draw_text_merged(&mut canvas, &texture_creator, fonts, glyphs)
+    ///             .unwrap();
+    ///     } else {
+    ///         font_texture.draw_text(&mut canvas, fonts, glyphs).unwrap();
+    ///     }
+    ///     let pixels = canvas.into_surface().without_lock().unwrap().to_vec();
+    ///     pixels.chunks(4).map(|pixel| pixel[0]).min().unwrap()
+    /// };
+    /// // A single glyph is as dark as the text should get.
+    /// let glyph_darkness = darkest_pixel(&squeezed[..1], false);
+    /// // Blending the glyphs over each other darkens the overlaps...
+    /// assert!(darkest_pixel(&squeezed, false) < glyph_darkness);
+    /// // ...while merging them doesn't.
+    /// assert_eq!(darkest_pixel(&squeezed, true), glyph_darkness);
+    /// ```
+    ///
+    /// Glyphs without a color are merged in the
+    /// [default color](FontTexture::set_default_color):
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let unset = Color::RGBA(0, 0, 0, 0);
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("mmm", 48.0, 0, unset));
+    ///
+    /// font_texture.set_default_color(Some(Color::RGBA(0, 0, 0, 128)));
+    /// canvas.set_draw_color(Color::WHITE);
+    /// canvas.clear();
+    /// font_texture
+    ///     .draw_text_merged(&mut canvas, &texture_creator, fonts, layout.glyphs())
+    ///     .unwrap();
+    /// let pixels = canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+    /// let darkest = pixels.chunks_exact(4).map(|pixel| pixel[0]).min().unwrap();
+    /// assert!((120..=136).contains(&darkest));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::render_to_texture`]. Additionally, a
+    /// [`FontTextureError::Copy`] is returned if the texture can't be
+    /// copied onto the canvas.
+    pub fn draw_text_merged<RT: RenderTarget, T>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        texture_creator: &TextureCreator<T>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<(), FontTextureError> {
+        crate::draw_text_merged(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            texture_creator,
+            fonts,
+            glyphs,
+        )
+    }
+
+    /// Renders the glyphs into a new texture, which is just big
+    /// enough to contain them (see [`text_bounds`](crate::text_bounds)).
+    /// The texture can then be drawn many times, e.g. as a cached
+    /// button label, without drawing each glyph separately.
+    ///
+    /// The canvas is used for rendering, and should be the one the
+    /// texture creator was created from, like in
+    /// [`FontTexture::new`]. The rest of the texture is transparent.
+    /// The texture has premultiplied alpha, and a custom blend mode
+    /// for it set, like with [`FontTexture::set_premultiplied_alpha`],
+    /// so drawing it looks the same as drawing the glyphs directly.
+    /// On renderers without custom blend modes, e.g. the software
+    /// renderer, the texture is converted to straight alpha instead,
+    /// and has [`BlendMode::Blend`](sdl2::render::BlendMode::Blend)
+    /// set.
+    /// Glyphs drawn with [`ColorMode::Subpixel`] are rendered with
+    /// [`ColorMode::Modulated`] instead, since subpixel coverage
+    /// can't be represented in a transparent texture. Returns None if
+    /// there's nothing to draw, e.g. if the glyphs are all spaces.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 32.0, 0, Color::WHITE));
+    ///
+    /// let draw = |via_texture: bool| {
+    ///     let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    ///     let mut canvas = surface.into_canvas().unwrap();
+    ///     canvas.set_draw_color(Color::RGB(0x20, 0x40, 0x80));
+    ///     canvas.clear();
+    ///     let texture_creator = canvas.texture_creator();
+    ///     let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    ///     if via_texture {
+    ///         let glyphs = layout.glyphs();
+    ///         let texture = font_texture
+    ///             .render_to_texture(&mut canvas, &texture_creator, fonts, glyphs)
+    ///             .unwrap()
+    ///             .unwrap();
+    ///         let bounds = fontdue_sdl2::text_bounds(glyphs).unwrap();
+    ///         canvas.copy(&texture, None, bounds).unwrap();
+    ///     } else {
+    ///         font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///     }
+    ///     canvas.into_surface().without_lock().unwrap().to_vec()
+    /// };
+    /// let (direct, via_texture) = (draw(false), draw(true));
+    /// // The antialiased edges match, give or take rounding.
+    /// let edge = |pixel: &[u8]| pixel[0] > 0x20 && pixel[0] < 0xFF;
+    /// assert!(direct.chunks(4).any(edge));
+    /// for (a, b) in direct.chunks(4).zip(via_texture.chunks(4)) {
+    ///     assert!(a.iter().zip(b).all(|(&a, &b)| (a as i32 - b as i32).abs() <= 2));
+    /// }
+    /// ```
+    ///
+    /// The texture is a target texture, so like all target textures,
+    /// it loses its contents on renderers which reset their device,
+    /// e.g. Direct3D, which SDL reports with an
+    /// `SDL_RENDER_TARGETS_RESET` event. When the event arrives, the
+    /// texture should be rendered again with this function.
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`]. Additionally, a
+    /// [`FontTextureError::TextureCreation`] is returned if the new
+    /// texture can't be created, and a [`FontTextureError::Draw`] if
+    /// the canvas doesn't support rendering to textures.
+    pub fn render_to_texture<'t, RT: RenderTarget, T>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        texture_creator: &'t TextureCreator<T>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<Option<Texture<'t>>, FontTextureError> {
+        crate::render_to_texture(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            texture_creator,
+            fonts,
+            glyphs,
+        )
+    }
+
+    /// Draws the glyphs appended to the text since the last call into
+    /// the target texture, e.g. the new lines of a log, without
+    /// drawing the rest of the text again. The texture scrolls along
+    /// with the end of the text once the text is taller than it. See
+    /// [`AppendedText`] for drawing the texture.
+    ///
+    /// `glyphs` is the whole text, laid out in the same place every
+    /// time with
+    /// [`CoordinateSystem::PositiveYDown`](fontdue::layout::CoordinateSystem::PositiveYDown),
+    /// and `appended` keeps track of how much of it has been drawn.
+    /// If the text has fewer glyphs than have been drawn, e.g. when
+    /// the log is cleared, the texture is drawn from scratch. The
+    /// texture should be a target texture (see
+    /// [`TextureCreator::create_texture_target`]) created by the
+    /// canvas' texture creator, with a blend mode for drawing text,
+    /// like the textures of [`FontTexture::render_to_texture`].
+    /// The glyphs are clipped to the width of the texture.
+    ///
+    /// Returns a [`DrawReport`] of the glyphs drawn, which are only
+    /// the visible glyphs of the appended text:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{AppendedText, FontTexture};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Point;
+    /// # use sdl2::render::BlendMode;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 256, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut log = texture_creator
+    ///     .create_texture_target(PixelFormatEnum::RGBA32, 256, 256)
+    ///     .unwrap();
+    /// log.set_blend_mode(BlendMode::Blend);
+    /// let mut appended = AppendedText::new();
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// for i in 0..1000 {
+    ///     let line = format!("Line {}\n", i);
+    ///     layout.append(fonts, &TextStyle::with_user_data(&line, 16.0, 0, Color::WHITE));
+    /// }
+    /// font_texture
+    ///     .draw_text_appended(&mut canvas, &mut log, &mut appended, fonts, layout.glyphs())
+    ///     .unwrap();
+    /// assert!(appended.scroll() > 0);
+    ///
+    /// layout.append(fonts, &TextStyle::with_user_data("New", 16.0, 0, Color::WHITE));
+    /// let report = font_texture
+    ///     .draw_text_appended(&mut canvas, &mut log, &mut appended, fonts, layout.glyphs())
+    ///     .unwrap();
+    /// assert_eq!(report.drawn_glyphs, 3);
+    /// appended.copy(&mut canvas, &log, Point::new(0, 0)).unwrap();
+    /// ```
+    ///
+    /// The glyphs are drawn into the texture right away, even with
+    /// [`FontTexture::set_deferred_uploads`]:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{AppendedText, FontTexture};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::render::BlendMode;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// font_texture.set_deferred_uploads(true);
+    /// let mut log = texture_creator
+    ///     .create_texture_target(PixelFormatEnum::RGBA32, 64, 64)
+    ///     .unwrap();
+    /// log.set_blend_mode(BlendMode::Blend);
+    /// let mut appended = AppendedText::new();
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Log", 16.0, 0, Color::WHITE));
+    /// font_texture
+    ///     .draw_text_appended(&mut canvas, &mut log, &mut appended, fonts, layout.glyphs())
+    ///     .unwrap();
+    ///
+    /// let mut pixels = Vec::new();
+    /// canvas
+    ///     .with_texture_canvas(&mut log, |log_canvas| {
+    ///         pixels = log_canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+    ///     })
+    ///     .unwrap();
+    /// assert!(pixels.chunks_exact(4).any(|pixel| pixel[3] > 0));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::draw_text`]. Additionally, a
+    /// [`FontTextureError::Draw`] is returned if the canvas doesn't
+    /// support rendering to textures, or the scrolled rows of the
+    /// texture can't be cleared.
+    ///
+    /// [`TextureCreator::create_texture_target`]: sdl2::render::TextureCreator::create_texture_target
+    pub fn draw_text_appended<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        target: &mut Texture,
+        appended: &mut AppendedText,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<DrawReport, FontTextureError> {
+        crate::draw_text_appended(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            target,
+            appended,
+            fonts,
+            glyphs,
+        )
+    }
+
+    /// Rasterizes the glyphs into the glyph cache without drawing
+    /// them, so that drawing them later is fast.
+    ///
+    /// Rasterizing many new glyphs at once can cause a visible
+    /// hitch, e.g. on the first frame of a new screen. Call this
+    /// during a loading screen with the glyphs that will be drawn
+    /// later to avoid that. Like with [`FontTexture::draw_text`], the
+    /// least recently drawn glyphs are evicted if the glyph cache is
+    /// full, and glyphs that still don't fit are logged as errors.
+    ///
+    /// The fonts and glyphs should be the same as the ones passed to
+    /// [`FontTexture::draw_text`] later. Then drawing them doesn't
+    /// rasterize anything:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(512, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Loading done!", 32.0, 0, Color::WHITE));
+    /// font_texture.cache_glyphs(fonts, layout.glyphs()).unwrap();
+    ///
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let stats = font_texture.last_frame_stats();
+    /// assert_eq!(stats.rasterized_glyphs, 0);
+    /// assert_eq!(stats.drawn_glyphs, 12);
+    /// ```
+    ///
+    /// Glyphs without any coverage, e.g. whitespace, don't take up
+    /// space in the glyph cache, and don't evict other glyphs:
+    ///
+    /// ```
+    /// # use fontdue::{Font, Metrics, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 64, 64).unwrap();
+    /// font_texture.set_glyph_override('\u{E000}', 24.0, || {
+    ///     let metrics = Metrics { width: 20, height: 20, ..Metrics::default() };
+    ///     (metrics, vec![0; 20 * 20])
+    /// });
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("\u{E000}", 24.0, 0, Color::WHITE));
+    /// let blank = layout.glyphs().clone();
+    ///
+    /// font_texture.cache_glyphs(fonts, &blank).unwrap();
+    /// assert_eq!(font_texture.cache_stats().glyph_count, 0);
+    /// assert_eq!(font_texture.cache_stats().largest_free_rect, Some(Rect::new(0, 0, 64, 64)));
+    ///
+    /// // Fill up the glyph cache, and draw the blank glyph again.
+    /// font_texture.evict_glyph(blank[0].key);
+    /// layout.clear();
+    /// layout.append(fonts, &TextStyle::with_user_data("ABCDEFGH", 24.0, 0, Color::WHITE));
+    /// font_texture.cache_glyphs(fonts, layout.glyphs()).unwrap();
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, &blank).unwrap();
+    /// assert_eq!(report.evicted_glyphs, 0);
+    /// assert_eq!(font_texture.cache_stats().glyph_count, 8);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return a
+    /// [`FontTextureError::TextureUpdate`] if the Texture cannot be
+    /// written to, containing an informational string from SDL.
+    pub fn cache_glyphs(
+        &mut self,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<(), FontTextureError> {
+        let buffers = crate::cache_glyphs(
+            &mut self.glyph_cache,
+            &self.settings,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            &DrawOptions::default(),
+        );
+        self.glyph_cache.draw_buffers = buffers;
+        self.glyph_cache.upload(&mut self.texture)
+    }
+
+    /// Uploads the glyphs rasterized by the draws made with
+    /// [`FontTexture::set_deferred_uploads`] to the glyph cache
+    /// texture with a single update, and then makes the draws onto
+    /// the canvas. Does nothing if there are no pending draws.
+    ///
+    /// The canvas should be the one the pending draws were made on.
+    /// Pending draws have to be flushed before
+    /// [`FontTexture::defragment`] or [`FontTexture::resize`], which
+    /// move the glyphs they use.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`FontTexture::draw_text`]. The
+    /// pending draws after a failed one are discarded.
+    pub fn flush_uploads<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+    ) -> Result<(), FontTextureError> {
+        crate::flush_uploads(&mut self.texture, &mut self.glyph_cache, canvas).map(|_| ())
+    }
+
+    /// Rasterizes the character into the glyph cache, if it isn't
+    /// cached already, and returns the area of
+    /// [`FontTexture::texture`] it's in. For drawing single glyphs
+    /// in ways the drawing functions don't support, by copying them
+    /// from the texture yourself:
+    ///
+    /// ```
+    /// # use fontdue::Font;
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let a = font_texture.glyph_rect(fonts, 0, 'A', 24.0, Color::WHITE).unwrap().unwrap();
+    /// let target = Rect::new(100, 10, a.width() * 2, a.height() * 2);
+    /// canvas.copy(&font_texture.texture, a, target).unwrap();
+    /// # let pixels = canvas.read_pixels(target, PixelFormatEnum::RGBA32).unwrap();
+    /// # assert!(pixels.iter().any(|&p| p != 0));
+    /// ```
+    ///
+    /// The glyph is rasterized like the drawing functions would
+    /// rasterize it, so e.g. with [`ColorMode::Modulated`] it's
+    /// white, and with [`FontTexture::set_dpi_scale`] its size is
+    /// the scaled size. Returns None if the character has nothing to
+    /// draw (e.g. a space), or if the glyph doesn't fit in the glyph
+    /// cache. Like the drawing functions, this can evict glyphs that
+    /// haven't been drawn recently, after which the area can contain
+    /// another glyph, so the area should be fetched again before
+    /// each copy.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a
+    /// [`FontTextureError::TextureUpdate`] if the Texture cannot be
+    /// written to, containing an informational string from SDL.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `font_index` is out of bounds for `fonts`.
+    pub fn glyph_rect(
+        &mut self,
+        fonts: &[Font],
+        font_index: usize,
+        character: char,
+        px: f32,
+        color: Color,
+    ) -> Result<Option<Rect>, FontTextureError> {
+        crate::glyph_rect(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            fonts,
+            font_index,
+            character,
+            px,
+            color,
+        )
+    }
+
+    /// Writes RGBA pixels, in tightly packed rows, into the area of
+    /// [`FontTexture::texture`] within one of the areas reserved with
+    /// [`FontTextureBuilder::reserved_areas`], and uploads them.
+    ///
+    /// The pixels are kept in the glyph cache's copy of the texture,
+    /// so that they're uploaded again when the whole texture is, e.g.
+    /// by [`FontTexture::defragment`], unlike pixels drawn into the
+    /// texture directly.
+    ///
+    /// # Errors
+    ///
+    /// This function will return a
+    /// [`FontTextureError::TextureUpdate`] if the area isn't within a
+    /// reserved area, if there isn't exactly 4 bytes of pixels for
+    /// every pixel of the area, or if the Texture cannot be written
+    /// to.
+    pub fn update_reserved_area(
+        &mut self,
+        rect: Rect,
+        rgba_pixels: &[u8],
+    ) -> Result<(), FontTextureError> {
+        crate::update_reserved_area(&mut self.texture, &mut self.glyph_cache, rect, rgba_pixels)
+    }
+
+    /// Returns the [`Metrics`] of the glyph's bitmap in the glyph
+    /// cache, as returned by fontdue when rasterizing it, if the glyph
+    /// is cached in the given color, and its metrics were kept (see
+    /// [`FontTexture::set_retain_metrics`]). Useful for debugging
+    /// layouts, e.g. checking that a glyph's bitmap is the size it's
+    /// expected to be.
+    ///
+    /// The glyph is looked up like with [`FontTexture::is_cached`].
+    /// The metrics are for the size the glyph is rasterized at, which
+    /// differs from its laid out size with e.g.
+    /// [`FontTexture::set_dpi_scale`].
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// font_texture.set_retain_metrics(true);
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hi", 32.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///
+    /// let h = layout.glyphs()[0].key;
+    /// let (metrics, _) = fonts[0].rasterize_config(h);
+    /// assert_eq!(font_texture.glyph_metrics(h, Color::WHITE), Some(metrics));
+    /// ```
+    pub fn glyph_metrics(&self, key: GlyphRasterConfig, color: Color) -> Option<Metrics> {
+        crate::glyph_metrics(&self.glyph_cache, &self.settings, key, color)
+    }
+
+    /// Returns the glyphs in the glyph cache, with the colors they
+    /// were rasterized in. The glyphs are in no particular order.
+    ///
+    /// The glyphs are as they were rasterized, so their size is the
+    /// size they were rasterized at (see e.g.
+    /// [`FontTexture::set_dpi_scale`]), and with
+    /// [`ColorMode::Modulated`] and [`ColorMode::Subpixel`], their
+    /// color is white. Glyphs rasterized with different settings,
+    /// e.g. gamma, are all included, even though only the ones
+    /// matching the current settings are drawn. Glyphs with nothing
+    /// to draw, e.g. spaces, aren't included.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("abc", 20.0, 0, Color::RED));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///
+    /// let mut cached = font_texture.cached_keys().collect::<Vec<_>>();
+    /// cached.sort_by_key(|(key, _)| key.glyph_index);
+    /// let drawn = layout.glyphs().iter().map(|glyph| (glyph.key, Color::RED));
+    /// assert!(cached.into_iter().eq(drawn));
+    /// ```
+    pub fn cached_keys(&self) -> impl Iterator<Item = (GlyphRasterConfig, Color)> + '_ {
+        self.glyph_cache
+            .rect_allocator
+            .reservations()
+            .map(|(key, _)| {
+                let (r, g, b) = key.rgb;
+                (key.glyph, Color::RGB(r, g, b))
+            })
+    }
+
+    /// Returns the index of the glyph drawn at the point by the last
+    /// draw, in the glyphs passed to it, or None if the point isn't
+    /// on any glyph. For interactive text, e.g. finding the glyph
+    /// under the mouse cursor, without laying out the text again.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Point;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello World", 32.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///
+    /// let w = &layout.glyphs()[6];
+    /// let inside_w = Point::new(w.x as i32 + w.width as i32 / 2, w.y as i32 + w.height as i32 / 2);
+    /// assert_eq!(font_texture.hit_test(inside_w), Some(6));
+    /// assert_eq!(font_texture.hit_test(Point::new(250, 60)), None);
+    /// ```
+    ///
+    /// The glyphs are hit-tested with the rectangles they're drawn
+    /// into, as returned by [`layout_draw_rects`](crate::layout_draw_rects)
+    /// (including any offset or scale of the draw), so points in the
+    /// gaps between glyphs, e.g. spaces, don't hit any glyph. Glyphs
+    /// that are rotated or transformed with a matrix are tested as if
+    /// they weren't. If glyphs overlap, the one drawn last is
+    /// returned.
+    pub fn hit_test(&self, point: Point) -> Option<usize> {
+        crate::hit_test(&self.glyph_cache, point)
+    }
+
+    /// Returns the counters of the draws since the start of the
+    /// frame: the glyphs drawn and rasterized, the bytes uploaded to
+    /// the glyph cache texture, and so on, see [`FrameStats`].
+    ///
+    /// By default, a frame is a single draw, i.e. the counters are
+    /// reset at the start of every draw. For counting the draws of a
+    /// whole frame, call [`FontTexture::begin_frame`] at its start.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 32.0, 0, Color::WHITE));
+    ///
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let stats = font_texture.last_frame_stats();
+    /// assert_eq!(stats.drawn_glyphs, 5);
+    /// assert_eq!(stats.rasterized_glyphs, 4); // The second "l" was cached by the first.
+    /// assert!(stats.uploaded_bytes > 0);
+    ///
+    /// font_texture.begin_frame();
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let stats = font_texture.last_frame_stats();
+    /// assert_eq!(stats.drawn_glyphs, 10);
+    /// assert_eq!(stats.rasterized_glyphs, 0);
+    /// assert_eq!(stats.uploaded_bytes, 0);
+    /// ```
+    pub fn last_frame_stats(&self) -> FrameStats {
+        self.glyph_cache.frame_stats
+    }
+
+    /// Starts a new frame for [`FontTexture::last_frame_stats`]:
+    /// resets the counters, and makes the following draws add to
+    /// them, instead of resetting them, until the next call.
+    pub fn begin_frame(&mut self) {
+        self.glyph_cache.frame_stats = FrameStats::default();
+        self.glyph_cache.frame_started = true;
+    }
+
+    /// Returns true if the glyph has already been rasterized into
+    /// the glyph cache in the given color (with the current color
+    /// mode and other settings), i.e. if drawing it wouldn't
+    /// rasterize it again. Useful for checking that preloading
+    /// glyphs, e.g. with [`FontTexture::cache_glyphs`], worked.
+    ///
+    /// With [`ColorMode::Modulated`] and [`ColorMode::Subpixel`],
+    /// glyphs are cached once for all colors, so the color doesn't
+    /// matter.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hi", 32.0, 0, Color::WHITE));
+    /// let h = layout.glyphs()[0].key;
+    /// assert!(!font_texture.is_cached(h, Color::WHITE));
+    ///
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert!(font_texture.is_cached(h, Color::WHITE));
+    /// assert!(!font_texture.is_cached(h, Color::RED));
+    /// ```
+    pub fn is_cached(&self, key: GlyphRasterConfig, color: Color) -> bool {
+        crate::is_cached(&self.glyph_cache, &self.settings, key, color)
+    }
+
+    /// Returns true if all the glyphs that aren't cached yet would fit
+    /// in the free space of the glyph cache, without evicting any of
+    /// the cached glyphs. The glyph cache isn't changed.
+    ///
+    /// If this returns false, drawing the glyphs would evict glyphs
+    /// from the cache, or, if even that doesn't make enough space,
+    /// draw some of them as missing glyphs. This can be checked before
+    /// a draw that introduces many new glyphs, to grow the glyph
+    /// cache first with [`FontTexture::resize`].
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 64, 64).unwrap();
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hi", 32.0, 0, Color::WHITE));
+    /// assert!(font_texture.can_fit(fonts, layout.glyphs()));
+    ///
+    /// layout.append(fonts, &TextStyle::with_user_data("ABCDEFGH", 32.0, 0, Color::WHITE));
+    /// assert!(!font_texture.can_fit(fonts, layout.glyphs()));
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert!(!report.missing_glyphs.is_empty());
+    /// ```
+    pub fn can_fit(&self, fonts: &[Font], glyphs: &[GlyphPosition<Color>]) -> bool {
+        crate::can_fit(
+            &self.glyph_cache,
+            &self.settings,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            None,
+        )
+    }
+
+    /// Removes the glyphs which haven't been drawn in the last
+    /// `max_age` draws from the glyph cache, freeing up their space,
+    /// and returns the amount of removed glyphs. Glyphs drawn in the
+    /// latest draw are never removed.
+    ///
+    /// Every call to one of the drawing functions (and
+    /// [`FontTexture::cache_glyphs`]) counts as a draw, so with one
+    /// draw per frame, `max_age` is the amount of frames. Calling
+    /// this every now and then keeps the cache from filling up with
+    /// glyphs that were only drawn once, e.g. from a message that was
+    /// shown briefly, while the glyphs that are drawn continuously
+    /// stay cached, and makes room for bigger glyphs:
+    ///
+    /// ```
+    /// # use fontdue::{Font, Metrics, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 64, 64).unwrap();
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("ABCDEFGHIJ", 20.0, 0, Color::WHITE));
+    /// font_texture.cache_glyphs(fonts, layout.glyphs()).unwrap();
+    /// layout.clear();
+    /// layout.append(fonts, &TextStyle::with_user_data("A", 20.0, 0, Color::WHITE));
+    /// for _frame in 0..3 {
+    ///     font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// }
+    /// assert_eq!(font_texture.prune_unused(2), 9);
+    ///
+    /// font_texture.set_glyph_override('\u{E000}', 20.0, || {
+    ///     let metrics = Metrics { width: 40, height: 60, ..Metrics::default() };
+    ///     (metrics, vec![0xFF; 40 * 60])
+    /// });
+    /// layout.clear();
+    /// layout.append(fonts, &TextStyle::with_user_data("\u{E000}", 20.0, 0, Color::WHITE));
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert!(report.missing_glyphs.is_empty());
+    /// assert_eq!(report.evicted_glyphs, 0);
+    /// assert_eq!(font_texture.cache_stats().glyph_count, 2);
+    /// ```
+    pub fn prune_unused(&mut self, max_age: u32) -> usize {
+        self.glyph_cache.rect_allocator.prune(max_age as u64)
+    }
+
+    /// Removes every cached version (e.g. in different colors) of
+    /// the glyph from the glyph cache, freeing up its space. The
+    /// glyph will be rasterized again the next time it's drawn.
+    /// Returns the amount of removed cache entries.
+    pub fn evict_glyph(&mut self, key: GlyphRasterConfig) -> usize {
+        self.glyph_cache
+            .rect_allocator
+            .evict(|cached| cached.glyph == key)
+    }
+
+    /// Removes every glyph of the font from the glyph cache, freeing
+    /// up their space. Returns the amount of removed cache entries.
+    ///
+    /// Glyphs are identified by the hash of the font file they're
+    /// from, so this is useful e.g. when a font is no longer used,
+    /// or when hot-reloading a font file that has changed: the
+    /// glyphs of the old version won't be used anymore, but would
+    /// still take up space in the cache.
+    ///
+    /// The space of the evicted glyphs is merged back into the free
+    /// space around it, so evicting every glyph leaves the whole
+    /// cache free for glyphs of any size:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 128, 128).unwrap();
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 24.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///
+    /// assert_eq!(font_texture.evict_font(&fonts[0]), 4);
+    /// let stats = font_texture.cache_stats();
+    /// assert_eq!(stats.largest_free_rect, Some(Rect::new(0, 0, 128, 128)));
+    /// ```
+    pub fn evict_font(&mut self, font: &Font) -> usize {
+        let font_hash = font.file_hash();
+        self.glyph_cache
+            .rect_allocator
+            .evict(|cached| cached.glyph.font_hash == font_hash)
+    }
+
+    /// Returns a [`GlyphRasterizer`] with the current settings of
+    /// this [`FontTexture`], for rasterizing glyphs on another
+    /// thread.
+    ///
+    /// Rasterizing is the expensive part of drawing new glyphs, so
+    /// doing it on a worker thread and only inserting the results
+    /// with [`FontTexture::insert_rasterized`] on the render thread
+    /// avoids hitches when a lot of new text appears at once:
+    ///
+    /// ```no_run
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::Color;
+    /// # let sdl_context = sdl2::init().unwrap();
+    /// # let video_subsystem = sdl_context.video().unwrap();
+    /// # let window = video_subsystem.window("", 800, 600).build().unwrap();
+    /// # let mut canvas = window.into_canvas().build().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// # let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// # layout.append(fonts, &TextStyle::with_user_data("Hello", 32.0, 0, Color::WHITE));
+    /// let rasterizer = font_texture.rasterizer();
+    /// let rasterized = std::thread::scope(|scope| {
+    ///     scope
+    ///         .spawn(|| rasterizer.rasterize(fonts, layout.glyphs()))
+    ///         .join()
+    ///         .unwrap()
+    /// });
+    /// font_texture.insert_rasterized(rasterized).unwrap();
+    /// ```
+    pub fn rasterizer(&self) -> GlyphRasterizer {
+        GlyphRasterizer {
+            settings: self.settings,
+        }
+    }
+
+    /// Adds glyphs rasterized by a [`GlyphRasterizer`] into the
+    /// glyph cache, so that drawing them doesn't rasterize them
+    /// again. Glyphs that are already in the cache are skipped, and
+    /// glyphs that don't fit in the cache are logged as errors.
+    ///
+    /// Inserting glyphs doesn't count as a draw: to make space, only
+    /// glyphs that weren't used in the latest draw are evicted, and
+    /// the inserted glyphs count as used in it. The
+    /// `prerasterize_benchmark` example compares the time spent on
+    /// the render thread with and without a [`GlyphRasterizer`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return a
+    /// [`FontTextureError::TextureUpdate`] if the Texture cannot be
+    /// written to, containing an informational string from SDL.
+    pub fn insert_rasterized(
+        &mut self,
+        rasterized: RasterizedGlyphs,
+    ) -> Result<(), FontTextureError> {
+        crate::insert_rasterized_glyphs(&mut self.texture, &mut self.glyph_cache, rasterized)
+    }
+
+    /// Saves the glyphs in the glyph cache into the writer, so that
+    /// they can be loaded back with [`FontTexture::load_cache`], e.g.
+    /// on the next start of the application, instead of rasterizing
+    /// them again.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// use std::fs::File;
+    /// use std::io::{BufReader, BufWriter};
+    ///
+    /// let path = std::env::temp_dir().join("fontdue-sdl2-glyph-cache.bin");
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 24.0, 0, Color::WHITE));
+    /// let draw = |first_run: bool| {
+    ///     let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    ///     let mut canvas = surface.into_canvas().unwrap();
+    ///     let texture_creator = canvas.texture_creator();
+    ///     let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    ///     // On startup:
+    ///     if let Ok(file) = File::open(&path) {
+    ///         font_texture.load_cache(fonts, BufReader::new(file)).unwrap();
+    ///     }
+    ///     font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    ///     let rasterized = font_texture.last_frame_stats().rasterized_glyphs;
+    ///     assert_eq!(rasterized, if first_run { 4 } else { 0 });
+    ///     // ...and on exit:
+    ///     let file = File::create(&path).unwrap();
+    ///     font_texture.dump_cache(BufWriter::new(file)).unwrap();
+    ///     canvas.into_surface().without_lock().unwrap().to_vec()
+    /// };
+    /// # let _ = std::fs::remove_file(&path);
+    /// let first_run = draw(true);
+    /// // The saved glyphs aren't rasterized again, and look the same.
+    /// assert_eq!(draw(false), first_run);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::CacheFile`] if writing fails.
+    pub fn dump_cache(&self, writer: impl Write) -> Result<(), FontTextureError> {
+        crate::persist::write_glyph_cache(&self.glyph_cache, writer)
+            .map_err(|err| FontTextureError::CacheFile(err.to_string()))
+    }
+
+    /// Loads glyphs saved with [`FontTexture::dump_cache`] into the
+    /// glyph cache, like [`FontTexture::insert_rasterized`]. The
+    /// glyph cache doesn't need to be the same size, or empty.
+    ///
+    /// The fonts should be the ones the glyphs will be drawn with.
+    /// Glyphs are identified by the hash of their font file, so the
+    /// glyphs of fonts that aren't in `fonts`, or whose files have
+    /// changed since the glyphs were saved, are skipped.
+    ///
+    /// Glyphs are saved with the settings they were rasterized with
+    /// (e.g. [`FontTexture::set_gamma`]), but the settings themselves
+    /// aren't saved, so the loaded glyphs are only used when drawing
+    /// with the same settings as when they were saved:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let darken = |coverage: u8| (coverage as f32 / 255.0).powf(0.7).mul_add(255.0, 0.5) as u8;
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 24.0, 0, Color::WHITE));
+    ///
+    /// let mut saved = Vec::new();
+    /// let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// font_texture.set_coverage_curve(darken);
+    /// font_texture.cache_glyphs(fonts, layout.glyphs()).unwrap();
+    /// font_texture.dump_cache(&mut saved).unwrap();
+    ///
+    /// let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// font_texture.set_coverage_curve(darken);
+    /// font_texture.load_cache(fonts, &saved[..]).unwrap();
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!(font_texture.last_frame_stats().rasterized_glyphs, 0);
+    ///
+    /// font_texture.set_coverage_curve(|coverage| coverage);
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!(font_texture.last_frame_stats().rasterized_glyphs, 4);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::CacheFile`] if reading fails, or
+    /// the data isn't a saved glyph cache. Returns a
+    /// [`FontTextureError::TextureUpdate`] if the texture cannot be
+    /// written to.
+    pub fn load_cache(
+        &mut self,
+        fonts: &[Font],
+        reader: impl Read,
+    ) -> Result<(), FontTextureError> {
+        let glyphs = crate::persist::read_glyphs(fonts, reader)
+            .map_err(|err| FontTextureError::CacheFile(err.to_string()))?;
+        crate::insert_rasterized_glyphs(&mut self.texture, &mut self.glyph_cache, glyphs)
+    }
+
+    /// Draws the glyph cache texture scaled into `dest`, with green
+    /// outlines around the empty areas the glyph allocator tracks.
+    /// Useful for seeing how well the glyphs are packed, and how
+    /// fragmented the empty space is, during development.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::Copy`] if drawing the texture
+    /// fails, or a [`FontTextureError::Draw`] if drawing the outlines
+    /// fails.
+    pub fn draw_debug_atlas<RT: RenderTarget>(
+        &self,
+        canvas: &mut Canvas<RT>,
+        dest: Rect,
+    ) -> Result<(), FontTextureError> {
+        crate::draw_debug_atlas(&self.texture, &self.glyph_cache, canvas, dest)
+    }
+
+    /// Packs the glyphs in the glyph cache again, from scratch.
+    ///
+    /// As glyphs are evicted and new ones are cached in their place,
+    /// the empty space in the cache gets chopped up into small gaps,
+    /// and big glyphs stop fitting in the cache even if there's
+    /// plenty of free space in total. Repacking moves the glyphs
+    /// together, leaving the free space in as few pieces as possible.
+    /// The glyphs aren't rasterized again, since the glyph cache
+    /// keeps a copy of the texture's pixels in memory.
+    ///
+    /// This is slow, as every glyph is moved and the whole texture is
+    /// uploaded again, so it's best done occasionally, e.g. based on
+    /// [`CacheStats::fragmentation`]:
+    ///
+    /// ```no_run
+    /// # let sdl_context = sdl2::init().unwrap();
+    /// # let video_subsystem = sdl_context.video().unwrap();
+    /// # let window = video_subsystem.window("", 800, 600).build().unwrap();
+    /// # let canvas = window.into_canvas().build().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = fontdue_sdl2::FontTexture::new(&texture_creator).unwrap();
+    /// if font_texture.cache_stats().fragmentation() > 0.5 {
+    ///     font_texture.defragment().unwrap();
+    /// }
+    /// ```
+    ///
+    /// For example, after evicting every other glyph, a glyph that
+    /// fits in the free space in total only fits between the
+    /// remaining glyphs once they're repacked:
+    ///
+    /// ```
+    /// # use fontdue::{Font, Metrics, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 64, 64).unwrap();
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("ABCDEFGHIJ", 20.0, 0, Color::WHITE));
+    /// font_texture.cache_glyphs(fonts, layout.glyphs()).unwrap();
+    /// for glyph in layout.glyphs().iter().skip(1).step_by(2) {
+    ///     font_texture.evict_glyph(glyph.key);
+    /// }
+    /// font_texture.defragment().unwrap();
+    ///
+    /// font_texture.set_glyph_override('\u{E000}', 20.0, || {
+    ///     let metrics = Metrics { width: 34, height: 58, ..Metrics::default() };
+    ///     (metrics, vec![0xFF; 34 * 58])
+    /// });
+    /// layout.clear();
+    /// layout.append(fonts, &TextStyle::with_user_data("\u{E000}", 20.0, 0, Color::WHITE));
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!(report.evicted_glyphs, 0); // Without defragmenting, 4 would be evicted.
+    /// assert_eq!(font_texture.cache_stats().glyph_count, 6);
+    /// ```
+    ///
+    /// In the rare case that some glyphs don't fit in the cache after
+    /// repacking, they're evicted.
+    ///
+    /// Draws waiting for [`FontTexture::flush_uploads`] use the glyphs
+    /// where they are, so they have to be flushed first:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::{FontTexture, FontTextureError};
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let mut font_texture = FontTexture::new(&texture_creator).unwrap();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 32.0, 0, Color::WHITE));
+    /// font_texture.set_deferred_uploads(true);
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert!(matches!(font_texture.defragment(), Err(FontTextureError::PendingDraws(_))));
+    /// assert!(matches!(
+    ///     font_texture.resize(&texture_creator, 512, 512),
+    ///     Err(FontTextureError::PendingDraws(_))
+    /// ));
+    ///
+    /// font_texture.flush_uploads(&mut canvas).unwrap();
+    /// font_texture.defragment().unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return a
+    /// [`FontTextureError::TextureUpdate`] if the texture cannot be
+    /// written to, and a [`FontTextureError::PendingDraws`] if there
+    /// are draws waiting for [`FontTexture::flush_uploads`].
+    pub fn defragment(&mut self) -> Result<(), FontTextureError> {
+        self.glyph_cache.defragment(&mut self.texture)
+    }
+
+    /// Replaces the glyph cache texture with a new `width`x`height`
+    /// one, e.g. to grow the cache when [`DrawReport::evicted_glyphs`]
+    /// shows that the text drawn every frame doesn't fit in it. The
+    /// new texture is created with `texture_creator`, which should be
+    /// the one the [`FontTexture`] was created with, and has the same
+    /// format and blend mode as the old one.
+    ///
+    /// The cached glyphs are moved into the new texture from the copy
+    /// of the glyph cache kept in memory, so none of them are
+    /// rasterized again. They're repacked like with
+    /// [`FontTexture::defragment`], and glyphs that don't fit, if
+    /// the new size is smaller, are evicted.
+    ///
+    /// ```no_run
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use sdl2::pixels::Color;
+    /// # let sdl_context = sdl2::init().unwrap();
+    /// # let video_subsystem = sdl_context.video().unwrap();
+    /// # let window = video_subsystem.window("", 800, 600).build().unwrap();
+    /// # let mut canvas = window.into_canvas().build().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// # let mut font_texture = fontdue_sdl2::FontTexture::new(&texture_creator).unwrap();
+    /// # let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// # layout.append(fonts, &TextStyle::with_user_data("Hello", 32.0, 0, Color::WHITE));
+    /// let report = font_texture.draw_text_checked(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// if report.evicted_glyphs > 0 {
+    ///     let size = font_texture.cache_stats().total_area;
+    ///     let side = (size as f32).sqrt() as u32 * 2;
+    ///     font_texture.resize(&texture_creator, side, side).unwrap();
+    /// }
+    /// ```
+    ///
+    /// The glyphs drawn after resizing look the same as before, and
+    /// the cached ones aren't rasterized again:
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(128, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 128, 128).unwrap();
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("Hello", 32.0, 0, Color::WHITE));
+    /// canvas.set_draw_color(Color::BLACK);
+    /// canvas.clear();
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let before = canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+    ///
+    /// canvas.clear();
+    /// font_texture.begin_frame();
+    /// font_texture.resize(&texture_creator, 256, 256).unwrap();
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!(font_texture.last_frame_stats().rasterized_glyphs, 0);
+    /// assert_eq!(font_texture.last_frame_stats().uploaded_bytes, 256 * 256 * 4);
+    /// assert_eq!(canvas.read_pixels(None, PixelFormatEnum::RGBA32).unwrap(), before);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FontTextureError::AtlasTooLarge`] or a
+    /// [`FontTextureError::TextureCreation`] if the new texture can't
+    /// be created (see [`FontTexture::with_size`]), or a
+    /// [`FontTextureError::TextureUpdate`] if it can't be written
+    /// to. Returns a [`FontTextureError::PendingDraws`] if there are
+    /// draws waiting for [`FontTexture::flush_uploads`]. The old
+    /// texture and glyphs are kept if the new texture can't be
+    /// created or written to.
+    pub fn resize<T>(
+        &mut self,
+        texture_creator: &'r TextureCreator<T>,
+        width: u32,
+        height: u32,
+    ) -> Result<(), FontTextureError> {
+        self.texture = crate::resize_font_texture(
+            &self.texture,
+            &mut self.glyph_cache,
+            texture_creator,
+            width,
+            height,
+        )?;
+        Ok(())
+    }
+
+    /// Returns statistics about the glyph cache, e.g. how many glyphs
+    /// are cached and how much space is left for new ones.
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 256, 256).unwrap();
+    /// let empty = font_texture.cache_stats();
+    /// assert_eq!(empty.glyph_count, 0);
+    /// assert_eq!(empty.free_area, 256 * 256);
+    /// assert_eq!(empty.largest_free_rect, Some(Rect::new(0, 0, 256, 256)));
+    ///
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("abc", 24.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let three = font_texture.cache_stats();
+    /// assert_eq!(three.glyph_count, 3);
+    /// assert!(three.free_area < empty.free_area);
+    ///
+    /// // Glyphs already in the cache don't change anything.
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// assert_eq!(font_texture.cache_stats(), three);
+    ///
+    /// layout.append(fonts, &TextStyle::with_user_data("de", 24.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let five = font_texture.cache_stats();
+    /// assert_eq!(five.glyph_count, 5);
+    /// assert!(five.free_area < three.free_area);
+    /// assert_eq!(five.total_area, 256 * 256);
+    /// ```
+    pub fn cache_stats(&self) -> CacheStats {
+        self.glyph_cache.stats()
+    }
+}
+
+impl FontTextureBuilder {
+    /// Creates the [`FontTexture`] with this configuration. See
+    /// [`FontTexture::new`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// See [`FontTexture::with_size`]. Additionally, a
+    /// [`FontTextureError::TextureCreation`] is returned if the
+    /// renderer doesn't support premultiplied alpha, if it was
+    /// enabled.
+    pub fn build<'r, T>(
+        &self,
+        texture_creator: &'r TextureCreator<T>,
+    ) -> Result<FontTexture<'r>, FontTextureError> {
+        let format = if self.native_format {
+            crate::native_glyph_cache_format(texture_creator)?
+        } else {
+            PixelFormatEnum::RGBA32
+        };
+        let texture = crate::create_font_texture(
+            texture_creator,
+            self.width,
+            self.height,
+            format,
+            self.texture_access,
+        )?;
+        let mut font_texture = FontTexture {
+            texture,
+            glyph_cache: GlyphCache::new(self.width, self.height, format),
+            settings: Settings::default(),
+        };
+        font_texture.glyph_cache.padding = self.padding;
+        font_texture.glyph_cache.rect_allocator =
+            RectAllocator::new_with_reserved(self.width, self.height, &self.reserved_areas);
+        font_texture.set_color_mode(self.color_mode);
+        font_texture.set_gamma(self.gamma);
+        font_texture.set_premultiplied_alpha(self.premultiplied_alpha)?;
+        if let Some(blend_mode) = self.blend_mode {
+            font_texture.set_blend_mode(blend_mode);
+        }
+        if let Some(scale_mode) = self.scale_mode {
+            font_texture.set_scale_mode(scale_mode)?;
+        }
+        font_texture.set_missing_glyph_style(self.missing_glyph_style);
+        Ok(font_texture)
+    }
+}
+
+/// A handle to a [`FontTexture`] shared with other handles, so that
+/// they all use the same glyph cache, but each with their own
+/// settings (e.g. [`FontTexture::set_color_mode`] or
+/// [`FontTexture::set_gamma`]).
+///
+/// Separate [`FontTexture`]s each cache their own copy of the glyphs
+/// they draw, so e.g. two UI panels drawing the same text in the same
+/// font would use twice the video memory. Handles created by cloning
+/// a [`SharedFontTexture`] instead share a single glyph cache texture,
+/// and glyphs drawn through one handle can be drawn through the
+/// others without rasterizing them again, as long as they're drawn
+/// with the same settings:
+///
+/// ```
+/// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+/// # use fontdue_sdl2::{FontTexture, SharedFontTexture};
+/// # use sdl2::pixels::{Color, PixelFormatEnum};
+/// # use sdl2::surface::Surface;
+/// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+/// # let mut canvas = surface.into_canvas().unwrap();
+/// # let texture_creator = canvas.texture_creator();
+/// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+/// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+/// # let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+/// # layout.append(fonts, &TextStyle::with_user_data("Hi", 32.0, 0, Color::WHITE));
+/// let mut sidebar = SharedFontTexture::new(FontTexture::new(&texture_creator).unwrap());
+/// let mut toolbar = sidebar.clone();
+/// sidebar.borrow_mut().draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+/// toolbar.borrow_mut().draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+/// // One cache entry each for "H" and "i", shared by both handles.
+/// assert_eq!(toolbar.borrow_mut().cache_stats().glyph_count, 2);
+/// ```
+///
+/// Note that the settings which change the glyph cache texture itself
+/// ([`FontTexture::set_premultiplied_alpha`],
+/// [`FontTexture::set_fixed_raster_size`], and
+/// [`FontTexture::set_blend_mode`]) affect every handle, so they
+/// should be the same for all of them.
+pub struct SharedFontTexture<'r> {
+    font_texture: Rc<RefCell<FontTexture<'r>>>,
+    settings: Settings,
+}
+
+impl<'r> SharedFontTexture<'r> {
+    /// Creates the first handle to the [`FontTexture`], with its
+    /// current settings. Clone the handle to create more.
+    pub fn new(font_texture: FontTexture<'r>) -> SharedFontTexture<'r> {
+        SharedFontTexture {
+            settings: font_texture.settings,
+            font_texture: Rc::new(RefCell::new(font_texture)),
+        }
+    }
+
+    /// Mutably borrows the shared [`FontTexture`], for drawing or
+    /// changing this handle's settings. While borrowed, the
+    /// [`FontTexture`] uses this handle's settings, and any changes
+    /// to them are kept in this handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`FontTexture`] is already borrowed through
+    /// another handle.
+    pub fn borrow_mut(&mut self) -> SharedFontTextureMut<'_, 'r> {
+        let mut font_texture = self.font_texture.borrow_mut();
+        mem::swap(&mut font_texture.settings, &mut self.settings);
+        SharedFontTextureMut {
+            font_texture,
+            settings: &mut self.settings,
+        }
+    }
+}
+
+/// Creates a new handle to the same [`FontTexture`], starting with
+/// the same settings as this one.
+impl<'r> Clone for SharedFontTexture<'r> {
+    fn clone(&self) -> SharedFontTexture<'r> {
+        SharedFontTexture {
+            font_texture: Rc::clone(&self.font_texture),
+            settings: self.settings,
+        }
+    }
+}
+
+/// A [`FontTexture`] borrowed through a [`SharedFontTexture`] handle,
+/// see [`SharedFontTexture::borrow_mut`].
+pub struct SharedFontTextureMut<'a, 'r> {
+    font_texture: RefMut<'a, FontTexture<'r>>,
+    /// Holds the settings the [`FontTexture`] had before it was
+    /// borrowed, while it uses the handle's settings.
+    settings: &'a mut Settings,
+}
+
+impl<'a, 'r> Deref for SharedFontTextureMut<'a, 'r> {
+    type Target = FontTexture<'r>;
+
+    fn deref(&self) -> &FontTexture<'r> {
+        &self.font_texture
+    }
+}
+
+impl<'a, 'r> DerefMut for SharedFontTextureMut<'a, 'r> {
+    fn deref_mut(&mut self) -> &mut FontTexture<'r> {
+        &mut self.font_texture
+    }
+}
+
+impl<'a, 'r> Drop for SharedFontTextureMut<'a, 'r> {
+    fn drop(&mut self) {
+        // Puts the handle's (possibly changed) settings back into the
+        // handle.
+        mem::swap(&mut self.font_texture.settings, self.settings);
+    }
+}
+
+/// A [`FontTexture`] together with a single font and a [`Layout`],
+/// for drawing a string with one call, e.g. in quick prototypes:
+///
+/// ```
+/// # use fontdue::Font;
+/// # use fontdue_sdl2::SimpleText;
+/// # use sdl2::pixels::{Color, PixelFormatEnum};
+/// # use sdl2::surface::Surface;
+/// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+/// # let mut canvas = surface.into_canvas().unwrap();
+/// # let texture_creator = canvas.texture_creator();
+/// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+/// let font = Font::from_bytes(font, Default::default()).unwrap();
+/// let mut text = SimpleText::new(&texture_creator, font).unwrap();
+/// text.draw(&mut canvas, "Hello world!", 10.0, 10.0, 32.0, Color::WHITE).unwrap();
+/// assert_eq!(text.font_texture.cache_stats().glyph_count, 8);
+/// ```
+///
+/// For text in multiple fonts, sizes, or colors, or with more control
+/// over the layout, use a [`FontTexture`] and a [`Layout`] directly.
+pub struct SimpleText<'r> {
+    /// The [`FontTexture`] the text is drawn with, e.g. for changing
+    /// its settings.
+    pub font_texture: FontTexture<'r>,
+    fonts: [Font; 1],
+    layout: Layout<Color>,
+}
+
+impl<'r> SimpleText<'r> {
+    /// Creates a [`SimpleText`] drawing with the font, with a new
+    /// [`FontTexture`] (see [`FontTexture::new`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`FontTexture::new`].
+    pub fn new<T>(
+        texture_creator: &'r TextureCreator<T>,
+        font: Font,
+    ) -> Result<SimpleText<'r>, FontTextureError> {
+        let font_texture = FontTexture::new(texture_creator)?;
+        Ok(SimpleText::with_font_texture(font_texture, font))
+    }
+
+    /// Creates a [`SimpleText`] drawing with the font and the
+    /// [`FontTexture`], e.g. one created with a
+    /// [`FontTextureBuilder`].
+    pub fn with_font_texture(font_texture: FontTexture<'r>, font: Font) -> SimpleText<'r> {
+        SimpleText {
+            font_texture,
+            fonts: [font],
+            layout: Layout::new(CoordinateSystem::PositiveYDown),
+        }
+    }
+
+    /// Returns the font the text is drawn with.
+    pub fn font(&self) -> &Font {
+        &self.fonts[0]
+    }
+
+    /// Lays out the text with its top left corner at (`x`, `y`), at
+    /// the size (in pixels), and draws it onto the canvas with
+    /// [`FontTexture::draw_text`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`FontTexture::draw_text`].
+    pub fn draw<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        text: &str,
+        x: f32,
+        y: f32,
+        size: f32,
+        color: Color,
+    ) -> Result<(), FontTextureError> {
+        self.layout.reset(&LayoutSettings {
+            x,
+            y,
+            ..LayoutSettings::default()
+        });
+        self.layout.append(
+            &self.fonts,
+            &TextStyle::with_user_data(text, size, 0, color),
+        );
+        self.font_texture
+            .draw_text(canvas, &self.fonts, self.layout.glyphs())
     }
 }
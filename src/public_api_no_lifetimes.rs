@@ -1,36 +1,932 @@
-use crate::RectAllocator;
-use fontdue::layout::GlyphPosition;
-use fontdue::Font;
-use sdl2::pixels::Color;
-use sdl2::render::{Canvas, RenderTarget, Texture, TextureCreator};
+use crate::{
+    AppendedText, CacheStats, ColorMode, Decoration, DrawOptions, DrawReport, FitMode,
+    FontTextureBuilder, FontTextureError, FrameStats, GlyphCache, GlyphRasterizer,
+    MissingGlyphStyle, RasterizedGlyphs, RectAllocator, Rounding, ScaleMode, Settings,
+    UnderlineMetrics, Warning,
+};
+use fontdue::layout::{
+    CoordinateSystem, GlyphPosition, GlyphRasterConfig, Layout, LayoutSettings, TextStyle,
+};
+use fontdue::{Font, Metrics};
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{BlendMode, Canvas, RenderTarget, Texture, TextureAccess, TextureCreator};
+use std::borrow::Borrow;
+use std::cell::{RefCell, RefMut};
+use std::io::{Read, Write};
+use std::mem;
+use std::ops::{Deref, DerefMut, Range};
+use std::rc::Rc;
 
 pub struct FontTexture {
     pub texture: Texture,
-    rect_allocator: RectAllocator,
+    glyph_cache: GlyphCache,
+    settings: Settings,
 }
 
 impl FontTexture {
-    pub fn new<T>(texture_creator: &TextureCreator<T>) -> Result<FontTexture, String> {
-        let texture = crate::create_font_texture(texture_creator)?;
-        let rect_allocator = RectAllocator::new(1024, 1024);
+    pub fn new<T>(texture_creator: &TextureCreator<T>) -> Result<FontTexture, FontTextureError> {
+        let size = crate::DEFAULT_TEXTURE_SIZE;
+        FontTexture::with_size(texture_creator, size, size)
+    }
+
+    pub fn with_size<T>(
+        texture_creator: &TextureCreator<T>,
+        width: u32,
+        height: u32,
+    ) -> Result<FontTexture, FontTextureError> {
+        let format = PixelFormatEnum::RGBA32;
+        let texture = crate::create_font_texture(
+            texture_creator,
+            width,
+            height,
+            format,
+            TextureAccess::Streaming,
+        )?;
+        let glyph_cache = GlyphCache::new(width, height, format);
+        Ok(FontTexture {
+            texture,
+            glyph_cache,
+            settings: Settings::default(),
+        })
+    }
+
+    pub fn max_texture_size<T>(
+        texture_creator: &TextureCreator<T>,
+    ) -> Result<Option<(u32, u32)>, FontTextureError> {
+        crate::max_texture_size(texture_creator)
+    }
+
+    pub fn with_native_format<T>(
+        texture_creator: &TextureCreator<T>,
+        width: u32,
+        height: u32,
+    ) -> Result<FontTexture, FontTextureError> {
+        let format = crate::native_glyph_cache_format(texture_creator)?;
+        let texture = crate::create_font_texture(
+            texture_creator,
+            width,
+            height,
+            format,
+            TextureAccess::Streaming,
+        )?;
+        let glyph_cache = GlyphCache::new(width, height, format);
+        Ok(FontTexture {
+            texture,
+            glyph_cache,
+            settings: Settings::default(),
+        })
+    }
+
+    pub fn from_texture(mut texture: Texture) -> Result<FontTexture, FontTextureError> {
+        let (width, height, format) = crate::adopt_font_texture(&mut texture)?;
         Ok(FontTexture {
             texture,
-            rect_allocator,
+            glyph_cache: GlyphCache::new(width, height, format),
+            settings: Settings::default(),
         })
     }
 
+    pub fn texture_dimensions(&self) -> (u32, u32) {
+        let packer = &self.glyph_cache.rect_allocator.packer;
+        (packer.width(), packer.height())
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        let (width, height) = self.texture_dimensions();
+        width as usize * height as usize * 4
+    }
+
+    pub fn set_color_mode(&mut self, color_mode: ColorMode) {
+        self.settings.color_mode = color_mode;
+    }
+
+    pub fn set_premultiplied_alpha(
+        &mut self,
+        premultiplied_alpha: bool,
+    ) -> Result<(), FontTextureError> {
+        if premultiplied_alpha {
+            crate::blend::set_premultiplied_blend_mode(&mut self.texture)
+                .map_err(FontTextureError::TextureCreation)?;
+        } else {
+            self.texture.set_blend_mode(BlendMode::Blend);
+        }
+        self.settings.raster.premultiplied_alpha = premultiplied_alpha;
+        Ok(())
+    }
+
+    pub fn set_gamma(&mut self, gamma: f32) {
+        self.settings.raster.gamma_bits = gamma.to_bits();
+        self.settings.update_coverage_table();
+    }
+
+    pub fn set_background_hint(&mut self, background: Option<Color>) {
+        self.settings.raster.background = background.map(|color| color.rgb());
+    }
+
+    pub fn set_coverage_curve(&mut self, curve: impl Fn(u8) -> u8) {
+        self.settings.set_coverage_curve(curve);
+    }
+
+    pub fn set_monochrome(&mut self, monochrome: bool) {
+        self.settings.raster.monochrome = monochrome;
+        self.settings.update_coverage_table();
+    }
+
+    pub fn set_supersampling(&mut self, factor: u8) {
+        self.settings.raster.supersample = factor.clamp(1, 4);
+    }
+
+    pub fn set_dpi_scale(&mut self, scale: f32) {
+        self.settings.dpi_scale = scale;
+    }
+
+    pub fn set_fixed_raster_size(&mut self, px: Option<f32>) -> Result<(), FontTextureError> {
+        if px.is_some() {
+            let linear = sdl2::sys::SDL_ScaleMode::SDL_ScaleModeLinear;
+            crate::set_scale_mode(&mut self.texture, linear)?;
+        }
+        self.settings.fixed_raster_px = px;
+        Ok(())
+    }
+
+    pub fn set_min_split_size(&mut self, min_split_size: u32) {
+        self.glyph_cache
+            .rect_allocator
+            .packer
+            .set_min_split_size(min_split_size);
+    }
+
+    pub fn set_default_color(&mut self, color: Option<Color>) {
+        self.settings.default_color = color;
+    }
+
+    pub fn set_retain_metrics(&mut self, retain_metrics: bool) {
+        self.settings.retain_metrics = retain_metrics;
+    }
+
+    pub fn set_scale_mode(&mut self, scale_mode: ScaleMode) -> Result<(), FontTextureError> {
+        crate::set_scale_mode(&mut self.texture, scale_mode.to_sdl())
+    }
+
+    pub fn set_raster_budget(&mut self, budget: Option<usize>) {
+        self.settings.raster_budget = budget;
+    }
+
+    pub fn set_batched(&mut self, batched: bool) {
+        self.settings.batched = batched;
+    }
+
+    pub fn set_warning_handler(&mut self, handler: impl FnMut(Warning) + 'static) {
+        self.glyph_cache.warning_handler = Some(Box::new(handler));
+    }
+
+    pub fn set_rounding(&mut self, rounding: Option<Rounding>) {
+        self.settings.rounding = rounding;
+    }
+
+    pub fn set_deferred_uploads(&mut self, deferred: bool) {
+        self.settings.deferred_uploads = deferred;
+    }
+
+    pub fn set_glyph_override(
+        &mut self,
+        character: char,
+        px: f32,
+        rasterize: impl Fn() -> (Metrics, Vec<u8>) + 'static,
+    ) {
+        crate::set_glyph_override(&mut self.glyph_cache, character, px, Box::new(rasterize));
+    }
+
+    pub fn set_missing_glyph_style(&mut self, style: MissingGlyphStyle) {
+        self.settings.missing_glyph_style = style;
+    }
+
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.texture.set_blend_mode(blend_mode);
+    }
+
     pub fn draw_text<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: impl IntoIterator<Item = impl Borrow<GlyphPosition<Color>>>,
+    ) -> Result<(), FontTextureError> {
+        let mut glyph_buffer = mem::take(&mut self.glyph_cache.glyph_buffer);
+        glyph_buffer.clear();
+        glyph_buffer.extend(glyphs.into_iter().map(|glyph| *glyph.borrow()));
+        let result = crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            &glyph_buffer,
+            |color: &Color| *color,
+            DrawOptions::default(),
+        );
+        self.glyph_cache.glyph_buffer = glyph_buffer;
+        result.map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_text_with_user_data<RT: RenderTarget, U: Copy>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<U>],
+        color_of: impl Fn(&U) -> Color,
+    ) -> Result<(), FontTextureError> {
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            color_of,
+            DrawOptions::default(),
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_texts<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        texts: &[&[GlyphPosition<Color>]],
+    ) -> Result<(), FontTextureError> {
+        let glyphs = texts.iter().flat_map(|glyphs| glyphs.iter());
+        self.draw_text(canvas, fonts, glyphs)
+    }
+
+    pub fn draw_text_checked<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<DrawReport, FontTextureError> {
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            DrawOptions::default(),
+        )
+    }
+
+    pub fn draw_text_at<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        offset: (i32, i32),
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            offset,
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_text_clipped<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        clip: Rect,
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            clip: Some(clip),
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_text_scaled<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        scale: f32,
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            scale: Some(scale),
+            ..DrawOptions::default()
+        };
+        let settings = crate::canvas_scale_settings(&self.settings, canvas);
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_text_fitted<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        target: Rect,
+        mode: FitMode,
+    ) -> Result<(), FontTextureError> {
+        let options = match crate::fitted_draw_options(glyphs, target, mode) {
+            Some(options) => options,
+            None => return Ok(()),
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_text_matrix<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        matrix: [f32; 6],
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            matrix: Some(matrix),
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_text_transformed<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        center: Point,
+        angle: f64,
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            rotation: Some((center, angle)),
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_text_with_glyph_angles<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        angles: &[f64],
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            glyph_angles: angles,
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_text_with_shadow<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        shadow_offset: (i32, i32),
+        shadow_color: Color,
+    ) -> Result<(), FontTextureError> {
+        let shadow_options = DrawOptions {
+            offset: shadow_offset,
+            color_override: Some(shadow_color),
+            ..DrawOptions::default()
+        };
+        crate::draw_text(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            shadow_options,
+        )?;
+        self.draw_text(canvas, fonts, glyphs)
+    }
+
+    pub fn draw_text_gradient<RT: RenderTarget>(
         &mut self,
         canvas: &mut Canvas<RT>,
         fonts: &[Font],
         glyphs: &[GlyphPosition<Color>],
-    ) -> Result<(), String> {
+        start_color: Color,
+        end_color: Color,
+    ) -> Result<(), FontTextureError> {
+        let options = DrawOptions {
+            gradient: Some((start_color, end_color)),
+            ..DrawOptions::default()
+        };
         crate::draw_text(
             &mut self.texture,
-            &mut self.rect_allocator,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            options,
+        )
+        .map(|report| self.glyph_cache.reuse_report(report))
+    }
+
+    pub fn draw_text_decorated<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        decorations: &[Decoration],
+    ) -> Result<(), FontTextureError> {
+        self.draw_text(canvas, fonts, glyphs)?;
+        crate::draw_decorations(canvas, &self.glyph_cache, fonts, glyphs, decorations)
+    }
+
+    pub fn bind_fonts(&mut self, fonts: &[Font]) {
+        crate::bind_fonts(&mut self.glyph_cache, fonts);
+    }
+
+    pub fn set_underline_metrics(&mut self, font: &Font, metrics: Option<UnderlineMetrics>) {
+        match metrics {
+            Some(metrics) => {
+                self.glyph_cache
+                    .underline_metrics
+                    .insert(font.file_hash(), metrics);
+            }
+            None => {
+                self.glyph_cache.underline_metrics.remove(&font.file_hash());
+            }
+        }
+    }
+
+    pub fn draw_text_with_highlight<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        ranges: &[Range<usize>],
+        highlight_color: Color,
+    ) -> Result<(), FontTextureError> {
+        crate::draw_highlights(canvas, fonts, glyphs, ranges, highlight_color)?;
+        self.draw_text(canvas, fonts, glyphs)
+    }
+
+    pub fn draw_text_ellipsized<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+        max_width: f32,
+    ) -> Result<(), FontTextureError> {
+        let glyphs = crate::ellipsize(fonts, glyphs, max_width);
+        self.draw_text(canvas, fonts, &glyphs)
+    }
+
+    pub fn draw_text_merged<RT: RenderTarget, T>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        texture_creator: &TextureCreator<T>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<(), FontTextureError> {
+        crate::draw_text_merged(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            texture_creator,
+            fonts,
+            glyphs,
+        )
+    }
+
+    pub fn render_to_texture<RT: RenderTarget, T>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        texture_creator: &TextureCreator<T>,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<Option<Texture>, FontTextureError> {
+        crate::render_to_texture(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            canvas,
+            texture_creator,
+            fonts,
+            glyphs,
+        )
+    }
+
+    pub fn draw_text_appended<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        target: &mut Texture,
+        appended: &mut AppendedText,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<DrawReport, FontTextureError> {
+        crate::draw_text_appended(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
             canvas,
+            target,
+            appended,
+            fonts,
+            glyphs,
+        )
+    }
+
+    pub fn cache_glyphs(
+        &mut self,
+        fonts: &[Font],
+        glyphs: &[GlyphPosition<Color>],
+    ) -> Result<(), FontTextureError> {
+        let buffers = crate::cache_glyphs(
+            &mut self.glyph_cache,
+            &self.settings,
+            fonts,
+            glyphs,
+            |color: &Color| *color,
+            &DrawOptions::default(),
+        );
+        self.glyph_cache.draw_buffers = buffers;
+        self.glyph_cache.upload(&mut self.texture)
+    }
+
+    pub fn flush_uploads<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+    ) -> Result<(), FontTextureError> {
+        crate::flush_uploads(&mut self.texture, &mut self.glyph_cache, canvas).map(|_| ())
+    }
+
+    pub fn glyph_rect(
+        &mut self,
+        fonts: &[Font],
+        font_index: usize,
+        character: char,
+        px: f32,
+        color: Color,
+    ) -> Result<Option<Rect>, FontTextureError> {
+        crate::glyph_rect(
+            &mut self.texture,
+            &mut self.glyph_cache,
+            &self.settings,
+            fonts,
+            font_index,
+            character,
+            px,
+            color,
+        )
+    }
+
+    pub fn update_reserved_area(
+        &mut self,
+        rect: Rect,
+        rgba_pixels: &[u8],
+    ) -> Result<(), FontTextureError> {
+        crate::update_reserved_area(&mut self.texture, &mut self.glyph_cache, rect, rgba_pixels)
+    }
+
+    pub fn glyph_metrics(&self, key: GlyphRasterConfig, color: Color) -> Option<Metrics> {
+        crate::glyph_metrics(&self.glyph_cache, &self.settings, key, color)
+    }
+
+    pub fn cached_keys(&self) -> impl Iterator<Item = (GlyphRasterConfig, Color)> + '_ {
+        self.glyph_cache
+            .rect_allocator
+            .reservations()
+            .map(|(key, _)| {
+                let (r, g, b) = key.rgb;
+                (key.glyph, Color::RGB(r, g, b))
+            })
+    }
+
+    pub fn hit_test(&self, point: Point) -> Option<usize> {
+        crate::hit_test(&self.glyph_cache, point)
+    }
+
+    pub fn last_frame_stats(&self) -> FrameStats {
+        self.glyph_cache.frame_stats
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.glyph_cache.frame_stats = FrameStats::default();
+        self.glyph_cache.frame_started = true;
+    }
+
+    pub fn is_cached(&self, key: GlyphRasterConfig, color: Color) -> bool {
+        crate::is_cached(&self.glyph_cache, &self.settings, key, color)
+    }
+
+    pub fn can_fit(&self, fonts: &[Font], glyphs: &[GlyphPosition<Color>]) -> bool {
+        crate::can_fit(
+            &self.glyph_cache,
+            &self.settings,
             fonts,
             glyphs,
+            |color: &Color| *color,
+            None,
         )
     }
+
+    pub fn prune_unused(&mut self, max_age: u32) -> usize {
+        self.glyph_cache.rect_allocator.prune(max_age as u64)
+    }
+
+    pub fn evict_glyph(&mut self, key: GlyphRasterConfig) -> usize {
+        self.glyph_cache
+            .rect_allocator
+            .evict(|cached| cached.glyph == key)
+    }
+
+    pub fn evict_font(&mut self, font: &Font) -> usize {
+        let font_hash = font.file_hash();
+        self.glyph_cache
+            .rect_allocator
+            .evict(|cached| cached.glyph.font_hash == font_hash)
+    }
+
+    pub fn rasterizer(&self) -> GlyphRasterizer {
+        GlyphRasterizer {
+            settings: self.settings,
+        }
+    }
+
+    pub fn insert_rasterized(
+        &mut self,
+        rasterized: RasterizedGlyphs,
+    ) -> Result<(), FontTextureError> {
+        crate::insert_rasterized_glyphs(&mut self.texture, &mut self.glyph_cache, rasterized)
+    }
+
+    pub fn dump_cache(&self, writer: impl Write) -> Result<(), FontTextureError> {
+        crate::persist::write_glyph_cache(&self.glyph_cache, writer)
+            .map_err(|err| FontTextureError::CacheFile(err.to_string()))
+    }
+
+    pub fn load_cache(
+        &mut self,
+        fonts: &[Font],
+        reader: impl Read,
+    ) -> Result<(), FontTextureError> {
+        let glyphs = crate::persist::read_glyphs(fonts, reader)
+            .map_err(|err| FontTextureError::CacheFile(err.to_string()))?;
+        crate::insert_rasterized_glyphs(&mut self.texture, &mut self.glyph_cache, glyphs)
+    }
+
+    pub fn draw_debug_atlas<RT: RenderTarget>(
+        &self,
+        canvas: &mut Canvas<RT>,
+        dest: Rect,
+    ) -> Result<(), FontTextureError> {
+        crate::draw_debug_atlas(&self.texture, &self.glyph_cache, canvas, dest)
+    }
+
+    pub fn defragment(&mut self) -> Result<(), FontTextureError> {
+        self.glyph_cache.defragment(&mut self.texture)
+    }
+
+    pub fn resize<T>(
+        &mut self,
+        texture_creator: &TextureCreator<T>,
+        width: u32,
+        height: u32,
+    ) -> Result<(), FontTextureError> {
+        let texture = crate::resize_font_texture(
+            &self.texture,
+            &mut self.glyph_cache,
+            texture_creator,
+            width,
+            height,
+        )?;
+        let old_texture = mem::replace(&mut self.texture, texture);
+        // Safety: the old texture isn't used anymore, and the
+        // renderer it was created with is still alive, since the new
+        // texture was just created with it.
+        unsafe { old_texture.destroy() };
+        Ok(())
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.glyph_cache.stats()
+    }
+}
+
+impl FontTextureBuilder {
+    pub fn build<T>(
+        &self,
+        texture_creator: &TextureCreator<T>,
+    ) -> Result<FontTexture, FontTextureError> {
+        let format = if self.native_format {
+            crate::native_glyph_cache_format(texture_creator)?
+        } else {
+            PixelFormatEnum::RGBA32
+        };
+        let texture = crate::create_font_texture(
+            texture_creator,
+            self.width,
+            self.height,
+            format,
+            self.texture_access,
+        )?;
+        let mut font_texture = FontTexture {
+            texture,
+            glyph_cache: GlyphCache::new(self.width, self.height, format),
+            settings: Settings::default(),
+        };
+        font_texture.glyph_cache.padding = self.padding;
+        font_texture.glyph_cache.rect_allocator =
+            RectAllocator::new_with_reserved(self.width, self.height, &self.reserved_areas);
+        font_texture.set_color_mode(self.color_mode);
+        font_texture.set_gamma(self.gamma);
+        font_texture.set_premultiplied_alpha(self.premultiplied_alpha)?;
+        if let Some(blend_mode) = self.blend_mode {
+            font_texture.set_blend_mode(blend_mode);
+        }
+        if let Some(scale_mode) = self.scale_mode {
+            font_texture.set_scale_mode(scale_mode)?;
+        }
+        font_texture.set_missing_glyph_style(self.missing_glyph_style);
+        Ok(font_texture)
+    }
+}
+
+pub struct SharedFontTexture {
+    font_texture: Rc<RefCell<FontTexture>>,
+    settings: Settings,
+}
+
+impl SharedFontTexture {
+    pub fn new(font_texture: FontTexture) -> SharedFontTexture {
+        SharedFontTexture {
+            settings: font_texture.settings,
+            font_texture: Rc::new(RefCell::new(font_texture)),
+        }
+    }
+
+    pub fn borrow_mut(&mut self) -> SharedFontTextureMut<'_> {
+        let mut font_texture = self.font_texture.borrow_mut();
+        mem::swap(&mut font_texture.settings, &mut self.settings);
+        SharedFontTextureMut {
+            font_texture,
+            settings: &mut self.settings,
+        }
+    }
+}
+
+impl Clone for SharedFontTexture {
+    fn clone(&self) -> SharedFontTexture {
+        SharedFontTexture {
+            font_texture: Rc::clone(&self.font_texture),
+            settings: self.settings,
+        }
+    }
+}
+
+pub struct SharedFontTextureMut<'a> {
+    font_texture: RefMut<'a, FontTexture>,
+    settings: &'a mut Settings,
+}
+
+impl<'a> Deref for SharedFontTextureMut<'a> {
+    type Target = FontTexture;
+
+    fn deref(&self) -> &FontTexture {
+        &self.font_texture
+    }
+}
+
+impl<'a> DerefMut for SharedFontTextureMut<'a> {
+    fn deref_mut(&mut self) -> &mut FontTexture {
+        &mut self.font_texture
+    }
+}
+
+impl<'a> Drop for SharedFontTextureMut<'a> {
+    fn drop(&mut self) {
+        // Puts the handle's (possibly changed) settings back into the
+        // handle.
+        mem::swap(&mut self.font_texture.settings, self.settings);
+    }
+}
+
+pub struct SimpleText {
+    pub font_texture: FontTexture,
+    fonts: [Font; 1],
+    layout: Layout<Color>,
+}
+
+impl SimpleText {
+    pub fn new<T>(
+        texture_creator: &TextureCreator<T>,
+        font: Font,
+    ) -> Result<SimpleText, FontTextureError> {
+        let font_texture = FontTexture::new(texture_creator)?;
+        Ok(SimpleText::with_font_texture(font_texture, font))
+    }
+
+    pub fn with_font_texture(font_texture: FontTexture, font: Font) -> SimpleText {
+        SimpleText {
+            font_texture,
+            fonts: [font],
+            layout: Layout::new(CoordinateSystem::PositiveYDown),
+        }
+    }
+
+    pub fn font(&self) -> &Font {
+        &self.fonts[0]
+    }
+
+    pub fn draw<RT: RenderTarget>(
+        &mut self,
+        canvas: &mut Canvas<RT>,
+        text: &str,
+        x: f32,
+        y: f32,
+        size: f32,
+        color: Color,
+    ) -> Result<(), FontTextureError> {
+        self.layout.reset(&LayoutSettings {
+            x,
+            y,
+            ..LayoutSettings::default()
+        });
+        self.layout.append(
+            &self.fonts,
+            &TextStyle::with_user_data(text, size, 0, color),
+        );
+        self.font_texture
+            .draw_text(canvas, &self.fonts, self.layout.glyphs())
+    }
 }
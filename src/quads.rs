@@ -0,0 +1,91 @@
+//! Drawing textured quadrilaterals with `SDL_RenderGeometry`, for
+//! copying glyphs into shapes that [Canvas::copy_ex] can't produce,
+//! e.g. sheared for faux-italic text.
+//!
+//! [Canvas::copy_ex]: sdl2::render::Canvas::copy_ex
+
+use sdl2::pixels::Color;
+use sdl2::rect::{FPoint, FRect};
+use sdl2::sys;
+use std::os::raw::c_int;
+
+/// A quadrilateral on the canvas to draw a part of a texture into.
+pub(crate) struct Quad {
+    /// The corners on the canvas, clockwise from the top-left corner
+    /// of the texture's part.
+    pub corners: [FPoint; 4],
+    /// The part of the texture, in normalized texture coordinates.
+    pub tex_rect: FRect,
+    /// The color and alpha the texture is modulated with. The
+    /// texture's own color and alpha modulation is ignored.
+    pub modulation: Color,
+}
+
+/// The quads to draw with [render_quads], and the vertices and
+/// indices they're turned into. Kept between draws, so that drawing
+/// doesn't allocate once the buffers are big enough.
+#[derive(Default)]
+pub(crate) struct QuadBuffers {
+    pub quads: Vec<Quad>,
+    vertices: Vec<sys::SDL_Vertex>,
+    indices: Vec<c_int>,
+}
+
+/// Draws the quads in the buffers onto the renderer's target with the
+/// texture, as two triangles each. The texture's blend mode is used.
+///
+/// # Safety
+///
+/// The renderer and texture pointers must be valid, and the texture
+/// must have been created for the renderer.
+pub(crate) unsafe fn render_quads(
+    renderer: *mut sys::SDL_Renderer,
+    texture: *mut sys::SDL_Texture,
+    buffers: &mut QuadBuffers,
+) -> Result<(), String> {
+    let QuadBuffers {
+        quads,
+        vertices,
+        indices,
+    } = buffers;
+    vertices.clear();
+    indices.clear();
+    for quad in quads.iter() {
+        let Color { r, g, b, a } = quad.modulation;
+        let tex = quad.tex_rect;
+        let tex_corners = [
+            (tex.left(), tex.top()),
+            (tex.right(), tex.top()),
+            (tex.right(), tex.bottom()),
+            (tex.left(), tex.bottom()),
+        ];
+        let first = vertices.len() as c_int;
+        for (corner, (u, v)) in quad.corners.iter().zip(tex_corners) {
+            vertices.push(sys::SDL_Vertex {
+                position: sys::SDL_FPoint {
+                    x: corner.x(),
+                    y: corner.y(),
+                },
+                color: sys::SDL_Color { r, g, b, a },
+                tex_coord: sys::SDL_FPoint { x: u, y: v },
+            });
+        }
+        indices.extend_from_slice(&[first, first + 1, first + 2, first + 2, first + 3, first]);
+    }
+    if vertices.is_empty() {
+        return Ok(());
+    }
+    let result = sys::SDL_RenderGeometry(
+        renderer,
+        texture,
+        vertices.as_ptr(),
+        vertices.len() as c_int,
+        indices.as_ptr(),
+        indices.len() as c_int,
+    );
+    if result != 0 {
+        Err(sdl2::get_error())
+    } else {
+        Ok(())
+    }
+}
@@ -1,133 +1,363 @@
-use fontdue::layout::{GlyphPosition, GlyphRasterConfig};
-use sdl2::pixels::Color;
+use fontdue::layout::GlyphRasterConfig;
+use fontdue::Metrics;
 use sdl2::rect::Rect;
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-struct GlyphKey {
-    glyph: GlyphRasterConfig,
-    color: Color,
+use crate::{CacheStats, RasterSettings, RectPacker};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlyphKey {
+    /// Includes the hash of the font's file, which tells apart glyphs
+    /// with the same index from different fonts.
+    pub glyph: GlyphRasterConfig,
+    /// The RGB of the color the glyph was rasterized with. Alpha is
+    /// applied when drawing, so it doesn't need a separate entry.
+    pub rgb: (u8, u8, u8),
+    pub raster_settings: RasterSettings,
+    /// Whether the glyph was rasterized with separate coverage for
+    /// each subpixel.
+    pub subpixel: bool,
+    /// The character whose glyph override the glyph was rasterized
+    /// from instead of the font, see
+    /// [`FontTexture::set_glyph_override`](crate::FontTexture::set_glyph_override).
+    pub override_char: Option<char>,
 }
 
 pub enum CacheReservation {
     AlreadyRasterized(Rect),
     EmptySpace(Rect),
+    /// The glyph has already been rasterized, and it turned out to
+    /// have no coverage, so there's nothing to draw.
+    Blank,
     OutOfSpace,
+    /// The glyph is bigger than the whole texture, so it can't fit
+    /// no matter how many glyphs are evicted.
+    TooLargeForAtlas,
+}
+
+/// A glyph's area in the texture, and when it was last used.
+#[derive(Clone)]
+struct Reservation {
+    rect: Rect,
+    /// The [RectAllocator::draw_count] at the time of the last draw
+    /// that used this glyph.
+    last_used: u64,
+    /// The metrics of the glyph's bitmap, if they were retained, see
+    /// [RectAllocator::set_metrics].
+    metrics: Option<Metrics>,
 }
 
+#[derive(Clone)]
 pub struct RectAllocator {
-    empty_rects: Vec<Rect>,
-    reserved_rects: HashMap<GlyphKey, Rect>,
+    pub packer: RectPacker,
+    reserved_rects: HashMap<GlyphKey, Reservation>,
+    blank_glyphs: HashSet<GlyphKey>,
+    /// Incremented at the start of every draw, see
+    /// [RectAllocator::begin_draw].
+    draw_count: u64,
+    /// The amount of glyphs evicted to make space since the last
+    /// [RectAllocator::begin_draw].
+    draw_evictions: usize,
+    /// The rects of removed glyphs which are kept allocated until
+    /// [RectAllocator::release_frees], or None if rects are freed
+    /// right away, see [RectAllocator::hold_frees].
+    held_frees: Option<Vec<Rect>>,
 }
 
 impl RectAllocator {
     pub fn new(width: u32, height: u32) -> RectAllocator {
+        RectAllocator::new_with_reserved(width, height, &[])
+    }
+
+    /// Creates an allocator which never places glyphs in the reserved
+    /// areas, see [RectPacker::with_reserved].
+    pub fn new_with_reserved(width: u32, height: u32, reserved: &[Rect]) -> RectAllocator {
         RectAllocator {
-            empty_rects: vec![Rect::new(0, 0, width, height)],
+            packer: RectPacker::with_reserved(width, height, reserved),
             reserved_rects: HashMap::new(),
+            blank_glyphs: HashSet::new(),
+            draw_count: 0,
+            draw_evictions: 0,
+            held_frees: None,
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            glyph_count: self.reserved_rects.len(),
+            free_rect_count: self.packer.empty_rects().len(),
+            free_area: self.packer.free_area(),
+            largest_free_rect: self
+                .packer
+                .empty_rects()
+                .iter()
+                .max_by_key(|rect| rect.width() * rect.height())
+                .copied(),
+            total_area: self.packer.width() * self.packer.height(),
+        }
+    }
+
+    /// Starts a new draw. Glyphs used in the current draw are never
+    /// evicted to make space for others, since they're still waiting
+    /// to be copied from the texture.
+    pub fn begin_draw(&mut self) {
+        self.draw_count += 1;
+        self.draw_evictions = 0;
+    }
+
+    /// Keeps the rects of the glyphs removed from now on allocated,
+    /// instead of freeing them, until [RectAllocator::release_frees].
+    /// While draws are pending, the glyphs they use must stay where
+    /// they are in the texture until the draws are made, even if the
+    /// glyphs are removed from the cache in the meantime. No glyphs
+    /// are evicted to make space while frees are held, since that
+    /// wouldn't make any.
+    pub fn hold_frees(&mut self) {
+        self.held_frees.get_or_insert_with(Vec::new);
+    }
+
+    /// Frees the rects held since [RectAllocator::hold_frees], and
+    /// goes back to freeing rects right away.
+    pub fn release_frees(&mut self) {
+        if let Some(rects) = self.held_frees.take() {
+            self.packer.free_rects(&rects);
         }
     }
 
-    pub fn get_rect_in_texture(&mut self, glyph: GlyphPosition<Color>) -> CacheReservation {
-        let key = GlyphKey {
-            glyph: glyph.key,
-            color: glyph.user_data,
-        };
-        if let Some(already_reserved) = self.reserved_rects.get(&key) {
-            CacheReservation::AlreadyRasterized(*already_reserved)
-        } else if let Some(new_rect) = self.get_empty_slot(glyph.width as u32, glyph.height as u32)
-        {
-            self.reserved_rects.insert(key, new_rect);
+    /// Frees the rects, or holds them, see [RectAllocator::hold_frees].
+    fn free_rects(&mut self, rects: &[Rect]) {
+        match &mut self.held_frees {
+            Some(held_frees) => held_frees.extend_from_slice(rects),
+            None => self.packer.free_rects(rects),
+        }
+    }
+
+    /// Returns the amount of glyphs evicted to make space for new
+    /// glyphs since the last [RectAllocator::begin_draw].
+    pub fn draw_evictions(&self) -> usize {
+        self.draw_evictions
+    }
+
+    pub fn get_rect_in_texture(
+        &mut self,
+        key: GlyphKey,
+        width: u32,
+        height: u32,
+    ) -> CacheReservation {
+        if let Some(already_reserved) = self.reserved_rects.get_mut(&key) {
+            already_reserved.last_used = self.draw_count;
+            CacheReservation::AlreadyRasterized(already_reserved.rect)
+        } else if self.blank_glyphs.contains(&key) {
+            CacheReservation::Blank
+        } else if width > self.packer.width() || height > self.packer.height() {
+            // Checked before trying to find a slot, to avoid evicting
+            // every other glyph for nothing.
+            CacheReservation::TooLargeForAtlas
+        } else if let Some(new_rect) = self.get_empty_slot_evicting(width, height) {
+            let reservation = Reservation {
+                rect: new_rect,
+                last_used: self.draw_count,
+                metrics: None,
+            };
+            self.reserved_rects.insert(key, reservation);
             CacheReservation::EmptySpace(new_rect)
         } else {
             CacheReservation::OutOfSpace
         }
     }
 
-    fn get_empty_slot(&mut self, width: u32, height: u32) -> Option<Rect> {
-        let new_rect = if let Some(rect) = self
-            .empty_rects
-            .iter_mut().find(|rect| rect.width() >= width && rect.height() >= height)
-        {
-            let mut new_rect = *rect;
-            new_rect.resize(width, height);
-            new_rect
-        } else {
+    /// Finds an empty slot for the rect, evicting the least recently
+    /// used glyphs until it fits, if needed. Returns None if the rect
+    /// doesn't fit even after evicting every glyph that isn't used in
+    /// the current draw.
+    fn get_empty_slot_evicting(&mut self, width: u32, height: u32) -> Option<Rect> {
+        if let Some(rect) = self.packer.allocate(width, height) {
+            return Some(rect);
+        }
+        if self.held_frees.is_some() {
             return None;
-        };
-
-        // Remove entirely contained empty rects:
-        self.empty_rects
-            .retain(|rect| !new_rect.contains_rect(*rect));
-
-        // Split intersecting rects into surrounding rects:
-        // TODO(cleanup): Could use Vec::drain_filter here, once it's stable
-        let mut i = 0;
-        while i < self.empty_rects.len() {
-            if self.empty_rects[i].has_intersection(new_rect) {
-                let intersecting_rect = self.empty_rects.remove(i);
-
-                if intersecting_rect.left() < new_rect.left() {
-                    let mut new_empty = intersecting_rect;
-                    new_empty.set_width((new_rect.left() - intersecting_rect.left()) as u32);
-                    debug_assert!(!new_empty.has_intersection(new_rect));
-                    self.empty_rects.push(new_empty);
-                }
-
-                if intersecting_rect.right() > new_rect.right() {
-                    let mut new_empty = intersecting_rect;
-                    new_empty.set_width((intersecting_rect.right() - new_rect.right()) as u32);
-                    new_empty.set_x(new_rect.right());
-                    debug_assert!(!new_empty.has_intersection(new_rect));
-                    self.empty_rects.push(new_empty);
-                }
+        }
+        // All the glyphs last used in the same draw are evicted at
+        // once, they're likely to be from the same text anyway.
+        let mut old_draws = self
+            .reserved_rects
+            .values()
+            .map(|reserved| reserved.last_used)
+            .filter(|&last_used| last_used < self.draw_count)
+            .collect::<Vec<u64>>();
+        old_draws.sort_unstable();
+        old_draws.dedup();
 
-                if intersecting_rect.top() < new_rect.top() {
-                    let mut new_empty = intersecting_rect;
-                    new_empty.set_height((new_rect.top() - intersecting_rect.top()) as u32);
-                    debug_assert!(!new_empty.has_intersection(new_rect));
-                    self.empty_rects.push(new_empty);
+        // Freeing works out the empty space again from scratch, so the
+        // evicted rects are freed in batches: none until they add up
+        // to enough area for the rect, and after that, twice as many
+        // draws' glyphs each time the rect still doesn't fit.
+        let needed_area = width as u64 * height as u64;
+        let mut free_area = self.packer.free_area() as u64;
+        let mut evicted_rects = Vec::new();
+        let mut batch_size = 1;
+        let mut old_draws = old_draws.into_iter();
+        loop {
+            let draws = if free_area < needed_area {
+                1
+            } else {
+                batch_size
+            };
+            let mut evicted_any = false;
+            for oldest_draw in old_draws.by_ref().take(draws) {
+                let start = evicted_rects.len();
+                self.take_reservations(&mut evicted_rects, |_, reserved| {
+                    reserved.last_used == oldest_draw
+                });
+                let evicted = &evicted_rects[start..];
+                free_area += evicted
+                    .iter()
+                    .map(|rect| rect.width() as u64 * rect.height() as u64)
+                    .sum::<u64>();
+                self.draw_evictions += evicted.len();
+                evicted_any = true;
+            }
+            if free_area >= needed_area || !evicted_any {
+                self.packer.free_rects(&evicted_rects);
+                evicted_rects.clear();
+                if let Some(rect) = self.packer.allocate(width, height) {
+                    return Some(rect);
                 }
-
-                if intersecting_rect.bottom() > new_rect.bottom() {
-                    let mut new_empty = intersecting_rect;
-                    new_empty.set_height((intersecting_rect.bottom() - new_rect.bottom()) as u32);
-                    new_empty.set_y(new_rect.bottom());
-                    debug_assert!(!new_empty.has_intersection(new_rect));
-                    self.empty_rects.push(new_empty);
+                if !evicted_any {
+                    return None;
                 }
-            } else {
-                i += 1;
+                batch_size *= 2;
             }
         }
+    }
+
+    /// Packs the reserved glyphs again into an empty texture, tallest
+    /// first, which packs them much more tightly than the order they
+    /// happened to be cached in. Returns the old and new rects of the
+    /// glyphs. Glyphs that don't fit anymore are evicted.
+    pub fn repack(&mut self) -> Vec<(Rect, Rect)> {
+        let mut reservations = self
+            .reserved_rects
+            .iter()
+            .map(|(key, reserved)| (*key, reserved.rect))
+            .collect::<Vec<(GlyphKey, Rect)>>();
+        // Glyphs of the same size are packed in the order of their old
+        // positions, which are unique, so that the result doesn't
+        // depend on the HashMap's iteration order.
+        reservations
+            .sort_by_key(|(_, rect)| (Reverse((rect.height(), rect.width())), rect.y(), rect.x()));
 
-        // TODO(opt): Is the sort & consolidate really needed?
-        // TODO: Reclaiming unused areas
-        // TODO: Resizing the texture
-
-        // Sort the empty rects by size (smallest first, so small
-        // glyphs will fit into the small nooks and crannies if
-        // possible)
-        self.empty_rects.sort_by_key(|a| a.width() * a.height());
-
-        // Remove rects that are completely within another. Reasoning:
-        // this should avoid "fake small areas" that are created
-        // inside bigger areas by the splitting algorithm above.
-        let mut i = 1;
-        while i < self.empty_rects.len() {
-            let rect = self.empty_rects[i];
-            let mut j = 0;
-            while j < i {
-                if rect.contains_rect(self.empty_rects[j]) {
-                    self.empty_rects.remove(j);
-                    i -= 1;
-                } else {
-                    j += 1;
+        self.packer.clear();
+        // The held rects aren't allocated in the cleared packer, so
+        // freeing them later could free the rects of repacked glyphs.
+        if let Some(held_frees) = &mut self.held_frees {
+            held_frees.clear();
+        }
+        let mut moves = Vec::with_capacity(reservations.len());
+        for (key, old_rect) in reservations {
+            match self.packer.allocate(old_rect.width(), old_rect.height()) {
+                Some(new_rect) => {
+                    if let Some(reserved) = self.reserved_rects.get_mut(&key) {
+                        reserved.rect = new_rect;
+                    }
+                    moves.push((old_rect, new_rect));
+                }
+                None => {
+                    self.reserved_rects.remove(&key);
                 }
             }
-            i += 1;
         }
+        moves
+    }
+
+    /// Changes the size of the texture, and packs the reserved glyphs
+    /// into it like [RectAllocator::repack].
+    pub fn resize(&mut self, width: u32, height: u32) -> Vec<(Rect, Rect)> {
+        self.packer.resize(width, height);
+        self.repack()
+    }
 
-        Some(new_rect)
+    /// Returns the cached glyphs, and their rects in the texture.
+    pub fn reservations(&self) -> impl Iterator<Item = (&GlyphKey, Rect)> {
+        self.reserved_rects
+            .iter()
+            .map(|(key, reserved)| (key, reserved.rect))
+    }
+
+    /// Returns the glyphs which have been found to be blank.
+    pub fn blank_glyphs(&self) -> impl Iterator<Item = &GlyphKey> {
+        self.blank_glyphs.iter()
+    }
+
+    /// Stores the metrics of a glyph with a rect reserved for it, to
+    /// be returned by [RectAllocator::metrics] until it's evicted.
+    pub fn set_metrics(&mut self, key: &GlyphKey, metrics: Metrics) {
+        if let Some(reserved) = self.reserved_rects.get_mut(key) {
+            reserved.metrics = Some(metrics);
+        }
+    }
+
+    /// Returns the metrics stored with [RectAllocator::set_metrics].
+    pub fn metrics(&self, key: &GlyphKey) -> Option<Metrics> {
+        self.reserved_rects.get(key)?.metrics
+    }
+
+    /// Returns true if the glyph has been rasterized into the texture,
+    /// or found to be blank.
+    pub fn contains(&self, key: &GlyphKey) -> bool {
+        self.reserved_rects.contains_key(key) || self.blank_glyphs.contains(key)
+    }
+
+    /// Marks the glyph as blank, so that it isn't rasterized again,
+    /// and frees the rect reserved for it, if any.
+    pub fn mark_blank(&mut self, key: GlyphKey) {
+        if let Some(reserved) = self.reserved_rects.remove(&key) {
+            self.free_rects(&[reserved.rect]);
+        }
+        self.blank_glyphs.insert(key);
+    }
+
+    /// Removes the glyphs matching the predicate from the cache,
+    /// freeing up their space. Returns the amount of removed glyphs.
+    pub fn evict<F: FnMut(&GlyphKey) -> bool>(&mut self, mut predicate: F) -> usize {
+        self.blank_glyphs.retain(|key| !predicate(key));
+        self.remove_reservations(|key, _| predicate(key))
+    }
+
+    /// Removes the glyphs which haven't been used in the last
+    /// `max_age` draws from the cache. Returns the amount of removed
+    /// glyphs.
+    pub fn prune(&mut self, max_age: u64) -> usize {
+        let draw_count = self.draw_count;
+        self.remove_reservations(|_, reserved| draw_count - reserved.last_used > max_age)
+    }
+
+    /// Removes and frees the reservations matching the predicate.
+    /// Returns the amount of removed reservations.
+    fn remove_reservations<F>(&mut self, predicate: F) -> usize
+    where
+        F: FnMut(&GlyphKey, &Reservation) -> bool,
+    {
+        let mut freed_rects = Vec::new();
+        self.take_reservations(&mut freed_rects, predicate);
+        self.free_rects(&freed_rects);
+        freed_rects.len()
+    }
+
+    /// Removes the reservations matching the predicate, and adds
+    /// their rects to `rects`, without freeing them.
+    fn take_reservations<F>(&mut self, rects: &mut Vec<Rect>, mut predicate: F)
+    where
+        F: FnMut(&GlyphKey, &Reservation) -> bool,
+    {
+        self.reserved_rects.retain(|key, reserved| {
+            if predicate(key, reserved) {
+                rects.push(reserved.rect);
+                false
+            } else {
+                true
+            }
+        });
     }
 }
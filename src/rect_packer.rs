@@ -0,0 +1,469 @@
+use sdl2::rect::Rect;
+
+/// Packs rectangles into a fixed-size area, e.g. sprites into a
+/// texture atlas. This is the packer [`FontTexture`](crate::FontTexture)
+/// uses for its glyph cache.
+///
+/// The empty space is tracked as a list of maximal empty rectangles
+/// (which can overlap), and new rects are placed in the empty rect
+/// they fit into most snugly. When rects are freed, the empty space
+/// is worked out again from the rects that are still allocated, so
+/// freeing every rect makes the whole area available again. A shelf
+/// or skyline packer would be simpler, but it couldn't reuse the space
+/// of freed rects below its skyline, which the glyph cache needs for
+/// evicting glyphs.
+///
+/// Glyph-sized rects in a random order fill over 90% of the area
+/// before the first one doesn't fit, where the first-fit packer the
+/// glyph cache used before filled 88.4%. The `packing_benchmark`
+/// example compares the two with more rects:
+///
+/// ```
+/// # use fontdue_sdl2::RectPacker;
+/// let mut seed = 7u32;
+/// let mut random = |range: u32| {
+///     seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+///     (seed >> 16) % range
+/// };
+/// let sizes = (0..500).map(|_| (4 + random(20), 8 + random(20))).collect::<Vec<_>>();
+///
+/// let mut packer = RectPacker::new(256, 256);
+/// let packed_area: u32 = sizes
+///     .iter()
+///     .map_while(|&(width, height)| packer.allocate(width, height).map(|_| width * height))
+///     .sum();
+/// assert!(packed_area as f32 / (256 * 256) as f32 > 0.9);
+/// ```
+///
+/// ```
+/// # use fontdue_sdl2::RectPacker;
+/// let mut packer = RectPacker::new(64, 64);
+/// let a = packer.allocate(32, 64).unwrap();
+/// let b = packer.allocate(32, 32).unwrap();
+/// assert!(!a.has_intersection(b));
+/// // Only a 32x32 area is left.
+/// assert!(packer.allocate(64, 32).is_none());
+/// packer.free(a);
+/// assert!(packer.allocate(32, 64).is_some());
+/// ```
+#[derive(Clone)]
+pub struct RectPacker {
+    width: u32,
+    height: u32,
+    empty_rects: Vec<Rect>,
+    /// The rects returned by [`RectPacker::allocate`] that haven't
+    /// been freed, which the empty rects are rebuilt around when
+    /// rects are freed.
+    allocated: Vec<Rect>,
+    /// Areas that are never allocated, see [`RectPacker::with_reserved`].
+    reserved: Vec<Rect>,
+    /// Split-off empty rects thinner than this are discarded, see
+    /// [`RectPacker::set_min_split_size`].
+    min_split_size: u32,
+}
+
+impl RectPacker {
+    /// Creates a packer for an empty `width`x`height` area.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the area is empty, if either side is longer than an
+    /// SDL [`Rect`] can be (see [`sdl2::rect::max_int_value`]), or if
+    /// the area has more pixels than a `u32` can count.
+    ///
+    /// ```should_panic
+    /// # use fontdue_sdl2::RectPacker;
+    /// RectPacker::new(0, 0);
+    /// ```
+    pub fn new(width: u32, height: u32) -> RectPacker {
+        assert_valid_size(width, height);
+        RectPacker {
+            width,
+            height,
+            empty_rects: vec![Rect::new(0, 0, width, height)],
+            allocated: Vec::new(),
+            reserved: Vec::new(),
+            min_split_size: 0,
+        }
+    }
+
+    /// Creates a packer for a `width`x`height` area, which never
+    /// allocates rects overlapping the reserved areas, e.g. to keep
+    /// a part of a texture atlas for other sprites.
+    ///
+    /// ```
+    /// # use fontdue_sdl2::RectPacker;
+    /// # use sdl2::rect::Rect;
+    /// let sprites = Rect::new(0, 0, 32, 32);
+    /// let mut packer = RectPacker::with_reserved(64, 64, &[sprites]);
+    /// let mut allocated = Vec::new();
+    /// while let Some(rect) = packer.allocate(8, 8) {
+    ///     allocated.push(rect);
+    /// }
+    /// assert_eq!(allocated.len(), 48);
+    /// assert!(allocated.iter().all(|rect| !rect.has_intersection(sprites)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is invalid, see [`RectPacker::new`].
+    pub fn with_reserved(width: u32, height: u32, reserved: &[Rect]) -> RectPacker {
+        let mut packer = RectPacker::new(width, height);
+        packer.reserved = reserved.to_vec();
+        packer.clear();
+        packer
+    }
+
+    /// Returns the areas that are never allocated, see
+    /// [`RectPacker::with_reserved`].
+    pub fn reserved(&self) -> &[Rect] {
+        &self.reserved
+    }
+
+    /// Returns the width of the packed area.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the packed area.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Sets the minimum width and height of the empty rects split off
+    /// when a rect is allocated: thinner slivers of empty space are
+    /// discarded instead of tracked. The default is 0, which keeps
+    /// every empty area.
+    ///
+    /// Slivers thinner than any rect that will be allocated are never
+    /// used anyway, but they still make every allocation slower, as
+    /// the list of empty rects grows. Discarding them keeps the list
+    /// short, at the cost of the discarded area, which is only empty
+    /// space again once the rects around it are freed, since the
+    /// empty space is then worked out again without them.
+    ///
+    /// ```
+    /// # use fontdue_sdl2::RectPacker;
+    /// let mut tracking_slivers = RectPacker::new(256, 256);
+    /// let mut discarding_slivers = RectPacker::new(256, 256);
+    /// discarding_slivers.set_min_split_size(4);
+    /// let (mut tracked, mut discarded) = (Vec::new(), Vec::new());
+    /// for size in (5..30).cycle().take(200) {
+    ///     tracked.extend(tracking_slivers.allocate(size, size + 2));
+    ///     discarded.extend(discarding_slivers.allocate(size, size + 2));
+    /// }
+    /// assert!(discarding_slivers.empty_rects().len() < tracking_slivers.empty_rects().len());
+    /// assert_eq!(discarded.len(), tracked.len());
+    ///
+    /// // Once every rect is freed, the whole area is empty again.
+    /// discarding_slivers.free_rects(&discarded);
+    /// assert!(discarding_slivers.allocate(256, 256).is_some());
+    /// ```
+    ///
+    /// The `packing_benchmark` example compares the speed of packing
+    /// with and without discarding slivers.
+    pub fn set_min_split_size(&mut self, min_split_size: u32) {
+        self.min_split_size = min_split_size;
+    }
+
+    /// Frees every allocated rect at once, making the whole area
+    /// empty again, apart from the reserved areas.
+    pub fn clear(&mut self) {
+        self.allocated.clear();
+        self.rebuild_empty_rects();
+    }
+
+    /// Recalculates the empty rects from scratch, as the whole area
+    /// minus the reserved areas and the allocated rects.
+    fn rebuild_empty_rects(&mut self) {
+        self.empty_rects.clear();
+        self.empty_rects
+            .push(Rect::new(0, 0, self.width, self.height));
+        let allocated = std::mem::take(&mut self.allocated);
+        for reserved in self.reserved.clone() {
+            self.split_empty_rects(reserved);
+        }
+        for &rect in &allocated {
+            self.split_empty_rects(rect);
+        }
+        self.allocated = allocated;
+    }
+
+    /// Changes the size of the packed area, freeing every allocated
+    /// rect like [`RectPacker::clear`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is invalid, see [`RectPacker::new`].
+    pub fn resize(&mut self, width: u32, height: u32) {
+        assert_valid_size(width, height);
+        self.width = width;
+        self.height = height;
+        self.clear();
+    }
+
+    /// Returns the maximal empty rects the empty space is tracked
+    /// as. Note that these can overlap.
+    pub fn empty_rects(&self) -> &[Rect] {
+        &self.empty_rects
+    }
+
+    /// Returns the amount of pixels not covered by allocated rects or
+    /// reserved areas. Overlapping reserved areas are only counted
+    /// once.
+    ///
+    /// ```
+    /// # use fontdue_sdl2::RectPacker;
+    /// # use sdl2::rect::Rect;
+    /// let reserved = [Rect::new(0, 0, 32, 32), Rect::new(16, 16, 32, 32), Rect::new(48, 48, 32, 32)];
+    /// let mut packer = RectPacker::with_reserved(64, 64, &reserved);
+    /// assert_eq!(packer.free_area(), 64 * 64 - (32 * 32 * 2 - 16 * 16) - 16 * 16);
+    /// packer.allocate(16, 16).unwrap();
+    /// assert_eq!(packer.free_area(), 64 * 64 - (32 * 32 * 2 - 16 * 16) - 16 * 16 * 2);
+    /// ```
+    pub fn free_area(&self) -> u32 {
+        let area = Rect::new(0, 0, self.width, self.height);
+        let reserved = self
+            .reserved
+            .iter()
+            .filter_map(|rect| rect.intersection(area))
+            .collect::<Vec<_>>();
+        // The allocated rects are within the area, and don't overlap
+        // each other or the reserved areas.
+        let allocated = self
+            .allocated
+            .iter()
+            .map(|rect| rect.width() as u64 * rect.height() as u64)
+            .sum::<u64>();
+        let free_area = self.width as u64 * self.height as u64 - union_area(&reserved) - allocated;
+        // The area fits in a u32, see assert_valid_size.
+        free_area as u32
+    }
+
+    /// Reserves a `width`x`height` rect of empty space, and returns
+    /// it. Returns None if there isn't an empty area big enough for
+    /// the rect, or if the rect has no area.
+    pub fn allocate(&mut self, width: u32, height: u32) -> Option<Rect> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        // Pick the empty rect which the new rect fits into most
+        // snugly along its shorter leftover side, and then its longer
+        // one ("best short side fit"). This keeps the big empty areas
+        // intact for as long as possible, which packs much better than
+        // taking the first empty rect that fits.
+        let mut new_rect = self
+            .empty_rects
+            .iter()
+            .filter(|rect| rect.width() >= width && rect.height() >= height)
+            .min_by_key(|rect| {
+                let leftover_x = rect.width() - width;
+                let leftover_y = rect.height() - height;
+                (
+                    leftover_x.min(leftover_y),
+                    leftover_x.max(leftover_y),
+                    rect.y(),
+                    rect.x(),
+                )
+            })
+            .copied()?;
+        new_rect.resize(width, height);
+
+        self.split_empty_rects(new_rect);
+        self.allocated.push(new_rect);
+
+        Some(new_rect)
+    }
+
+    /// Removes the area of `new_rect` from the empty rects, splitting
+    /// the empty rects that intersect it into the (up to four)
+    /// largest rects around it.
+    fn split_empty_rects(&mut self, new_rect: Rect) {
+        // Split intersecting rects into surrounding rects:
+        let mut split_rects = Vec::new();
+        let mut i = 0;
+        while i < self.empty_rects.len() {
+            if self.empty_rects[i].has_intersection(new_rect) {
+                let intersecting_rect = self.empty_rects.swap_remove(i);
+
+                if intersecting_rect.left() < new_rect.left() {
+                    let mut new_empty = intersecting_rect;
+                    new_empty.set_width((new_rect.left() - intersecting_rect.left()) as u32);
+                    debug_assert!(!new_empty.has_intersection(new_rect));
+                    split_rects.push(new_empty);
+                }
+
+                if intersecting_rect.right() > new_rect.right() {
+                    let mut new_empty = intersecting_rect;
+                    new_empty.set_width((intersecting_rect.right() - new_rect.right()) as u32);
+                    new_empty.set_x(new_rect.right());
+                    debug_assert!(!new_empty.has_intersection(new_rect));
+                    split_rects.push(new_empty);
+                }
+
+                if intersecting_rect.top() < new_rect.top() {
+                    let mut new_empty = intersecting_rect;
+                    new_empty.set_height((new_rect.top() - intersecting_rect.top()) as u32);
+                    debug_assert!(!new_empty.has_intersection(new_rect));
+                    split_rects.push(new_empty);
+                }
+
+                if intersecting_rect.bottom() > new_rect.bottom() {
+                    let mut new_empty = intersecting_rect;
+                    new_empty.set_height((intersecting_rect.bottom() - new_rect.bottom()) as u32);
+                    new_empty.set_y(new_rect.bottom());
+                    debug_assert!(!new_empty.has_intersection(new_rect));
+                    split_rects.push(new_empty);
+                }
+            } else {
+                i += 1;
+            }
+        }
+
+        let min_size = self.min_split_size;
+        split_rects.retain(|rect| rect.width() >= min_size && rect.height() >= min_size);
+
+        // Drop split rects that are completely within another empty
+        // rect, they're just "fake small areas" of a bigger one. The
+        // rects that weren't split can't be within a split rect,
+        // since none of them are within each other to begin with, so
+        // only the split rects need to be checked.
+        let mut i = 0;
+        while i < split_rects.len() {
+            let rect = split_rects[i];
+            let within_old = self.empty_rects.iter().any(|old| old.contains_rect(rect));
+            // For identical split rects, only the last one is kept.
+            let within_split = split_rects
+                .iter()
+                .enumerate()
+                .any(|(j, other)| j != i && other.contains_rect(rect) && (*other != rect || j > i));
+            if within_old || within_split {
+                split_rects.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        self.empty_rects.append(&mut split_rects);
+    }
+
+    /// Returns a rect returned by [`RectPacker::allocate`] to the
+    /// empty space, so that it can be allocated again. Rects that
+    /// aren't currently allocated are ignored.
+    ///
+    /// The empty space is recalculated around the rects that are still
+    /// allocated, so the freed area is merged with the empty space
+    /// around it, and can fit bigger rects than the one that was
+    /// freed:
+    ///
+    /// ```
+    /// # use fontdue_sdl2::RectPacker;
+    /// let mut packer = RectPacker::new(64, 64);
+    /// let small = packer.allocate(10, 10).unwrap();
+    /// assert!(packer.allocate(64, 64).is_none());
+    /// packer.free(small);
+    /// assert!(packer.allocate(64, 64).is_some());
+    /// ```
+    ///
+    /// Afterwards, the empty rects and the allocated rects still
+    /// cover the whole area between them, without overlapping each
+    /// other:
+    ///
+    /// ```
+    /// # use fontdue_sdl2::RectPacker;
+    /// let mut packer = RectPacker::new(32, 32);
+    /// let mut allocated = Vec::new();
+    /// for size in (1..8).cycle().take(40) {
+    ///     allocated.extend(packer.allocate(size, 9 - size));
+    /// }
+    /// for rect in allocated.iter().step_by(3) {
+    ///     packer.free(*rect);
+    /// }
+    /// let allocated = allocated
+    ///     .iter()
+    ///     .enumerate()
+    ///     .filter(|(i, _)| i % 3 != 0)
+    ///     .map(|(_, rect)| *rect)
+    ///     .collect::<Vec<_>>();
+    /// for (i, rect) in allocated.iter().enumerate() {
+    ///     assert!(allocated[i + 1..].iter().all(|other| !rect.has_intersection(*other)));
+    ///     assert!(packer.empty_rects().iter().all(|empty| !rect.has_intersection(*empty)));
+    /// }
+    /// for y in 0..32 {
+    ///     for x in 0..32 {
+    ///         let point = sdl2::rect::Point::new(x, y);
+    ///         let used = allocated.iter().any(|rect| rect.contains_point(point));
+    ///         let empty = packer.empty_rects().iter().any(|rect| rect.contains_point(point));
+    ///         assert!(used != empty);
+    ///     }
+    /// }
+    /// ```
+    pub fn free(&mut self, rect: Rect) {
+        self.free_rects(&[rect]);
+    }
+
+    /// Frees each of the rects like [`RectPacker::free`], but only
+    /// recalculates the empty space once, which is faster than
+    /// freeing them one by one.
+    pub fn free_rects(&mut self, rects: &[Rect]) {
+        let allocated_count = self.allocated.len();
+        self.allocated
+            .retain(|allocated| !rects.contains(allocated));
+        if self.allocated.len() != allocated_count {
+            self.rebuild_empty_rects();
+        }
+    }
+}
+
+/// Panics if a packer can't be `width`x`height`, see
+/// [`RectPacker::new`].
+fn assert_valid_size(width: u32, height: u32) {
+    assert!(
+        width > 0 && height > 0,
+        "the packed area {}x{} is empty",
+        width,
+        height
+    );
+    let max_side = sdl2::rect::max_int_value();
+    assert!(
+        width <= max_side && height <= max_side && width.checked_mul(height).is_some(),
+        "the packed area {}x{} is too large",
+        width,
+        height
+    );
+}
+
+/// Returns the amount of pixels covered by at least one of the rects,
+/// by summing up the covered lengths of each column between the rects'
+/// vertical edges.
+fn union_area(rects: &[Rect]) -> u64 {
+    let mut edges = rects
+        .iter()
+        .flat_map(|rect| [rect.left(), rect.right()])
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges.dedup();
+    let mut area = 0;
+    let mut spans = Vec::new();
+    for columns in edges.windows(2) {
+        let (left, right) = (columns[0], columns[1]);
+        spans.clear();
+        spans.extend(
+            rects
+                .iter()
+                .filter(|rect| rect.left() <= left && rect.right() >= right)
+                .map(|rect| (rect.top(), rect.bottom())),
+        );
+        spans.sort_unstable();
+        let mut covered = 0;
+        let mut covered_until = i32::MIN;
+        for &(top, bottom) in &spans {
+            let top = top.max(covered_until);
+            if bottom > top {
+                covered += (bottom - top) as u64;
+                covered_until = bottom;
+            }
+        }
+        area += covered * (right - left) as u64;
+    }
+    area
+}
@@ -0,0 +1,178 @@
+use sdl2::rect::Rect;
+
+/// Statistics about the glyph cache of a
+/// [`FontTexture`](crate::FontTexture), returned by
+/// [`FontTexture::cache_stats`](crate::FontTexture::cache_stats).
+///
+/// Useful for tuning the atlas size: if `largest_free_rect` is
+/// getting smaller than the glyphs you draw, new glyphs will soon
+/// stop fitting in the cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The amount of glyphs currently stored in the cache.
+    pub glyph_count: usize,
+    /// The amount of empty rectangles tracked by the allocator. Note
+    /// that these can overlap.
+    pub free_rect_count: usize,
+    /// The amount of pixels in the cache texture not reserved by any
+    /// glyph, or by
+    /// [`FontTextureBuilder::reserved_areas`](crate::FontTextureBuilder::reserved_areas).
+    /// Pixels covered by more than one reserved area are only
+    /// counted once:
+    ///
+    /// ```
+    /// # use fontdue_sdl2::FontTextureBuilder;
+    /// # use sdl2::pixels::PixelFormatEnum;
+    /// # use sdl2::rect::Rect;
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(64, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// let font_texture = FontTextureBuilder::new()
+    ///     .size(64, 64)
+    ///     .reserved_areas(&[Rect::new(0, 0, 64, 48), Rect::new(0, 32, 64, 32)])
+    ///     .build(&texture_creator)
+    ///     .unwrap();
+    /// let stats = font_texture.cache_stats();
+    /// assert_eq!(stats.free_area, 0);
+    /// assert_eq!(stats.occupancy(), 1.0);
+    /// ```
+    pub free_area: u32,
+    /// The largest empty rectangle, i.e. the biggest glyph that could
+    /// still fit. None if the cache is full.
+    pub largest_free_rect: Option<Rect>,
+    /// The amount of pixels in the cache texture.
+    pub total_area: u32,
+}
+
+impl CacheStats {
+    /// Returns the fraction of the cache texture reserved by glyphs,
+    /// between 0 (empty) and 1 (full).
+    ///
+    /// ```
+    /// # use fontdue::{Font, layout::{Layout, TextStyle, CoordinateSystem}};
+    /// # use fontdue_sdl2::FontTexture;
+    /// # use sdl2::pixels::{Color, PixelFormatEnum};
+    /// # use sdl2::surface::Surface;
+    /// # let surface = Surface::new(256, 64, PixelFormatEnum::RGBA32).unwrap();
+    /// # let mut canvas = surface.into_canvas().unwrap();
+    /// # let texture_creator = canvas.texture_creator();
+    /// # let font = include_bytes!("../examples/roboto/Roboto-Regular.ttf") as &[u8];
+    /// # let fonts = &[Font::from_bytes(font, Default::default()).unwrap()];
+    /// let mut font_texture = FontTexture::with_size(&texture_creator, 256, 256).unwrap();
+    /// let empty = font_texture.cache_stats();
+    /// assert_eq!((empty.occupancy(), empty.fragmentation()), (0.0, 0.0));
+    ///
+    /// let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+    /// layout.append(fonts, &TextStyle::with_user_data("abc", 16.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let some = font_texture.cache_stats();
+    ///
+    /// layout.append(fonts, &TextStyle::with_user_data("defghijklmnop", 16.0, 0, Color::WHITE));
+    /// font_texture.draw_text(&mut canvas, fonts, layout.glyphs()).unwrap();
+    /// let more = font_texture.cache_stats();
+    /// assert!(empty.occupancy() < some.occupancy() && some.occupancy() < more.occupancy());
+    /// assert!(more.fragmentation() > 0.0);
+    /// ```
+    ///
+    /// Returns 0 for a cache with no area at all:
+    ///
+    /// ```
+    /// # use fontdue_sdl2::CacheStats;
+    /// let stats = CacheStats {
+    ///     glyph_count: 0,
+    ///     free_rect_count: 0,
+    ///     free_area: 0,
+    ///     largest_free_rect: None,
+    ///     total_area: 0,
+    /// };
+    /// assert_eq!(stats.occupancy(), 0.0);
+    /// ```
+    pub fn occupancy(&self) -> f32 {
+        if self.total_area == 0 {
+            return 0.0;
+        }
+        1.0 - self.free_area as f32 / self.total_area as f32
+    }
+
+    /// Returns how chopped up the free space of the cache texture is,
+    /// between 0 and 1: 0 when all of it is in one rectangle, and
+    /// close to 1 when it's scattered into many small gaps between
+    /// glyphs, which new glyphs might not fit in despite the free
+    /// area. Calculated as 1 - (largest free rectangle's area / free
+    /// area), so it's 0 for a full cache as well.
+    ///
+    /// If this is high while [`CacheStats::occupancy`] isn't,
+    /// [`FontTexture::defragment`](crate::FontTexture::defragment)
+    /// packs the cached glyphs tightly again, without rasterizing
+    /// them again.
+    pub fn fragmentation(&self) -> f32 {
+        match self.largest_free_rect {
+            Some(rect) if self.free_area > 0 => {
+                1.0 - (rect.width() * rect.height()) as f32 / self.free_area as f32
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// A summary of a single draw, returned by
+/// [`FontTexture::draw_text_checked`](crate::FontTexture::draw_text_checked).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DrawReport {
+    /// The amount of glyphs drawn from the glyph cache.
+    pub drawn_glyphs: usize,
+    /// The characters of the glyphs that didn't fit in the glyph
+    /// cache, and were drawn as rectangles instead (see
+    /// [`MissingGlyphStyle`](crate::MissingGlyphStyle)). If this isn't
+    /// empty, the glyph cache is too small for the text being drawn,
+    /// or some glyphs are bigger than the whole glyph cache texture.
+    pub missing_glyphs: Vec<char>,
+    /// The amount of glyphs evicted from the glyph cache to make space
+    /// for the glyphs of this draw. Glyphs are evicted when the cache
+    /// is full, starting from the least recently drawn ones. If this
+    /// is nonzero every frame, the text drawn each frame doesn't fit
+    /// in the cache at once, and a bigger cache would avoid
+    /// rasterizing the same glyphs over and over.
+    pub evicted_glyphs: usize,
+    /// The amount of glyphs left out of this draw because they
+    /// weren't cached yet, and the raster budget (see
+    /// [`FontTexture::set_raster_budget`](crate::FontTexture::set_raster_budget))
+    /// was used up by other glyphs.
+    pub deferred_glyphs: usize,
+    /// The amount of calls made to the renderer to copy the glyphs
+    /// onto the canvas: one per glyph, or one for all of them with
+    /// [`FontTexture::set_batched`](crate::FontTexture::set_batched).
+    /// Doesn't include drawing decorations, highlights, or the
+    /// rectangles of missing glyphs.
+    pub draw_calls: usize,
+}
+
+/// Counters for the draws of a frame, returned by
+/// [`FontTexture::last_frame_stats`](crate::FontTexture::last_frame_stats),
+/// e.g. for a performance overlay.
+///
+/// Unlike a [`DrawReport`], the glyphs rasterized and uploaded by
+/// other functions, like
+/// [`FontTexture::cache_glyphs`](crate::FontTexture::cache_glyphs),
+/// are counted too, if they're called between
+/// [`FontTexture::begin_frame`](crate::FontTexture::begin_frame) and
+/// the end of the frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// The amount of glyphs drawn from the glyph cache, like
+    /// [`DrawReport::drawn_glyphs`].
+    pub drawn_glyphs: usize,
+    /// The amount of glyphs rasterized into the glyph cache, i.e. the
+    /// glyphs that weren't cached yet.
+    pub rasterized_glyphs: usize,
+    /// The amount of bytes of pixels uploaded to the glyph cache
+    /// texture.
+    pub uploaded_bytes: usize,
+    /// The amount of glyphs that didn't fit in the glyph cache, like
+    /// [`DrawReport::missing_glyphs`].
+    pub missing_glyphs: usize,
+    /// The amount of calls made to the renderer to copy glyphs onto
+    /// the canvas, like [`DrawReport::draw_calls`].
+    pub draw_calls: usize,
+}
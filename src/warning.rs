@@ -0,0 +1,68 @@
+use std::fmt;
+
+/// Problems encountered while drawing, which don't stop the draw, but
+/// leave some glyphs undrawn or drawn as placeholders. Passed to the
+/// handler set with
+/// [`FontTexture::set_warning_handler`](crate::FontTexture::set_warning_handler),
+/// or logged if there isn't one.
+///
+/// The [`Display`](fmt::Display) implementation describes the
+/// warning like the logged message.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Warning {
+    /// The glyph didn't fit in the glyph cache, even after evicting
+    /// the glyphs not used in the current draw, and was drawn as a
+    /// placeholder instead (see
+    /// [`MissingGlyphStyle`](crate::MissingGlyphStyle)).
+    OutOfSpace {
+        /// The character the glyph is for.
+        character: char,
+        /// The size the glyph was rasterized at.
+        px: f32,
+        /// The index of the glyph's font in the fonts.
+        font_index: usize,
+    },
+    /// The glyph was too large to rasterize, most likely because the
+    /// layout is malformed, and was skipped.
+    GlyphTooLarge {
+        /// The character the glyph is for.
+        character: char,
+        /// The size the glyph would have been rasterized at.
+        px: f32,
+        /// The index of the glyph's font in the fonts.
+        font_index: usize,
+        /// The width of the glyph's bitmap.
+        width: u32,
+        /// The height of the glyph's bitmap.
+        height: u32,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The chars are debug-formatted, so that e.g. control
+        // characters are escaped instead of printed as is.
+        match self {
+            Warning::OutOfSpace {
+                character,
+                px,
+                font_index,
+            } => write!(
+                f,
+                "Glyph cache cannot fit {:?} (U+{:04X}, size {}, font {})",
+                character, *character as u32, px, font_index,
+            ),
+            Warning::GlyphTooLarge {
+                character,
+                px,
+                font_index,
+                width,
+                height,
+            } => write!(
+                f,
+                "Glyph {:?} (U+{:04X}, size {}, font {}) is too large to draw ({}x{}), skipping it",
+                character, *character as u32, px, font_index, width, height,
+            ),
+        }
+    }
+}